@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::CostRecord;
+
+/// Label used for records that cannot be attributed to a group.
+pub const UNATTRIBUTED: &str = "unattributed";
+
+/// Calendar period used to group costs over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    /// Calendar day, labelled `YYYY-MM-DD`.
+    Day,
+    /// ISO week starting on Monday, labelled with the Monday's date.
+    Week,
+    /// Calendar month, labelled `YYYY-MM`.
+    Month,
+}
+
+impl Period {
+    /// Returns the label of the period containing `time`.
+    pub fn label(&self, time: DateTime<Utc>) -> String {
+        let date = time.date_naive();
+        match self {
+            Self::Day => date.format("%Y-%m-%d").to_string(),
+            Self::Week => {
+                let monday =
+                    date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+                monday.format("%Y-%m-%d").to_string()
+            }
+            Self::Month => date.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// Dimension used to group [`CostRecord`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupBy {
+    /// Billing source (Pod, endpoint or network volume).
+    Source,
+    /// Pod ID.
+    Pod,
+    /// Serverless endpoint ID.
+    Endpoint,
+    /// GPU type ID.
+    GpuType,
+    /// Template ID.
+    Template,
+    /// Container image name.
+    Image,
+    /// Calendar period.
+    Period(Period),
+}
+
+impl GroupBy {
+    /// Returns the group key of `record`, or [`UNATTRIBUTED`] if unknown.
+    pub fn key(&self, record: &CostRecord) -> String {
+        let key = match self {
+            Self::Source => Some(record.source.as_str().to_string()),
+            Self::Pod => record.pod_id.clone(),
+            Self::Endpoint => record.endpoint_id.clone(),
+            Self::GpuType => record.gpu_type_id.clone(),
+            Self::Template => record.template_id.clone(),
+            Self::Image => record.image_name.clone(),
+            Self::Period(period) => Some(period.label(record.time)),
+        };
+        key.unwrap_or_else(|| UNATTRIBUTED.to_string())
+    }
+}
+
+/// Aggregated costs of one group.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostSummary {
+    /// Group key, see [`GroupBy::key`].
+    pub key: String,
+
+    /// Total amount charged, in USD.
+    pub amount: f64,
+
+    /// Total GPU-hours billed.
+    pub gpu_hours: f64,
+
+    /// Number of billing records in the group.
+    pub records: usize,
+}
+
+impl CostSummary {
+    fn add(&mut self, record: &CostRecord) {
+        self.amount += record.amount;
+        self.gpu_hours += record.gpu_hours();
+        self.records += 1;
+    }
+}
+
+/// Groups `records` by `group_by`, sorted by descending amount, then key.
+pub fn aggregate<'a>(
+    records: impl IntoIterator<Item = &'a CostRecord>,
+    group_by: GroupBy,
+) -> Vec<CostSummary> {
+    let mut groups: BTreeMap<String, CostSummary> = BTreeMap::new();
+    for record in records {
+        let key = group_by.key(record);
+        groups
+            .entry(key.clone())
+            .or_insert_with(|| CostSummary {
+                key,
+                ..Default::default()
+            })
+            .add(record);
+    }
+
+    let mut summaries: Vec<_> = groups.into_values().collect();
+    summaries.sort_by(|a, b| {
+        b.amount
+            .total_cmp(&a.amount)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::analytics::CostSource;
+
+    fn record(source: CostSource, day: u32, amount: f64, ms: i64, gpu: Option<&str>) -> CostRecord {
+        CostRecord {
+            source,
+            time: Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap(),
+            amount,
+            time_billed_ms: Some(ms),
+            disk_space_billed_gb: None,
+            pod_id: None,
+            endpoint_id: None,
+            gpu_type_id: gpu.map(str::to_string),
+            template_id: None,
+            image_name: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_by_gpu_type() {
+        let records = [
+            record(CostSource::Pod, 1, 1.0, 3_600_000, Some("A100")),
+            record(CostSource::Endpoint, 2, 3.0, 7_200_000, Some("A100")),
+            record(CostSource::Pod, 3, 2.0, 1_800_000, Some("H100")),
+            record(CostSource::NetworkVolume, 3, 0.5, 3_600_000, None),
+        ];
+
+        let summaries = aggregate(&records, GroupBy::GpuType);
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].key, "A100");
+        assert_eq!(summaries[0].amount, 4.0);
+        assert_eq!(summaries[0].gpu_hours, 3.0);
+        assert_eq!(summaries[2].key, UNATTRIBUTED);
+        assert_eq!(summaries[2].gpu_hours, 0.0);
+    }
+
+    #[test]
+    fn test_period_labels() {
+        // 2025-01-08 is a Wednesday.
+        let time = Utc.with_ymd_and_hms(2025, 1, 8, 13, 0, 0).unwrap();
+        assert_eq!(Period::Day.label(time), "2025-01-08");
+        assert_eq!(Period::Week.label(time), "2025-01-06");
+        assert_eq!(Period::Month.label(time), "2025-01");
+    }
+}
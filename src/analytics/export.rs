@@ -0,0 +1,158 @@
+use std::io::Write;
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+
+use super::{CostRecord, CostSummary};
+use crate::Result;
+
+/// A type that can be written as a CSV row.
+pub trait CsvRow {
+    /// Column names, in order.
+    fn header() -> &'static [&'static str];
+
+    /// Column values, in the same order as [`CsvRow::header`].
+    fn fields(&self) -> Vec<String>;
+}
+
+impl CsvRow for CostRecord {
+    fn header() -> &'static [&'static str] {
+        &[
+            "source",
+            "time",
+            "amount",
+            "timeBilledMs",
+            "gpuHours",
+            "diskSpaceBilledGb",
+            "podId",
+            "endpointId",
+            "gpuTypeId",
+            "templateId",
+            "imageName",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.source.as_str().to_string(),
+            self.time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.amount.to_string(),
+            optional(&self.time_billed_ms),
+            self.gpu_hours().to_string(),
+            optional(&self.disk_space_billed_gb),
+            optional(&self.pod_id),
+            optional(&self.endpoint_id),
+            optional(&self.gpu_type_id),
+            optional(&self.template_id),
+            optional(&self.image_name),
+        ]
+    }
+}
+
+impl CsvRow for CostSummary {
+    fn header() -> &'static [&'static str] {
+        &["key", "amount", "gpuHours", "records"]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.key.clone(),
+            self.amount.to_string(),
+            self.gpu_hours.to_string(),
+            self.records.to_string(),
+        ]
+    }
+}
+
+/// Writes `rows` as RFC 4180 CSV with a header line.
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_csv<'a, T, W>(mut writer: W, rows: impl IntoIterator<Item = &'a T>) -> Result<()>
+where
+    T: CsvRow + 'a,
+    W: Write,
+{
+    write_line(&mut writer, T::header().iter().map(|s| s.to_string()))?;
+    for row in rows {
+        write_line(&mut writer, row.fields())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes `rows` as JSON Lines, one JSON object per line.
+///
+/// # Errors
+///
+/// Returns an error if serialization or writing fails.
+pub fn write_json_lines<'a, T, W>(
+    mut writer: W,
+    rows: impl IntoIterator<Item = &'a T>,
+) -> Result<()>
+where
+    T: Serialize + 'a,
+    W: Write,
+{
+    for row in rows {
+        serde_json::to_writer(&mut writer, row)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_line(writer: &mut impl Write, fields: impl IntoIterator<Item = String>) -> Result<()> {
+    let line: Vec<String> = fields.into_iter().map(|field| escape(&field)).collect();
+    writer.write_all(line.join(",").as_bytes())?;
+    writer.write_all(b"\r\n")?;
+    Ok(())
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_csv_escapes_fields() -> Result<()> {
+        let summaries = [CostSummary {
+            key: "repo/image:\"latest\", v2".to_string(),
+            amount: 1.5,
+            gpu_hours: 2.0,
+            records: 3,
+        }];
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &summaries)?;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "key,amount,gpuHours,records\r\n\"repo/image:\"\"latest\"\", v2\",1.5,2,3\r\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_json_lines() -> Result<()> {
+        let summaries = [CostSummary::default(), CostSummary::default()];
+
+        let mut out = Vec::new();
+        write_json_lines(&mut out, &summaries)?;
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.starts_with(r#"{"key":"","amount":0.0"#));
+        Ok(())
+    }
+}
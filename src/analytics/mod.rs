@@ -0,0 +1,241 @@
+//! Billing analytics: cost attribution, aggregation and export.
+//!
+//! A [`BillingReport`] merges Pod, serverless endpoint and network volume
+//! billing over a time range into uniform [`CostRecord`]s, attributes them to
+//! templates, images and GPU types, and aggregates them by any [`GroupBy`]
+//! dimension. Records and summaries can be exported as CSV or JSON Lines.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use chrono::{Duration, Utc};
+//! use runpod_sdk::analytics::{BillingRange, BillingReport, GroupBy, Period};
+//! use runpod_sdk::model::v1::BucketSize;
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//!
+//! let end = Utc::now();
+//! let range = BillingRange::new(end - Duration::days(30), end, BucketSize::Day);
+//! let report = BillingReport::collect(&client, &range).await?;
+//!
+//! println!("total: ${:.2}, {:.1} GPU-hours", report.total(), report.gpu_hours());
+//! for summary in report.aggregate(GroupBy::Period(Period::Week)) {
+//!     println!("{}: ${:.2}", summary.key, summary.amount);
+//! }
+//!
+//! report.write_csv(std::fs::File::create("costs.csv")?)?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod aggregate;
+mod export;
+mod record;
+mod window;
+
+use std::io::Write;
+
+pub use aggregate::{CostSummary, GroupBy, Period, UNATTRIBUTED, aggregate};
+use chrono::{DateTime, SecondsFormat, Utc};
+pub use export::{CsvRow, write_csv, write_json_lines};
+pub(crate) use record::parse_time;
+pub use record::{Attribution, CostRecord, CostSource, ResourceDetails};
+use serde::{Deserialize, Serialize};
+pub use window::{BillingWindows, BucketLimits};
+
+use crate::Result;
+use crate::model::v1::{
+    BillingGrouping, BillingRecord, BucketSize, EndpointBillingQuery, ListEndpointsQuery,
+    ListPodsQuery, NetworkVolumeBillingQuery, PodBillingQuery,
+};
+use crate::service::v1::{BillingService, EndpointsService, PodsService};
+
+/// Time range and bucket size of a billing query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingRange {
+    /// Start of the range.
+    pub start: DateTime<Utc>,
+
+    /// End of the range.
+    pub end: DateTime<Utc>,
+
+    /// Size of the billing buckets.
+    pub bucket_size: BucketSize,
+}
+
+impl BillingRange {
+    /// Creates a billing range.
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>, bucket_size: BucketSize) -> Self {
+        Self {
+            start,
+            end,
+            bucket_size,
+        }
+    }
+
+    /// Returns a Pod billing query grouped by Pod over this range.
+    pub fn pod_query(&self) -> PodBillingQuery {
+        PodBillingQuery {
+            bucket_size: Some(self.bucket_size),
+            end_time: Some(format_time(self.end)),
+            grouping: Some(BillingGrouping::PodId),
+            start_time: Some(format_time(self.start)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an endpoint billing query grouped by endpoint over this range.
+    pub fn endpoint_query(&self) -> EndpointBillingQuery {
+        EndpointBillingQuery {
+            bucket_size: Some(self.bucket_size),
+            end_time: Some(format_time(self.end)),
+            grouping: Some(BillingGrouping::EndpointId),
+            start_time: Some(format_time(self.start)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a network volume billing query over this range.
+    pub fn volume_query(&self) -> NetworkVolumeBillingQuery {
+        NetworkVolumeBillingQuery {
+            bucket_size: Some(self.bucket_size),
+            end_time: Some(format_time(self.end)),
+            start_time: Some(format_time(self.start)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Merged, attributed billing of an account over a time range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingReport {
+    /// Range the report covers.
+    pub range: BillingRange,
+
+    /// Cost records of all sources, ordered by time, then source.
+    pub records: Vec<CostRecord>,
+}
+
+impl BillingReport {
+    /// Fetches Pod, endpoint and network volume billing concurrently and
+    /// attributes it using the account's current Pods and endpoints.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails or a record has an invalid time.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn collect<C>(client: &C, range: &BillingRange) -> Result<Self>
+    where
+        C: BillingService + PodsService + EndpointsService,
+    {
+        let endpoints_query = ListEndpointsQuery {
+            include_template: Some(true),
+            ..Default::default()
+        };
+        let (pods, endpoints) = futures::try_join!(
+            client.list_pods(ListPodsQuery::default()),
+            client.list_endpoints(endpoints_query),
+        )?;
+
+        Self::collect_with(client, range, &Attribution::new(&pods, &endpoints)).await
+    }
+
+    /// Fetches Pod, endpoint and network volume billing concurrently and
+    /// attributes it using `attribution`.
+    ///
+    /// Long ranges are split into API-sized windows with the default
+    /// [`BillingWindows`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails or a record has an invalid time.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn collect_with<C>(
+        client: &C,
+        range: &BillingRange,
+        attribution: &Attribution,
+    ) -> Result<Self>
+    where
+        C: BillingService,
+    {
+        let windows = BillingWindows::default();
+        let (pods, endpoints, volumes) = futures::try_join!(
+            windows.pod_billing(client, range.pod_query()),
+            windows.endpoint_billing(client, range.endpoint_query()),
+            windows.volume_billing(client, range.volume_query()),
+        )?;
+
+        Self::from_records(range.clone(), &pods, &endpoints, &volumes, attribution)
+    }
+
+    /// Builds a report from already fetched billing records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a record has an invalid time.
+    pub fn from_records(
+        range: BillingRange,
+        pods: &[BillingRecord],
+        endpoints: &[BillingRecord],
+        volumes: &[BillingRecord],
+        attribution: &Attribution,
+    ) -> Result<Self> {
+        let sources = [
+            (CostSource::Pod, pods),
+            (CostSource::Endpoint, endpoints),
+            (CostSource::NetworkVolume, volumes),
+        ];
+        let mut records = sources
+            .iter()
+            .flat_map(|(source, records)| {
+                records
+                    .iter()
+                    .map(|record| CostRecord::from_billing(*source, record, attribution))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        records.sort_by(|a, b| a.time.cmp(&b.time).then(a.source.cmp(&b.source)));
+
+        Ok(Self { range, records })
+    }
+
+    /// Total amount charged, in USD.
+    pub fn total(&self) -> f64 {
+        self.records.iter().map(|record| record.amount).sum()
+    }
+
+    /// Total GPU-hours billed.
+    pub fn gpu_hours(&self) -> f64 {
+        self.records.iter().map(CostRecord::gpu_hours).sum()
+    }
+
+    /// Aggregates the records by `group_by`, sorted by descending amount.
+    pub fn aggregate(&self, group_by: GroupBy) -> Vec<CostSummary> {
+        aggregate(&self.records, group_by)
+    }
+
+    /// Writes all records as CSV.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_csv(&self, writer: impl Write) -> Result<()> {
+        write_csv(writer, &self.records)
+    }
+
+    /// Writes all records as JSON Lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails.
+    pub fn write_json_lines(&self, writer: impl Write) -> Result<()> {
+        write_json_lines(writer, &self.records)
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
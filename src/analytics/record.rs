@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::AccountSnapshot;
+use crate::model::v1::{BillingRecord, Endpoint, Pod};
+use crate::pricing::gpu_type_key;
+use crate::{Error, Result};
+
+/// Billing API a [`CostRecord`] originates from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CostSource {
+    /// Pod billing.
+    Pod,
+    /// Serverless endpoint billing.
+    Endpoint,
+    /// Network volume billing.
+    NetworkVolume,
+}
+
+impl CostSource {
+    /// Returns the source name used in reports and exports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pod => "pod",
+            Self::Endpoint => "endpoint",
+            Self::NetworkVolume => "networkVolume",
+        }
+    }
+}
+
+/// A billing record from any source, enriched with attribution details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostRecord {
+    /// Billing API the record comes from.
+    pub source: CostSource,
+
+    /// Start of the billing bucket.
+    pub time: DateTime<Utc>,
+
+    /// Amount charged, in USD.
+    pub amount: f64,
+
+    /// Time billed, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_billed_ms: Option<i64>,
+
+    /// Disk space billed, in gigabytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_space_billed_gb: Option<i32>,
+
+    /// Pod the cost is attributed to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_id: Option<String>,
+
+    /// Serverless endpoint the cost is attributed to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+
+    /// GPU type, from the record or the attributed resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_type_id: Option<String>,
+
+    /// Template of the attributed resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+
+    /// Container image of the attributed resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_name: Option<String>,
+}
+
+impl CostRecord {
+    /// Converts a raw [`BillingRecord`], filling in details from `attribution`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Validation`] if the record time is not an ISO 8601 datetime or date.
+    pub fn from_billing(
+        source: CostSource,
+        record: &BillingRecord,
+        attribution: &Attribution,
+    ) -> Result<Self> {
+        let resource = match source {
+            CostSource::Pod => record
+                .pod_id
+                .as_ref()
+                .and_then(|id| attribution.pods.get(id)),
+            CostSource::Endpoint => record
+                .endpoint_id
+                .as_ref()
+                .and_then(|id| attribution.endpoints.get(id)),
+            CostSource::NetworkVolume => None,
+        };
+        let resource = resource.cloned().unwrap_or_default();
+
+        Ok(Self {
+            source,
+            time: parse_time(&record.time)?,
+            amount: record.amount,
+            time_billed_ms: record.time_billed_ms,
+            disk_space_billed_gb: record.disk_space_billed_gb,
+            pod_id: record.pod_id.clone(),
+            endpoint_id: record.endpoint_id.clone().or(resource.endpoint_id),
+            gpu_type_id: record.gpu_type_id.clone().or(resource.gpu_type_id),
+            template_id: resource.template_id,
+            image_name: resource.image_name,
+        })
+    }
+
+    /// GPU-hours billed by this record.
+    ///
+    /// Computed from `time_billed_ms` for Pod and endpoint records; network
+    /// volume records never account for GPU time.
+    pub fn gpu_hours(&self) -> f64 {
+        match self.source {
+            CostSource::NetworkVolume => 0.0,
+            _ => self.time_billed_ms.unwrap_or_default() as f64 / 3_600_000.0,
+        }
+    }
+}
+
+/// Details used to attribute billing records to templates, images and GPU types.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDetails {
+    /// Serverless endpoint the resource belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+
+    /// GPU type the resource runs on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_type_id: Option<String>,
+
+    /// Template the resource was created from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+
+    /// Container image of the resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_name: Option<String>,
+}
+
+/// Lookup tables attributing Pods and endpoints to their details.
+///
+/// Billing records only carry Pod, endpoint or GPU type IDs. Attribution adds
+/// the template, image and GPU type of the billed resource, which is only
+/// possible for resources that still exist, or that were recorded in an
+/// earlier [`AccountSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attribution {
+    /// Details keyed by Pod ID.
+    pub pods: HashMap<String, ResourceDetails>,
+
+    /// Details keyed by endpoint ID.
+    pub endpoints: HashMap<String, ResourceDetails>,
+}
+
+impl Attribution {
+    /// Builds attribution tables from Pods and endpoints.
+    ///
+    /// Endpoint images are only known if the endpoints were listed with their
+    /// templates. Endpoint GPU types are only attributed when a single GPU type
+    /// is configured.
+    pub fn new(pods: &[Pod], endpoints: &[Endpoint]) -> Self {
+        let pods = pods
+            .iter()
+            .map(|pod| {
+                let details = ResourceDetails {
+                    endpoint_id: pod.endpoint_id.clone(),
+                    gpu_type_id: pod.gpu_type_id.clone(),
+                    template_id: pod.template_id.clone(),
+                    image_name: Some(pod.image.clone()).filter(|image| !image.is_empty()),
+                };
+                (pod.id.clone(), details)
+            })
+            .collect();
+
+        let endpoints = endpoints
+            .iter()
+            .map(|endpoint| {
+                let gpu_type_id = match endpoint.gpu_type_ids.as_deref() {
+                    Some([gpu_type_id]) => Some(gpu_type_key(gpu_type_id)),
+                    _ => None,
+                };
+                let details = ResourceDetails {
+                    endpoint_id: Some(endpoint.id.clone()),
+                    gpu_type_id,
+                    template_id: Some(endpoint.template_id.clone()),
+                    image_name: endpoint
+                        .template
+                        .as_ref()
+                        .map(|template| template.image_name.clone()),
+                };
+                (endpoint.id.clone(), details)
+            })
+            .collect();
+
+        Self { pods, endpoints }
+    }
+
+    /// Merges `other` into `self`, keeping existing entries.
+    ///
+    /// Useful to combine live resources with older snapshots, so that costs
+    /// of since-deleted resources remain attributed.
+    pub fn merge(&mut self, other: Attribution) {
+        for (id, details) in other.pods {
+            self.pods.entry(id).or_insert(details);
+        }
+        for (id, details) in other.endpoints {
+            self.endpoints.entry(id).or_insert(details);
+        }
+    }
+}
+
+impl From<&AccountSnapshot> for Attribution {
+    fn from(snapshot: &AccountSnapshot) -> Self {
+        Self::new(&snapshot.pods, &snapshot.endpoints)
+    }
+}
+
+/// Parses a billing timestamp, accepting RFC 3339, naive UTC datetimes and dates.
+pub(crate) fn parse_time(time: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Ok(time.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(time, "%Y-%m-%d") {
+        return Ok(date.and_time(Default::default()).and_utc());
+    }
+
+    Err(Error::Validation(format!("invalid billing time '{time}'")))
+}
@@ -0,0 +1,397 @@
+use std::future::Future;
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Timelike, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use super::{format_time, parse_time};
+use crate::model::v1::{
+    BillingRecord, BillingRecords, BucketSize, EndpointBillingQuery, NetworkVolumeBillingQuery,
+    PodBillingQuery,
+};
+use crate::service::v1::BillingService;
+use crate::{Error, Result};
+
+/// Splits long billing queries into windows the API accepts.
+///
+/// The range of a query is cut at bucket boundaries into windows of at most
+/// [`max_buckets`](Self::max_buckets) buckets of the query's size, which are
+/// fetched with at most
+/// [`concurrency`](Self::concurrency) requests in flight. Buckets returned by
+/// two adjacent windows are de-duplicated, keeping the record with the larger
+/// amount, and the merged series is ordered by time.
+///
+/// Queries without both `start_time` and `end_time` are sent unchanged.
+///
+/// # Examples
+///
+/// ```no_run
+/// use runpod_sdk::analytics::BillingWindows;
+/// use runpod_sdk::model::v1::{BucketSize, PodBillingQuery};
+/// use runpod_sdk::{Result, RunpodClient};
+///
+/// # async fn example() -> Result<()> {
+/// let client = RunpodClient::from_env()?;
+///
+/// let query = PodBillingQuery {
+///     bucket_size: Some(BucketSize::Hour),
+///     start_time: Some("2024-01-01T00:00:00Z".to_string()),
+///     end_time: Some("2025-01-01T00:00:00Z".to_string()),
+///     ..Default::default()
+/// };
+/// let records = BillingWindows::default().pod_billing(&client, query).await?;
+/// println!("{} hourly records", records.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BillingWindows {
+    /// Maximum number of buckets per request, by bucket size.
+    pub max_buckets: BucketLimits,
+
+    /// Maximum number of requests in flight. Values below 1 are treated as 1.
+    pub concurrency: usize,
+}
+
+impl Default for BillingWindows {
+    fn default() -> Self {
+        Self {
+            max_buckets: BucketLimits::default(),
+            concurrency: 4,
+        }
+    }
+}
+
+/// Maximum number of buckets per billing request, by bucket size.
+///
+/// The billing API does not document a limit on the number of buckets it
+/// returns. The default of 168 buckets for every size, one week of hourly
+/// buckets, keeps each response small when records are grouped by Pod or
+/// endpoint; raise it for sizes where fewer requests are preferable. Values
+/// below 1 are treated as 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BucketLimits {
+    /// Maximum number of hourly buckets.
+    pub hour: u32,
+    /// Maximum number of daily buckets.
+    pub day: u32,
+    /// Maximum number of weekly buckets.
+    pub week: u32,
+    /// Maximum number of monthly buckets.
+    pub month: u32,
+    /// Maximum number of yearly buckets.
+    pub year: u32,
+}
+
+impl BucketLimits {
+    /// Uses the same limit for every bucket size.
+    pub const fn uniform(max_buckets: u32) -> Self {
+        Self {
+            hour: max_buckets,
+            day: max_buckets,
+            week: max_buckets,
+            month: max_buckets,
+            year: max_buckets,
+        }
+    }
+
+    /// Returns the limit for `bucket_size`, at least 1.
+    pub fn get(&self, bucket_size: BucketSize) -> u32 {
+        let max_buckets = match bucket_size {
+            BucketSize::Hour => self.hour,
+            BucketSize::Day => self.day,
+            BucketSize::Week => self.week,
+            BucketSize::Month => self.month,
+            BucketSize::Year => self.year,
+        };
+        max_buckets.max(1)
+    }
+}
+
+impl Default for BucketLimits {
+    fn default() -> Self {
+        Self::uniform(168)
+    }
+}
+
+impl BillingWindows {
+    /// Splits `start..end` into consecutive windows aligned to `bucket_size`.
+    ///
+    /// The first window starts at `start` and the last ends at `end`; every
+    /// other boundary falls on the start of a bucket.
+    pub fn split(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        bucket_size: BucketSize,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut windows = Vec::new();
+        let mut cursor = start;
+        while cursor < end {
+            let next = advance(
+                align(cursor, bucket_size),
+                bucket_size,
+                self.max_buckets.get(bucket_size),
+            );
+            if next >= end {
+                break;
+            }
+            windows.push((cursor, next));
+            cursor = next;
+        }
+        if cursor < end || windows.is_empty() {
+            windows.push((cursor, end));
+        }
+        windows
+    }
+
+    /// Fetches Pod billing for the whole range of `query`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is invalid or any request fails.
+    pub async fn pod_billing<C: BillingService>(
+        &self,
+        client: &C,
+        query: PodBillingQuery,
+    ) -> Result<BillingRecords> {
+        let (start, end, bucket) = (&query.start_time, &query.end_time, query.bucket_size);
+        self.fetch(start, end, bucket, |from, to| {
+            let query = PodBillingQuery {
+                start_time: from,
+                end_time: to,
+                ..query.clone()
+            };
+            client.get_pod_billing(query)
+        })
+        .await
+    }
+
+    /// Fetches serverless endpoint billing for the whole range of `query`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is invalid or any request fails.
+    pub async fn endpoint_billing<C: BillingService>(
+        &self,
+        client: &C,
+        query: EndpointBillingQuery,
+    ) -> Result<BillingRecords> {
+        let (start, end, bucket) = (&query.start_time, &query.end_time, query.bucket_size);
+        self.fetch(start, end, bucket, |from, to| {
+            let query = EndpointBillingQuery {
+                start_time: from,
+                end_time: to,
+                ..query.clone()
+            };
+            client.get_endpoint_billing(query)
+        })
+        .await
+    }
+
+    /// Fetches network volume billing for the whole range of `query`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is invalid or any request fails.
+    pub async fn volume_billing<C: BillingService>(
+        &self,
+        client: &C,
+        query: NetworkVolumeBillingQuery,
+    ) -> Result<BillingRecords> {
+        let (start, end, bucket) = (&query.start_time, &query.end_time, query.bucket_size);
+        self.fetch(start, end, bucket, |from, to| {
+            let query = NetworkVolumeBillingQuery {
+                start_time: from,
+                end_time: to,
+                ..query.clone()
+            };
+            client.get_volume_billing(query)
+        })
+        .await
+    }
+
+    async fn fetch<F, Fut>(
+        &self,
+        start: &Option<String>,
+        end: &Option<String>,
+        bucket_size: Option<BucketSize>,
+        request: F,
+    ) -> Result<BillingRecords>
+    where
+        F: Fn(Option<String>, Option<String>) -> Fut,
+        Fut: Future<Output = Result<BillingRecords>>,
+    {
+        let (Some(start), Some(end)) = (start, end) else {
+            return request(start.clone(), end.clone()).await;
+        };
+
+        let (start, end) = (parse_time(start)?, parse_time(end)?);
+        if start > end {
+            return Err(Error::Validation(format!(
+                "billing start time {start} is after end time {end}"
+            )));
+        }
+
+        let windows = self.split(start, end, bucket_size.unwrap_or_default());
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(windows = windows.len(), "Fetching billing windows");
+
+        let batches: Vec<BillingRecords> = stream::iter(windows)
+            .map(|(from, to)| request(Some(format_time(from)), Some(format_time(to))))
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        merge(batches.into_iter().flatten())
+    }
+}
+
+/// Merges records from several windows, keeping one record per bucket and group.
+fn merge(records: impl Iterator<Item = BillingRecord>) -> Result<BillingRecords> {
+    let mut keyed = records
+        .map(|record| Ok((parse_time(&record.time)?, record)))
+        .collect::<Result<Vec<_>>>()?;
+    keyed.sort_by(|(a_time, a), (b_time, b)| {
+        a_time
+            .cmp(b_time)
+            .then_with(|| group(a).cmp(&group(b)))
+            .then_with(|| b.amount.total_cmp(&a.amount))
+    });
+    keyed.dedup_by(|(b_time, b), (a_time, a)| a_time == b_time && group(a) == group(b));
+
+    Ok(keyed.into_iter().map(|(_, record)| record).collect())
+}
+
+fn group(record: &BillingRecord) -> (&Option<String>, &Option<String>, &Option<String>) {
+    (&record.pod_id, &record.endpoint_id, &record.gpu_type_id)
+}
+
+/// Returns the start of the bucket containing `time`.
+fn align(time: DateTime<Utc>, bucket_size: BucketSize) -> DateTime<Utc> {
+    let date = time.date_naive();
+    let date = match bucket_size {
+        BucketSize::Hour => {
+            let hour = time.date_naive().and_hms_opt(time.hour(), 0, 0);
+            return hour.unwrap_or_default().and_utc();
+        }
+        BucketSize::Day => date,
+        BucketSize::Week => date - Duration::days(i64::from(date.weekday().num_days_from_monday())),
+        BucketSize::Month => date.with_day(1).unwrap_or(date),
+        BucketSize::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap_or(date),
+    };
+    date.and_time(Default::default()).and_utc()
+}
+
+/// Advances a bucket-aligned `time` by `buckets` buckets.
+fn advance(time: DateTime<Utc>, bucket_size: BucketSize, buckets: u32) -> DateTime<Utc> {
+    let next = match bucket_size {
+        BucketSize::Hour => time.checked_add_signed(Duration::hours(i64::from(buckets))),
+        BucketSize::Day => time.checked_add_signed(Duration::days(i64::from(buckets))),
+        BucketSize::Week => time.checked_add_signed(Duration::weeks(i64::from(buckets))),
+        BucketSize::Month => time.checked_add_months(Months::new(buckets)),
+        BucketSize::Year => time.checked_add_months(Months::new(buckets.saturating_mul(12))),
+    };
+    next.unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn time(month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, month, day, hour, 0, 0).unwrap()
+    }
+
+    fn record(time: &str, pod: &str, amount: f64) -> BillingRecord {
+        BillingRecord {
+            amount,
+            disk_space_billed_gb: None,
+            endpoint_id: None,
+            gpu_type_id: None,
+            pod_id: Some(pod.to_string()),
+            time: time.to_string(),
+            time_billed_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_split_aligns_to_buckets() {
+        let windows = BillingWindows {
+            max_buckets: BucketLimits {
+                hour: 24,
+                ..BucketLimits::uniform(12)
+            },
+            concurrency: 1,
+        };
+
+        let split = windows.split(time(1, 1, 6), time(1, 3, 12), BucketSize::Hour);
+        assert_eq!(
+            split,
+            [
+                (time(1, 1, 6), time(1, 2, 6)),
+                (time(1, 2, 6), time(1, 3, 6)),
+                (time(1, 3, 6), time(1, 3, 12)),
+            ]
+        );
+
+        let split = windows.split(time(1, 15, 0), time(3, 10, 0), BucketSize::Month);
+        assert_eq!(split, [(time(1, 15, 0), time(3, 10, 0))]);
+
+        let windows = BillingWindows {
+            max_buckets: BucketLimits::uniform(0),
+            concurrency: 1,
+        };
+        let split = windows.split(time(1, 15, 0), time(3, 10, 0), BucketSize::Month);
+        assert_eq!(
+            split,
+            [
+                (time(1, 15, 0), time(2, 1, 0)),
+                (time(2, 1, 0), time(3, 1, 0)),
+                (time(3, 1, 0), time(3, 10, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_empty_range() {
+        let split = BillingWindows::default().split(time(1, 1, 0), time(1, 1, 0), BucketSize::Day);
+        assert_eq!(split, [(time(1, 1, 0), time(1, 1, 0))]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_passes_open_ranges_through() -> Result<()> {
+        let requests = std::sync::Mutex::new(Vec::new());
+        let start = Some("2025-01-01T00:00:00Z".to_string());
+
+        let records = BillingWindows::default()
+            .fetch(&start, &None, None, |from, to| {
+                requests.lock().unwrap().push((from, to));
+                async { Ok(vec![record("2025-01-01T00:00:00Z", "a", 1.0)]) }
+            })
+            .await?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(requests.into_inner().unwrap(), [(start, None)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_deduplicates_boundary_buckets() -> Result<()> {
+        let records = vec![
+            record("2025-01-02T00:00:00Z", "b", 1.0),
+            record("2025-01-02T00:00:00Z", "a", 0.5),
+            record("2025-01-01T00:00:00Z", "a", 2.0),
+            record("2025-01-02T00:00:00.000Z", "a", 3.0),
+        ];
+
+        let merged = merge(records.into_iter())?;
+        let summary: Vec<_> = merged
+            .iter()
+            .map(|r| (r.pod_id.as_deref().unwrap(), r.amount))
+            .collect();
+        assert_eq!(summary, [("a", 2.0), ("a", 3.0), ("b", 1.0)]);
+        Ok(())
+    }
+}
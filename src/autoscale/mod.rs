@@ -0,0 +1,511 @@
+//! Autoscaling recommendations for serverless endpoints.
+//!
+//! A [`ScalingAdvisor`] combines hourly endpoint billing with
+//! [`EndpointHealth`] snapshots taken over time and recommends a scaler type,
+//! scaler value, worker bounds and idle timeout for an endpoint. Billing gives
+//! the average number of busy workers per hour and the price of a worker-hour;
+//! health snapshots show short-lived peaks that hourly averages flatten.
+//!
+//! Every [`ScalingRecommendation`] includes the projected monthly cost and
+//! the expected number of cold starts per day before and after the change,
+//! and converts into a ready-to-apply [`EndpointUpdateInput`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use chrono::{Duration, Utc};
+//! use runpod_sdk::autoscale::ScalingAdvisor;
+//! use runpod_sdk::model::v1::GetEndpointQuery;
+//! use runpod_sdk::service::v1::EndpointsService;
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let endpoint = client
+//!     .get_endpoint("endpoint_id", GetEndpointQuery::default())
+//!     .await?;
+//!
+//! let end = Utc::now();
+//! let mut advisor = ScalingAdvisor::new();
+//! advisor
+//!     .collect_billing(&client, &endpoint.id, end - Duration::days(14), end)
+//!     .await?;
+//! advisor.sample(&client, &endpoint.id).await?;
+//!
+//! if let Some(recommendation) = advisor.recommend(&endpoint) {
+//!     println!(
+//!         "${:.2}/month -> ${:.2}/month",
+//!         recommendation.current_monthly_cost, recommendation.projected_monthly_cost
+//!     );
+//!     client
+//!         .update_endpoint(&endpoint.id, recommendation.update_input())
+//!         .await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::{BillingRange, BillingWindows, parse_time};
+use crate::model::v1::{
+    BillingRecord, BucketSize, Endpoint, EndpointBillingQuery, EndpointHealth, EndpointUpdateInput,
+    ScalerType,
+};
+use crate::pricing::HOURS_PER_MONTH;
+use crate::service::v1::{BillingService, ServerlessService};
+use crate::{Error, Result};
+
+/// Tuning knobs of a [`ScalingAdvisor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdvisorPolicy {
+    /// Extra capacity added on top of the observed peak, e.g. `0.25` for 25%.
+    pub headroom: f64,
+
+    /// Target queue delay in seconds when recommending queue delay scaling.
+    pub queue_delay: i32,
+
+    /// Ratio of peak to mean load above which traffic is considered bursty
+    /// and queue delay scaling is recommended.
+    pub burst_ratio: f64,
+
+    /// Share of active hours, from 0 to 1, above which a worker is kept warm.
+    pub warm_ratio: f64,
+}
+
+impl Default for AdvisorPolicy {
+    fn default() -> Self {
+        Self {
+            headroom: 0.25,
+            queue_delay: 4,
+            burst_ratio: 3.0,
+            warm_ratio: 0.9,
+        }
+    }
+}
+
+/// An [`EndpointHealth`] snapshot taken at a point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSample {
+    /// Time of the snapshot.
+    pub at: DateTime<Utc>,
+
+    /// Job and worker counts at that time.
+    pub health: EndpointHealth,
+}
+
+impl HealthSample {
+    /// Jobs that needed a worker at the time of the snapshot.
+    fn demand(&self) -> u64 {
+        let busy = self.health.workers.running + self.health.workers.initializing;
+        self.health.jobs.pending().max(busy)
+    }
+}
+
+/// Endpoint usage during one hour.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct HourUsage {
+    amount: f64,
+    worker_hours: f64,
+}
+
+/// Recommended autoscaling settings of an endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScalingRecommendation {
+    /// Recommended scaling strategy.
+    pub scaler_type: ScalerType,
+
+    /// Recommended scaler value: seconds of queue delay, or jobs per worker.
+    pub scaler_value: i32,
+
+    /// Recommended number of always-on workers.
+    pub workers_min: i32,
+
+    /// Recommended maximum number of workers.
+    pub workers_max: i32,
+
+    /// Recommended idle timeout in seconds.
+    pub idle_timeout: i32,
+
+    /// Monthly cost at the observed usage with the current settings, in USD.
+    pub current_monthly_cost: f64,
+
+    /// Projected monthly cost with the recommended settings, in USD.
+    pub projected_monthly_cost: f64,
+
+    /// Cold starts per day with the current settings.
+    pub current_cold_starts_per_day: f64,
+
+    /// Projected cold starts per day with the recommended settings.
+    pub projected_cold_starts_per_day: f64,
+
+    /// Why each setting was chosen.
+    pub rationale: Vec<String>,
+}
+
+impl ScalingRecommendation {
+    /// Returns an update applying the recommended settings.
+    pub fn update_input(&self) -> EndpointUpdateInput {
+        EndpointUpdateInput {
+            scaler_type: Some(self.scaler_type),
+            scaler_value: Some(self.scaler_value),
+            workers_min: Some(self.workers_min),
+            workers_max: Some(self.workers_max),
+            idle_timeout: Some(self.idle_timeout),
+            ..Default::default()
+        }
+    }
+
+    /// Projected monthly savings, in USD; negative if costs increase.
+    pub fn monthly_savings(&self) -> f64 {
+        self.current_monthly_cost - self.projected_monthly_cost
+    }
+}
+
+/// Recommends autoscaling settings from historical endpoint usage.
+///
+/// Hours without billing count as idle. The advisor assumes one job per
+/// worker at a time when converting health snapshots into worker demand, and
+/// projects costs as if the billed worker-hours were the demand: savings from
+/// lowering `workers_min` are not projected, since billing cannot tell warm
+/// workers from busy ones.
+#[derive(Debug, Clone, Default)]
+pub struct ScalingAdvisor {
+    policy: AdvisorPolicy,
+    hours: BTreeMap<DateTime<Utc>, HourUsage>,
+    samples: Vec<HealthSample>,
+}
+
+impl ScalingAdvisor {
+    /// Creates an advisor with the default policy and no history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the advisor policy.
+    pub fn with_policy(mut self, policy: AdvisorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the advisor policy.
+    pub fn policy(&self) -> &AdvisorPolicy {
+        &self.policy
+    }
+
+    /// Returns the recorded health snapshots, oldest first.
+    pub fn samples(&self) -> &[HealthSample] {
+        &self.samples
+    }
+
+    /// Adds hourly billing records of a single endpoint.
+    ///
+    /// Missing hours between the first and last record count as idle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a record has an invalid time.
+    pub fn add_billing(&mut self, records: &[BillingRecord]) -> Result<()> {
+        for record in records {
+            let hour = truncate_hour(parse_time(&record.time)?)?;
+            let usage = self.hours.entry(hour).or_default();
+            usage.amount += record.amount;
+            usage.worker_hours += record.time_billed_ms.unwrap_or_default().max(0) as f64 / 3.6e6;
+        }
+
+        let range = self
+            .hours
+            .keys()
+            .next()
+            .copied()
+            .zip(self.hours.keys().last().copied());
+        if let Some((start, end)) = range {
+            self.fill_hours(start, end + Duration::hours(1));
+        }
+        Ok(())
+    }
+
+    /// Records a health snapshot taken at `at`.
+    pub fn add_sample(&mut self, at: DateTime<Utc>, health: EndpointHealth) {
+        let index = self.samples.partition_point(|sample| sample.at <= at);
+        self.samples.insert(index, HealthSample { at, health });
+    }
+
+    /// Fetches hourly billing of `endpoint_id` between `start` and `end`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or a record has an invalid time.
+    pub async fn collect_billing<C: BillingService>(
+        &mut self,
+        client: &C,
+        endpoint_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<()> {
+        let range = BillingRange::new(start, end, BucketSize::Hour);
+        let query = EndpointBillingQuery {
+            endpoint_id: Some(endpoint_id.to_string()),
+            ..range.endpoint_query()
+        };
+        let records = BillingWindows::default()
+            .endpoint_billing(client, query)
+            .await?;
+
+        self.add_billing(&records)?;
+        self.fill_hours(truncate_hour(start)?, end);
+        Ok(())
+    }
+
+    /// Takes a health snapshot of `endpoint_id` now.
+    ///
+    /// Call it periodically, e.g. every minute, to capture peaks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn sample<C: ServerlessService>(
+        &mut self,
+        client: &C,
+        endpoint_id: &str,
+    ) -> Result<()> {
+        let health = client.get_endpoint_health(endpoint_id).await?;
+        self.add_sample(Utc::now(), health);
+        Ok(())
+    }
+
+    /// Recommends settings for `endpoint`, or `None` without billing history.
+    pub fn recommend(&self, endpoint: &Endpoint) -> Option<ScalingRecommendation> {
+        if self.hours.is_empty() {
+            return None;
+        }
+        let policy = &self.policy;
+        let mut rationale = Vec::new();
+
+        let workers: Vec<f64> = self.hours.values().map(|hour| hour.worker_hours).collect();
+        let active: Vec<f64> = workers.iter().copied().filter(|w| *w > 0.0).collect();
+        let active_share = active.len() as f64 / workers.len() as f64;
+        let mean_active = match active.len() {
+            0 => 0.0,
+            n => active.iter().sum::<f64>() / n as f64,
+        };
+
+        let sampled_peak = self.samples.iter().map(HealthSample::demand).max();
+        let peak = workers
+            .iter()
+            .copied()
+            .fold(sampled_peak.unwrap_or_default() as f64, f64::max);
+
+        let workers_min = if active_share >= policy.warm_ratio {
+            let baseline = percentile(&workers, 0.1).floor().max(1.0) as i32;
+            rationale.push(format!(
+                "active {:.0}% of hours: keep {baseline} warm worker(s)",
+                active_share * 100.0
+            ));
+            baseline
+        } else {
+            rationale.push(format!(
+                "active {:.0}% of hours: scale to zero when idle",
+                active_share * 100.0
+            ));
+            0
+        };
+
+        let workers_max = ((peak * (1.0 + policy.headroom)).ceil() as i32)
+            .max(workers_min)
+            .max(1);
+        rationale.push(format!(
+            "peak of {peak:.1} busy workers plus {:.0}% headroom: at most {workers_max} workers",
+            policy.headroom * 100.0
+        ));
+
+        let bursty = mean_active > 0.0 && peak / mean_active > policy.burst_ratio;
+        let (scaler_type, scaler_value) = if bursty || self.samples.is_empty() {
+            rationale.push(format!(
+                "peak is {:.1}x the mean load: scale on {}s queue delay",
+                if mean_active > 0.0 {
+                    peak / mean_active
+                } else {
+                    0.0
+                },
+                policy.queue_delay
+            ));
+            (ScalerType::QueueDelay, policy.queue_delay)
+        } else {
+            let per_worker = self.jobs_per_worker();
+            rationale.push(format!(
+                "steady load: scale at {per_worker} queued job(s) per worker"
+            ));
+            (ScalerType::RequestCount, per_worker)
+        };
+
+        let idle_timeout = if active_share >= 0.5 { 30 } else { 5 };
+        rationale.push(format!(
+            "traffic in {:.0}% of hours: {idle_timeout}s idle timeout",
+            active_share * 100.0
+        ));
+
+        let hours = self.hours.len() as f64;
+        let amount: f64 = self.hours.values().map(|hour| hour.amount).sum();
+        let worker_hours: f64 = workers.iter().sum();
+        let current_monthly_cost = amount / hours * HOURS_PER_MONTH;
+
+        let price = match worker_hours {
+            0.0 => 0.0,
+            _ => amount / worker_hours,
+        };
+        let change = workers
+            .iter()
+            .map(|w| w.clamp(f64::from(workers_min), f64::from(workers_max)) - w)
+            .sum::<f64>();
+        let projected_monthly_cost =
+            (current_monthly_cost + change * price / hours * HOURS_PER_MONTH).max(0.0);
+
+        Some(ScalingRecommendation {
+            scaler_type,
+            scaler_value,
+            workers_min,
+            workers_max,
+            idle_timeout,
+            current_monthly_cost,
+            projected_monthly_cost,
+            current_cold_starts_per_day: cold_starts_per_day(&workers, endpoint.workers_min),
+            projected_cold_starts_per_day: cold_starts_per_day(&workers, workers_min),
+            rationale,
+        })
+    }
+
+    /// Mean number of jobs per running worker across snapshots, at least 1.
+    fn jobs_per_worker(&self) -> i32 {
+        let ratios: Vec<f64> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.health.workers.running > 0)
+            .map(|sample| {
+                sample.health.jobs.pending() as f64 / sample.health.workers.running as f64
+            })
+            .collect();
+        match ratios.len() {
+            0 => 1,
+            n => (ratios.iter().sum::<f64>() / n as f64).ceil().max(1.0) as i32,
+        }
+    }
+
+    /// Inserts idle hours for every hour of `start..end` without usage.
+    fn fill_hours(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) {
+        let mut hour = start;
+        while hour < end {
+            self.hours.entry(hour).or_default();
+            hour += Duration::hours(1);
+        }
+    }
+}
+
+fn truncate_hour(time: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    time.duration_trunc(Duration::hours(1))
+        .map_err(|e| Error::Validation(format!("invalid billing time {time}: {e}")))
+}
+
+/// Returns the `q` quantile of `values`, from 0 to 1.
+fn percentile(values: &[f64], q: f64) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let index = ((sorted.len() - 1) as f64 * q).round() as usize;
+    sorted.get(index).copied().unwrap_or_default()
+}
+
+/// Counts hours starting with traffic after an idle hour, when no worker is
+/// kept warm, per day.
+fn cold_starts_per_day(workers: &[f64], workers_min: i32) -> f64 {
+    if workers_min > 0 || workers.is_empty() {
+        return 0.0;
+    }
+    let starts = workers
+        .windows(2)
+        .filter(|pair| pair[0] == 0.0 && pair[1] > 0.0)
+        .count();
+    starts as f64 / (workers.len() as f64 / 24.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing;
+
+    fn endpoint(workers_min: i32) -> Endpoint {
+        testing::endpoint(json!({
+            "workersMax": 10,
+            "workersMin": workers_min,
+        }))
+    }
+
+    fn billing(hours: &[f64]) -> Vec<BillingRecord> {
+        let start = Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap();
+        hours
+            .iter()
+            .enumerate()
+            .map(|(i, workers)| {
+                serde_json::from_value(json!({
+                    "amount": workers * 2.0,
+                    "time": (start + Duration::hours(i as i64)).to_rfc3339(),
+                    "timeBilledMs": (workers * 3.6e6) as i64,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recommends_scale_to_zero_for_sparse_traffic() {
+        let mut advisor = ScalingAdvisor::new();
+        let mut hours = vec![0.0; 24];
+        hours[3] = 0.5;
+        hours[15] = 2.0;
+        advisor.add_billing(&billing(&hours)).unwrap();
+
+        let recommendation = advisor.recommend(&endpoint(0)).unwrap();
+        assert_eq!(recommendation.workers_min, 0);
+        assert_eq!(recommendation.workers_max, 3);
+        assert_eq!(recommendation.scaler_type, ScalerType::QueueDelay);
+        assert_eq!(recommendation.idle_timeout, 5);
+        assert_eq!(recommendation.current_cold_starts_per_day, 2.0);
+        assert_eq!(recommendation.projected_cold_starts_per_day, 2.0);
+        assert!((recommendation.current_monthly_cost - 5.0 / 24.0 * 730.0).abs() < 1e-9);
+        assert!(recommendation.monthly_savings().abs() < 1e-9);
+
+        let update = recommendation.update_input();
+        assert_eq!(update.workers_min, Some(0));
+        assert_eq!(update.scaler_value, Some(4));
+    }
+
+    #[test]
+    fn test_recommends_warm_workers_for_steady_traffic() {
+        let mut advisor = ScalingAdvisor::new();
+        advisor.add_billing(&billing(&[2.0; 48])).unwrap();
+        let health: EndpointHealth = serde_json::from_value(json!({
+            "jobs": {"inProgress": 3, "inQueue": 3},
+            "workers": {"running": 3},
+        }))
+        .unwrap();
+        advisor.add_sample(Utc::now(), health);
+
+        let recommendation = advisor.recommend(&endpoint(0)).unwrap();
+        assert_eq!(recommendation.workers_min, 2);
+        assert_eq!(recommendation.workers_max, 8);
+        assert_eq!(recommendation.scaler_type, ScalerType::RequestCount);
+        assert_eq!(recommendation.scaler_value, 2);
+        assert_eq!(recommendation.idle_timeout, 30);
+        assert_eq!(recommendation.current_cold_starts_per_day, 0.0);
+        assert_eq!(recommendation.projected_cold_starts_per_day, 0.0);
+        assert!((recommendation.current_monthly_cost - 4.0 * 730.0).abs() < 1e-9);
+        assert!(recommendation.monthly_savings().abs() < 1e-9);
+    }
+}
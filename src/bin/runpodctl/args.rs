@@ -0,0 +1,436 @@
+//! Command-line arguments.
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use runpod_sdk::model::v1::{
+    BillingGrouping, BucketSize, ComputeType, CpuFlavorId, DataCenterId, GpuTypeId, PodStatus,
+};
+use serde::de::DeserializeOwned;
+
+use crate::output::OutputFormat;
+
+/// Manage RunPod pods, serverless endpoints, templates, network volumes,
+/// container registry auths and billing.
+#[derive(Debug, Parser)]
+#[command(name = "runpodctl", version)]
+pub struct Cli {
+    /// Output format.
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Profile to read from the configuration file. When neither a profile is
+    /// given nor `RUNPOD_API_KEY` is set, the `default` profile is used.
+    #[arg(long, global = true, env = "RUNPOD_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Path of the profile configuration file [default: ~/.runpod/config.toml].
+    #[arg(long, global = true, env = "RUNPOD_CONFIG_FILE")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Manage Pods.
+    #[command(subcommand, alias = "pods")]
+    Pod(PodCommand),
+
+    /// Manage serverless endpoints.
+    #[command(subcommand, alias = "endpoints")]
+    Endpoint(EndpointCommand),
+
+    /// Manage templates.
+    #[command(subcommand, alias = "templates")]
+    Template(TemplateCommand),
+
+    /// Manage network volumes.
+    #[command(subcommand, alias = "volumes")]
+    Volume(VolumeCommand),
+
+    /// Manage container registry auths.
+    #[command(subcommand)]
+    Registry(RegistryCommand),
+
+    /// Query billing history.
+    #[command(subcommand)]
+    Billing(BillingCommand),
+}
+
+/// A request body read from a JSON, YAML or TOML file.
+#[derive(Debug, Args)]
+pub struct InputFile {
+    /// File with the request body, or `-` for standard input (JSON or YAML).
+    /// The format is detected from the file extension.
+    #[arg(short, long, value_name = "PATH")]
+    pub file: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PodCommand {
+    /// List Pods.
+    #[command(alias = "ls")]
+    List(ListPodsArgs),
+
+    /// Show a Pod.
+    Get {
+        /// Pod ID.
+        pod_id: String,
+        #[command(flatten)]
+        include: PodIncludes,
+    },
+
+    /// Create a Pod from a `PodCreateInput`.
+    Create(InputFile),
+
+    /// Update a Pod from a `PodUpdateInput`.
+    Update {
+        /// Pod ID.
+        pod_id: String,
+        #[command(flatten)]
+        input: InputFile,
+    },
+
+    /// Delete a Pod.
+    #[command(alias = "rm")]
+    Delete {
+        /// Pod ID.
+        pod_id: String,
+    },
+
+    /// Start or resume a Pod.
+    Start {
+        /// Pod ID.
+        pod_id: String,
+    },
+
+    /// Stop a Pod.
+    Stop {
+        /// Pod ID.
+        pod_id: String,
+    },
+
+    /// Reset a Pod.
+    Reset {
+        /// Pod ID.
+        pod_id: String,
+    },
+
+    /// Restart a Pod.
+    Restart {
+        /// Pod ID.
+        pod_id: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct PodIncludes {
+    /// Include machine details.
+    #[arg(long)]
+    pub include_machine: bool,
+
+    /// Include the attached network volume.
+    #[arg(long)]
+    pub include_network_volume: bool,
+
+    /// Include savings plans.
+    #[arg(long)]
+    pub include_savings_plans: bool,
+
+    /// Include the template.
+    #[arg(long)]
+    pub include_template: bool,
+
+    /// Include serverless workers.
+    #[arg(long)]
+    pub include_workers: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ListPodsArgs {
+    /// Filter by compute type (`GPU` or `CPU`).
+    #[arg(long, value_parser = parse_enum::<ComputeType>)]
+    pub compute_type: Option<ComputeType>,
+
+    /// Filter by CPU flavor. Can be repeated.
+    #[arg(long, value_parser = parse_enum::<CpuFlavorId>)]
+    pub cpu_flavor_id: Vec<CpuFlavorId>,
+
+    /// Filter by data center. Can be repeated.
+    #[arg(long, value_parser = parse_enum::<DataCenterId>)]
+    pub data_center_id: Vec<DataCenterId>,
+
+    /// Filter by status (`RUNNING`, `EXITED` or `TERMINATED`).
+    #[arg(long, value_parser = parse_enum::<PodStatus>)]
+    pub desired_status: Option<PodStatus>,
+
+    /// Filter by serverless endpoint ID.
+    #[arg(long)]
+    pub endpoint_id: Option<String>,
+
+    /// Filter by GPU type. Can be repeated.
+    #[arg(long, value_parser = parse_enum::<GpuTypeId>)]
+    pub gpu_type_id: Vec<GpuTypeId>,
+
+    /// Filter by Pod ID.
+    #[arg(long)]
+    pub id: Option<String>,
+
+    /// Filter by image name.
+    #[arg(long)]
+    pub image_name: Option<String>,
+
+    /// Filter by name.
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Filter by network volume ID.
+    #[arg(long)]
+    pub network_volume_id: Option<String>,
+
+    /// Filter by template ID.
+    #[arg(long)]
+    pub template_id: Option<String>,
+
+    #[command(flatten)]
+    pub include: PodIncludes,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EndpointCommand {
+    /// List serverless endpoints.
+    #[command(alias = "ls")]
+    List(EndpointIncludes),
+
+    /// Show a serverless endpoint.
+    Get {
+        /// Endpoint ID.
+        endpoint_id: String,
+        #[command(flatten)]
+        include: EndpointIncludes,
+    },
+
+    /// Create an endpoint from an `EndpointCreateInput`.
+    Create(InputFile),
+
+    /// Update an endpoint from an `EndpointUpdateInput`.
+    Update {
+        /// Endpoint ID.
+        endpoint_id: String,
+        #[command(flatten)]
+        input: InputFile,
+    },
+
+    /// Delete an endpoint.
+    #[command(alias = "rm")]
+    Delete {
+        /// Endpoint ID.
+        endpoint_id: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct EndpointIncludes {
+    /// Include the template.
+    #[arg(long)]
+    pub include_template: bool,
+
+    /// Include workers.
+    #[arg(long)]
+    pub include_workers: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TemplateCommand {
+    /// List templates.
+    #[command(alias = "ls")]
+    List(TemplateIncludes),
+
+    /// Show a template.
+    Get {
+        /// Template ID.
+        template_id: String,
+        #[command(flatten)]
+        include: TemplateIncludes,
+    },
+
+    /// Create a template from a `TemplateCreateInput`.
+    Create(InputFile),
+
+    /// Update a template from a `TemplateUpdateInput`.
+    Update {
+        /// Template ID.
+        template_id: String,
+        #[command(flatten)]
+        input: InputFile,
+    },
+
+    /// Delete a template.
+    #[command(alias = "rm")]
+    Delete {
+        /// Template ID.
+        template_id: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct TemplateIncludes {
+    /// Include templates bound to serverless endpoints.
+    #[arg(long)]
+    pub include_endpoint_bound: bool,
+
+    /// Include community templates.
+    #[arg(long)]
+    pub include_public: bool,
+
+    /// Include official RunPod templates.
+    #[arg(long)]
+    pub include_runpod: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VolumeCommand {
+    /// List network volumes.
+    #[command(alias = "ls")]
+    List,
+
+    /// Show a network volume.
+    Get {
+        /// Network volume ID.
+        volume_id: String,
+    },
+
+    /// Create a network volume from a `NetworkVolumeCreateInput`.
+    Create(InputFile),
+
+    /// Update a network volume from a `NetworkVolumeUpdateInput`.
+    Update {
+        /// Network volume ID.
+        volume_id: String,
+        #[command(flatten)]
+        input: InputFile,
+    },
+
+    /// Delete a network volume.
+    #[command(alias = "rm")]
+    Delete {
+        /// Network volume ID.
+        volume_id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RegistryCommand {
+    /// List container registry auths.
+    #[command(alias = "ls")]
+    List,
+
+    /// Show a container registry auth.
+    Get {
+        /// Registry auth ID.
+        auth_id: String,
+    },
+
+    /// Create a registry auth from a `ContainerRegistryAuthCreateInput`.
+    Create(InputFile),
+
+    /// Delete a registry auth.
+    #[command(alias = "rm")]
+    Delete {
+        /// Registry auth ID.
+        auth_id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BillingCommand {
+    /// Pod billing history.
+    Pods {
+        #[command(flatten)]
+        range: BillingRange,
+
+        /// Group records (`podId` or `gpuTypeId`).
+        #[arg(long, value_parser = parse_enum::<BillingGrouping>)]
+        grouping: Option<BillingGrouping>,
+
+        /// Filter by GPU type.
+        #[arg(long, value_parser = parse_enum::<GpuTypeId>)]
+        gpu_type_id: Option<GpuTypeId>,
+
+        /// Filter by Pod ID.
+        #[arg(long)]
+        pod_id: Option<String>,
+    },
+
+    /// Serverless endpoint billing history.
+    Endpoints {
+        #[command(flatten)]
+        range: BillingRange,
+
+        /// Group records (`endpointId`, `podId` or `gpuTypeId`).
+        #[arg(long, value_parser = parse_enum::<BillingGrouping>)]
+        grouping: Option<BillingGrouping>,
+
+        /// Filter by data center. Can be repeated.
+        #[arg(long)]
+        data_center_id: Vec<String>,
+
+        /// Filter by endpoint ID.
+        #[arg(long)]
+        endpoint_id: Option<String>,
+
+        /// Filter by GPU type. Can be repeated.
+        #[arg(long, value_parser = parse_enum::<GpuTypeId>)]
+        gpu_type_id: Vec<GpuTypeId>,
+
+        /// Filter by image name.
+        #[arg(long)]
+        image_name: Option<String>,
+
+        /// Filter by template ID.
+        #[arg(long)]
+        template_id: Option<String>,
+    },
+
+    /// Network volume billing history.
+    Volumes {
+        #[command(flatten)]
+        range: BillingRange,
+
+        /// Filter by network volume ID.
+        #[arg(long)]
+        network_volume_id: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct BillingRange {
+    /// Start of the period (ISO 8601).
+    #[arg(long)]
+    pub start_time: Option<String>,
+
+    /// End of the period (ISO 8601).
+    #[arg(long)]
+    pub end_time: Option<String>,
+
+    /// Bucket size (`hour`, `day`, `week`, `month` or `year`).
+    #[arg(long, value_parser = parse_enum::<BucketSize>)]
+    pub bucket_size: Option<BucketSize>,
+}
+
+/// Parses an SDK enum from its API representation.
+fn parse_enum<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_string())).map_err(|e| e.to_string())
+}
+
+/// Converts a flag into the optional boolean used by SDK queries.
+pub fn flag(value: bool) -> Option<bool> {
+    value.then_some(true)
+}
+
+/// Converts a repeated argument into the optional list used by SDK queries.
+pub fn list<T>(values: Vec<T>) -> Option<Vec<T>> {
+    (!values.is_empty()).then_some(values)
+}
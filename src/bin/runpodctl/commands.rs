@@ -0,0 +1,338 @@
+//! Execution of subcommands against the SDK services.
+
+use std::io::Read;
+use std::path::Path;
+
+use runpod_sdk::manifest::{ManifestError, ManifestFormat};
+use runpod_sdk::model::v1::{
+    EndpointBillingQuery, GetEndpointQuery, GetPodQuery, GetTemplateQuery, ListEndpointsQuery,
+    ListPodsQuery, ListTemplatesQuery, NetworkVolumeBillingQuery, PodBillingQuery,
+};
+use runpod_sdk::service::v1::{
+    BillingService, EndpointsService, PodsService, RegistryService, TemplatesService,
+    VolumesService,
+};
+use runpod_sdk::{Error, Result, RunpodClient};
+use serde::de::DeserializeOwned;
+
+use crate::args::{
+    BillingCommand, Command, EndpointCommand, EndpointIncludes, InputFile, PodCommand, PodIncludes,
+    RegistryCommand, TemplateCommand, TemplateIncludes, VolumeCommand, flag, list,
+};
+use crate::output::{self, OutputFormat};
+
+/// Runs `command` and prints its result.
+pub async fn run(client: &RunpodClient, command: Command, format: OutputFormat) -> Result<()> {
+    match command {
+        Command::Pod(command) => pod(client, command, format).await,
+        Command::Endpoint(command) => endpoint(client, command, format).await,
+        Command::Template(command) => template(client, command, format).await,
+        Command::Volume(command) => volume(client, command, format).await,
+        Command::Registry(command) => registry(client, command, format).await,
+        Command::Billing(command) => billing(client, command, format).await,
+    }
+}
+
+async fn pod(client: &RunpodClient, command: PodCommand, format: OutputFormat) -> Result<()> {
+    let columns = output::PODS;
+    match command {
+        PodCommand::List(args) => {
+            let query = ListPodsQuery {
+                compute_type: args.compute_type,
+                cpu_flavor_id: list(args.cpu_flavor_id),
+                data_center_id: list(args.data_center_id),
+                desired_status: args.desired_status,
+                endpoint_id: args.endpoint_id,
+                gpu_type_id: list(args.gpu_type_id),
+                id: args.id,
+                image_name: args.image_name,
+                name: args.name,
+                network_volume_id: args.network_volume_id,
+                template_id: args.template_id,
+                include_machine: flag(args.include.include_machine),
+                include_network_volume: flag(args.include.include_network_volume),
+                include_savings_plans: flag(args.include.include_savings_plans),
+                include_template: flag(args.include.include_template),
+                include_workers: flag(args.include.include_workers),
+            };
+            output::print(&client.list_pods(query).await?, format, columns)
+        }
+        PodCommand::Get { pod_id, include } => {
+            let pod = client.get_pod(&pod_id, pod_includes(include)).await?;
+            output::print(&pod, format, columns)
+        }
+        PodCommand::Create(input) => {
+            let pod = client.create_pod(read_input(&input)?).await?;
+            output::print(&pod, format, columns)
+        }
+        PodCommand::Update { pod_id, input } => {
+            let pod = client.update_pod(&pod_id, read_input(&input)?).await?;
+            output::print(&pod, format, columns)
+        }
+        PodCommand::Delete { pod_id } => {
+            client.delete_pod(&pod_id).await?;
+            done("pod", &pod_id, "deleted")
+        }
+        PodCommand::Start { pod_id } => {
+            client.start_pod(&pod_id).await?;
+            done("pod", &pod_id, "started")
+        }
+        PodCommand::Stop { pod_id } => {
+            client.stop_pod(&pod_id).await?;
+            done("pod", &pod_id, "stopped")
+        }
+        PodCommand::Reset { pod_id } => {
+            client.reset_pod(&pod_id).await?;
+            done("pod", &pod_id, "reset")
+        }
+        PodCommand::Restart { pod_id } => {
+            client.restart_pod(&pod_id).await?;
+            done("pod", &pod_id, "restarted")
+        }
+    }
+}
+
+async fn endpoint(
+    client: &RunpodClient,
+    command: EndpointCommand,
+    format: OutputFormat,
+) -> Result<()> {
+    let columns = output::ENDPOINTS;
+    match command {
+        EndpointCommand::List(include) => {
+            let query = ListEndpointsQuery {
+                include_template: flag(include.include_template),
+                include_workers: flag(include.include_workers),
+            };
+            output::print(&client.list_endpoints(query).await?, format, columns)
+        }
+        EndpointCommand::Get {
+            endpoint_id,
+            include:
+                EndpointIncludes {
+                    include_template,
+                    include_workers,
+                },
+        } => {
+            let query = GetEndpointQuery {
+                include_template: flag(include_template),
+                include_workers: flag(include_workers),
+            };
+            let endpoint = client.get_endpoint(&endpoint_id, query).await?;
+            output::print(&endpoint, format, columns)
+        }
+        EndpointCommand::Create(input) => {
+            let endpoint = client.create_endpoint(read_input(&input)?).await?;
+            output::print(&endpoint, format, columns)
+        }
+        EndpointCommand::Update { endpoint_id, input } => {
+            let endpoint = client
+                .update_endpoint(&endpoint_id, read_input(&input)?)
+                .await?;
+            output::print(&endpoint, format, columns)
+        }
+        EndpointCommand::Delete { endpoint_id } => {
+            client.delete_endpoint(&endpoint_id).await?;
+            done("endpoint", &endpoint_id, "deleted")
+        }
+    }
+}
+
+async fn template(
+    client: &RunpodClient,
+    command: TemplateCommand,
+    format: OutputFormat,
+) -> Result<()> {
+    let columns = output::TEMPLATES;
+    match command {
+        TemplateCommand::List(include) => {
+            let query = ListTemplatesQuery {
+                include_endpoint_bound_templates: flag(include.include_endpoint_bound),
+                include_public_templates: flag(include.include_public),
+                include_runpod_templates: flag(include.include_runpod),
+            };
+            output::print(&client.list_templates(query).await?, format, columns)
+        }
+        TemplateCommand::Get {
+            template_id,
+            include:
+                TemplateIncludes {
+                    include_endpoint_bound,
+                    include_public,
+                    include_runpod,
+                },
+        } => {
+            let query = GetTemplateQuery {
+                include_endpoint_bound_templates: flag(include_endpoint_bound),
+                include_public_templates: flag(include_public),
+                include_runpod_templates: flag(include_runpod),
+            };
+            let template = client.get_template(&template_id, query).await?;
+            output::print(&template, format, columns)
+        }
+        TemplateCommand::Create(input) => {
+            let template = client.create_template(read_input(&input)?).await?;
+            output::print(&template, format, columns)
+        }
+        TemplateCommand::Update { template_id, input } => {
+            let template = client
+                .update_template(&template_id, read_input(&input)?)
+                .await?;
+            output::print(&template, format, columns)
+        }
+        TemplateCommand::Delete { template_id } => {
+            client.delete_template(&template_id).await?;
+            done("template", &template_id, "deleted")
+        }
+    }
+}
+
+async fn volume(client: &RunpodClient, command: VolumeCommand, format: OutputFormat) -> Result<()> {
+    let columns = output::VOLUMES;
+    match command {
+        VolumeCommand::List => output::print(&client.list_volumes().await?, format, columns),
+        VolumeCommand::Get { volume_id } => {
+            output::print(&client.get_volume(&volume_id).await?, format, columns)
+        }
+        VolumeCommand::Create(input) => {
+            let volume = client.create_volume(read_input(&input)?).await?;
+            output::print(&volume, format, columns)
+        }
+        VolumeCommand::Update { volume_id, input } => {
+            let volume = client
+                .update_volume(&volume_id, read_input(&input)?)
+                .await?;
+            output::print(&volume, format, columns)
+        }
+        VolumeCommand::Delete { volume_id } => {
+            client.delete_volume(&volume_id).await?;
+            done("network volume", &volume_id, "deleted")
+        }
+    }
+}
+
+async fn registry(
+    client: &RunpodClient,
+    command: RegistryCommand,
+    format: OutputFormat,
+) -> Result<()> {
+    let columns = output::REGISTRY_AUTHS;
+    match command {
+        RegistryCommand::List => {
+            output::print(&client.list_registry_auths().await?, format, columns)
+        }
+        RegistryCommand::Get { auth_id } => {
+            output::print(&client.get_registry_auth(&auth_id).await?, format, columns)
+        }
+        RegistryCommand::Create(input) => {
+            let auth = client.create_registry_auth(read_input(&input)?).await?;
+            output::print(&auth, format, columns)
+        }
+        RegistryCommand::Delete { auth_id } => {
+            client.delete_registry_auth(&auth_id).await?;
+            done("registry auth", &auth_id, "deleted")
+        }
+    }
+}
+
+async fn billing(
+    client: &RunpodClient,
+    command: BillingCommand,
+    format: OutputFormat,
+) -> Result<()> {
+    let records = match command {
+        BillingCommand::Pods {
+            range,
+            grouping,
+            gpu_type_id,
+            pod_id,
+        } => {
+            let query = PodBillingQuery {
+                bucket_size: range.bucket_size,
+                end_time: range.end_time,
+                gpu_type_id,
+                grouping,
+                pod_id,
+                start_time: range.start_time,
+            };
+            client.get_pod_billing(query).await?
+        }
+        BillingCommand::Endpoints {
+            range,
+            grouping,
+            data_center_id,
+            endpoint_id,
+            gpu_type_id,
+            image_name,
+            template_id,
+        } => {
+            let query = EndpointBillingQuery {
+                bucket_size: range.bucket_size,
+                data_center_id: list(data_center_id),
+                endpoint_id,
+                end_time: range.end_time,
+                gpu_type_id: list(gpu_type_id),
+                grouping,
+                image_name,
+                start_time: range.start_time,
+                template_id,
+            };
+            client.get_endpoint_billing(query).await?
+        }
+        BillingCommand::Volumes {
+            range,
+            network_volume_id,
+        } => {
+            let query = NetworkVolumeBillingQuery {
+                bucket_size: range.bucket_size,
+                end_time: range.end_time,
+                network_volume_id,
+                start_time: range.start_time,
+            };
+            client.get_volume_billing(query).await?
+        }
+    };
+
+    output::print(&records, format, output::BILLING)
+}
+
+fn pod_includes(include: PodIncludes) -> GetPodQuery {
+    GetPodQuery {
+        include_machine: flag(include.include_machine),
+        include_network_volume: flag(include.include_network_volume),
+        include_savings_plans: flag(include.include_savings_plans),
+        include_template: flag(include.include_template),
+        include_workers: flag(include.include_workers),
+    }
+}
+
+/// Reports a successful operation that returns no resource.
+fn done(kind: &str, id: &str, action: &str) -> Result<()> {
+    eprintln!("{kind} {id} {action}");
+    Ok(())
+}
+
+/// Reads a request body from a file or standard input.
+///
+/// Files are parsed according to their extension; standard input is parsed as
+/// YAML, which also accepts JSON.
+fn read_input<T: DeserializeOwned>(input: &InputFile) -> Result<T> {
+    if input.file == Path::new("-") {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        return parse_yaml(&contents);
+    }
+
+    let contents = std::fs::read_to_string(&input.file)?;
+    match ManifestFormat::from_path(&input.file)? {
+        ManifestFormat::Json => Ok(serde_json::from_str(&contents)?),
+        ManifestFormat::Yaml => parse_yaml(&contents),
+        ManifestFormat::Toml => {
+            toml::from_str(&contents).map_err(|e| Error::from(ManifestError::from(e)))
+        }
+    }
+}
+
+fn parse_yaml<T: DeserializeOwned>(contents: &str) -> Result<T> {
+    serde_yaml::from_str(contents).map_err(|e| Error::from(ManifestError::from(e)))
+}
@@ -0,0 +1,178 @@
+//! Client configuration from the environment or a profile file.
+//!
+//! The profile file is a TOML document with one table per profile:
+//!
+//! ```toml
+//! [default]
+//! api_key = "..."
+//!
+//! [staging]
+//! api_key = "..."
+//! base_url = "https://rest.runpod.io/v1"
+//! timeout_secs = 60
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use runpod_sdk::{Error, Result, RunpodBuilderError, RunpodConfig};
+use serde::Deserialize;
+
+/// Name of the profile used when none is given.
+const DEFAULT_PROFILE: &str = "default";
+
+/// A single profile of the configuration file.
+#[derive(Debug, Deserialize)]
+struct Profile {
+    api_key: String,
+    base_url: Option<String>,
+    timeout_secs: Option<u64>,
+}
+
+/// Where the client configuration is read from.
+#[derive(Debug, PartialEq, Eq)]
+enum Source {
+    /// The `RUNPOD_*` environment variables.
+    Env,
+    /// A profile file.
+    File(PathBuf),
+}
+
+/// Resolves the client configuration.
+///
+/// Without an explicit profile, [`RunpodConfig::from_env`] is used when
+/// `RUNPOD_API_KEY` is set. Otherwise the profile is read from `path`, or
+/// from `~/.runpod/config.toml`.
+pub fn load(profile: Option<&str>, path: Option<&Path>) -> Result<RunpodConfig> {
+    let env_api_key = std::env::var_os("RUNPOD_API_KEY").is_some();
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+
+    match source(profile, path, env_api_key, home)? {
+        Source::Env => RunpodConfig::from_env(),
+        Source::File(path) => from_file(profile, &path),
+    }
+}
+
+fn source(
+    profile: Option<&str>,
+    path: Option<&Path>,
+    env_api_key: bool,
+    home: Option<OsString>,
+) -> Result<Source> {
+    if profile.is_none() && env_api_key {
+        return Ok(Source::Env);
+    }
+
+    match (path, home) {
+        (Some(path), _) => Ok(Source::File(path.to_path_buf())),
+        (None, Some(home)) => Ok(Source::File(
+            PathBuf::from(home).join(".runpod").join("config.toml"),
+        )),
+        (None, None) => Err(config_error(
+            "cannot determine the home directory".to_string(),
+        )),
+    }
+}
+
+/// Reads `profile`, or the default profile, from the profile file at `path`.
+fn from_file(profile: Option<&str>, path: &Path) -> Result<RunpodConfig> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| config_error(format!("cannot read {}: {e}", path.display())))?;
+    let mut profiles: HashMap<String, Profile> = toml::from_str(&contents)
+        .map_err(|e| config_error(format!("invalid {}: {e}", path.display())))?;
+
+    let name = profile.unwrap_or(DEFAULT_PROFILE);
+    let profile = profiles
+        .remove(name)
+        .ok_or_else(|| config_error(format!("profile '{name}' not found in {}", path.display())))?;
+
+    let mut builder = RunpodConfig::builder().with_api_key(profile.api_key);
+    if let Some(base_url) = profile.base_url {
+        builder = builder.with_base_url(base_url);
+    }
+    if let Some(timeout_secs) = profile.timeout_secs {
+        builder = builder.with_timeout(Duration::from_secs(timeout_secs));
+    }
+
+    Ok(builder.build()?)
+}
+
+fn config_error(message: String) -> Error {
+    RunpodBuilderError::ValidationError(message).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILES: &str = r#"
+        [default]
+        api_key = "default-key"
+
+        [staging]
+        api_key = "staging-key"
+        base_url = "https://staging.example.com/v1"
+        timeout_secs = 60
+    "#;
+
+    fn profiles(name: &str, contents: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("runpodctl-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_env_takes_precedence_without_profile() -> Result<()> {
+        let path = Path::new("profiles.toml");
+        let home = Some(OsString::from("/home/me"));
+
+        assert_eq!(source(None, None, true, home.clone())?, Source::Env);
+        assert_eq!(source(None, Some(path), true, None)?, Source::Env);
+        assert_eq!(
+            source(Some("staging"), Some(path), true, None)?,
+            Source::File(path.to_path_buf())
+        );
+        assert_eq!(
+            source(Some("staging"), None, true, home)?,
+            Source::File(PathBuf::from("/home/me/.runpod/config.toml"))
+        );
+        assert!(source(None, None, false, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_loads_profiles_from_file() -> Result<()> {
+        let path = profiles("config.toml", PROFILES);
+
+        let config = from_file(None, &path)?;
+        assert_eq!(config.api_key(), "default-key");
+
+        let config = from_file(Some("staging"), &path)?;
+        assert_eq!(config.api_key(), "staging-key");
+        assert_eq!(config.base_url(), "https://staging.example.com/v1");
+        assert_eq!(config.timeout(), Duration::from_secs(60));
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_profile_is_rejected() {
+        let path = profiles("missing.toml", PROFILES);
+        let error = from_file(Some("production"), &path).unwrap_err();
+        assert!(error.to_string().contains("profile 'production' not found"));
+        std::fs::remove_file(path).unwrap();
+
+        let path = profiles("invalid.toml", "[default]\nbase_url = 1\n");
+        let error = from_file(None, &path).unwrap_err();
+        assert!(error.to_string().contains("invalid"));
+        std::fs::remove_file(path).unwrap();
+
+        let error = from_file(None, Path::new("/nonexistent/config.toml")).unwrap_err();
+        assert!(error.to_string().contains("cannot read"));
+    }
+}
@@ -0,0 +1,43 @@
+//! `runpodctl`: a command-line interface for RunPod built on the SDK.
+//!
+//! Every subcommand maps onto one [`runpod_sdk::service::v1`] operation, and
+//! request bodies are read from files using the SDK request models, so scripts
+//! and Rust services share the same definitions.
+//!
+//! ```text
+//! runpodctl pod list --desired-status RUNNING
+//! runpodctl endpoint create --file endpoint.yaml -o json
+//! runpodctl billing pods --bucket-size day --start-time 2025-01-01T00:00:00Z
+//! ```
+
+mod args;
+mod commands;
+mod config;
+mod output;
+
+use std::process::ExitCode;
+
+use clap::Parser;
+use runpod_sdk::{Result, RunpodClient};
+
+use crate::args::Cli;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let config = config::load(cli.profile.as_deref(), cli.config.as_deref())?;
+    let client = RunpodClient::new(config)?;
+
+    commands::run(&client, cli.command, cli.output).await
+}
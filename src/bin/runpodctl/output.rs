@@ -0,0 +1,201 @@
+//! Rendering of command results.
+
+use clap::ValueEnum;
+use runpod_sdk::{Error, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Output format of command results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns with the most relevant fields.
+    Table,
+    /// Pretty-printed JSON, using the API field names.
+    Json,
+    /// YAML, using the API field names.
+    Yaml,
+}
+
+/// Columns shown in table output, as `(header, dotted JSON path)` pairs.
+pub type Columns = &'static [(&'static str, &'static str)];
+
+pub const PODS: Columns = &[
+    ("ID", "id"),
+    ("NAME", "name"),
+    ("STATUS", "desiredStatus"),
+    ("IMAGE", "image"),
+    ("GPU", "gpu.displayName"),
+    ("GPUS", "gpuCount"),
+    ("$/HR", "costPerHr"),
+];
+
+pub const ENDPOINTS: Columns = &[
+    ("ID", "id"),
+    ("NAME", "name"),
+    ("TEMPLATE", "templateId"),
+    ("COMPUTE", "computeType"),
+    ("MIN", "workersMin"),
+    ("MAX", "workersMax"),
+    ("VERSION", "version"),
+];
+
+pub const TEMPLATES: Columns = &[
+    ("ID", "id"),
+    ("NAME", "name"),
+    ("IMAGE", "imageName"),
+    ("SERVERLESS", "isServerless"),
+    ("PUBLIC", "isPublic"),
+];
+
+pub const VOLUMES: Columns = &[
+    ("ID", "id"),
+    ("NAME", "name"),
+    ("SIZE (GB)", "size"),
+    ("DATA CENTER", "dataCenterId"),
+];
+
+pub const REGISTRY_AUTHS: Columns = &[("ID", "id"), ("NAME", "name")];
+
+pub const BILLING: Columns = &[
+    ("TIME", "time"),
+    ("AMOUNT", "amount"),
+    ("BILLED MS", "timeBilledMs"),
+    ("DISK GB", "diskSpaceBilledGb"),
+    ("POD", "podId"),
+    ("ENDPOINT", "endpointId"),
+    ("GPU", "gpuTypeId"),
+];
+
+/// Prints `value` to standard output in the requested format.
+pub fn print<T: Serialize>(value: &T, format: OutputFormat, columns: Columns) -> Result<()> {
+    print!("{}", render(value, format, columns)?);
+    Ok(())
+}
+
+/// Renders `value` in the requested format, ending with a newline.
+///
+/// Tables render a list as one row per element and a single object as a
+/// one-row table.
+fn render<T: Serialize>(value: &T, format: OutputFormat, columns: Columns) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(value)?)),
+        OutputFormat::Yaml => serde_yaml::to_string(value)
+            .map_err(runpod_sdk::manifest::ManifestError::from)
+            .map_err(Error::from),
+        OutputFormat::Table => {
+            let value = serde_json::to_value(value)?;
+            let rows = match &value {
+                Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            };
+            Ok(table(&rows, columns))
+        }
+    }
+}
+
+fn table(rows: &[&Value], columns: Columns) -> String {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|(_, path)| cell(lookup(row, path)))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (header, _))| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .fold(header.len(), usize::max)
+        })
+        .collect();
+
+    let mut out = String::new();
+    let headers = columns.iter().map(|(header, _)| header.to_string());
+    for line in std::iter::once(headers.collect::<Vec<_>>()).chain(cells) {
+        let line: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| value.get(key))
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "-".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| cell(Some(item)))
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const COLUMNS: Columns = &[("ID", "id"), ("GPU", "gpu.displayName"), ("PORTS", "ports")];
+
+    fn pods() -> Value {
+        json!([
+            { "id": "pod-1", "gpu": { "displayName": "A40" }, "ports": ["22/tcp", "8888/http"] },
+            { "id": "pod-with-long-id", "gpu": null },
+        ])
+    }
+
+    #[test]
+    fn test_renders_table() -> Result<()> {
+        let table = render(&pods(), OutputFormat::Table, COLUMNS)?;
+        assert_eq!(
+            table,
+            "ID                GPU  PORTS\n\
+             pod-1             A40  22/tcp,8888/http\n\
+             pod-with-long-id  -    -\n"
+        );
+
+        let single = render(&json!({ "id": "pod-1" }), OutputFormat::Table, COLUMNS)?;
+        assert_eq!(single, "ID     GPU  PORTS\npod-1  -    -\n");
+        Ok(())
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Pod {
+        id: &'static str,
+        cost_per_hr: f64,
+    }
+
+    #[test]
+    fn test_renders_json_and_yaml() -> Result<()> {
+        let value = Pod {
+            id: "pod-1",
+            cost_per_hr: 0.5,
+        };
+
+        let json = render(&value, OutputFormat::Json, COLUMNS)?;
+        assert_eq!(json, "{\n  \"id\": \"pod-1\",\n  \"costPerHr\": 0.5\n}\n");
+
+        let yaml = render(&value, OutputFormat::Yaml, COLUMNS)?;
+        assert_eq!(yaml, "id: pod-1\ncostPerHr: 0.5\n");
+        Ok(())
+    }
+}
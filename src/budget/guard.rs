@@ -0,0 +1,502 @@
+use std::ops::Deref;
+
+use chrono::Utc;
+
+use super::{BudgetLimits, BudgetStatus, start_of_day, start_of_month};
+use crate::analytics::{Attribution, BillingRange, BillingReport};
+use crate::model::v1::{
+    BucketSize, ComputeType, Endpoint, EndpointCreateInput, EndpointUpdateInput, Endpoints,
+    GetEndpointQuery, GetPodQuery, GpuTypeId, ListEndpointsQuery, ListPodsQuery, Pod,
+    PodCreateInput, PodStatus, PodUpdateInput, Pods,
+};
+use crate::pricing::{GpuPrice, PriceCatalog, gpu_type_key};
+use crate::service::v1::{BillingService, EndpointsService, PodsService};
+use crate::{Error, Result};
+
+/// Client wrapper that refuses requests exceeding [`BudgetLimits`].
+///
+/// Implements the guarded service traits, [`PodsService`] and
+/// [`EndpointsService`]; the other services of the wrapped client are reached
+/// through [`Deref`] or [`inner`](Self::inner). The following requests are
+/// checked before they are sent:
+///
+/// - [`create_pod`](PodsService::create_pod), estimated from the GPU count and
+///   the most expensive candidate GPU type in the requested cloud;
+/// - [`start_pod`](PodsService::start_pod) of a stopped Pod, estimated from
+///   its `cost_per_hr`;
+/// - [`create_endpoint`](EndpointsService::create_endpoint), estimated with all
+///   `workers_max` workers running;
+/// - [`update_endpoint`](EndpointsService::update_endpoint) when `workers_min`
+///   or `workers_max` increase, estimated from the difference in worst-case cost.
+///
+/// Nothing is estimated while no limit is set. CPU Pods and endpoints are not
+/// priced and are always allowed. GPU endpoints without `workers_max` have no
+/// worst-case cost and are refused while a limit is set.
+#[derive(Debug, Clone)]
+pub struct BudgetGuard<C> {
+    inner: C,
+    limits: BudgetLimits,
+    prices: PriceCatalog,
+}
+
+impl<C> BudgetGuard<C> {
+    /// Wraps `inner`, enforcing `limits` with GPU prices from `prices`.
+    pub fn new(inner: C, limits: BudgetLimits, prices: PriceCatalog) -> Self {
+        Self {
+            inner,
+            limits,
+            prices,
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Consumes the guard, returning the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Returns the enforced limits.
+    pub fn limits(&self) -> &BudgetLimits {
+        &self.limits
+    }
+
+    /// Returns the price catalog used for estimates.
+    pub fn prices(&self) -> &PriceCatalog {
+        &self.prices
+    }
+
+    /// Estimates the worst-case hourly cost of a Pod created from `input`, in USD.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Validation`] if no price is known for a candidate GPU type.
+    pub fn pod_rate(&self, input: &PodCreateInput) -> Result<f64> {
+        if input.compute_type == Some(ComputeType::Cpu) {
+            return Ok(0.0);
+        }
+
+        let estimate = self.prices.estimate_pod(input, None);
+        if let Some(gpu_type) = estimate.unpriced.first() {
+            return Err(Error::Validation(format!(
+                "no price known for GPU type '{gpu_type}'"
+            )));
+        }
+        match estimate.compute() {
+            Some(compute) => Ok(compute.max),
+            None => Err(Error::Validation(
+                "no GPU price known for the requested cloud".to_string(),
+            )),
+        }
+    }
+
+    /// Estimates the worst-case hourly cost of an endpoint, in USD.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Validation`] if the endpoint has no `workers_max` or no
+    /// price is known for a candidate GPU type.
+    pub fn endpoint_rate(&self, endpoint: &EndpointScale<'_>) -> Result<f64> {
+        if endpoint.compute_type == ComputeType::Cpu {
+            return Ok(0.0);
+        }
+
+        let Some(workers_max) = endpoint.workers_max else {
+            return Err(Error::Validation(
+                "endpoint without workers_max has no worst-case cost".to_string(),
+            ));
+        };
+        let price = self.gpu_rate(endpoint.gpu_type_ids, |price| {
+            price.secure.or_else(|| price.max())
+        })?;
+        let workers = workers_max.max(endpoint.workers_min);
+
+        Ok(price * f64::from(endpoint.gpu_count.max(0)) * f64::from(workers.max(0)))
+    }
+
+    /// Returns whether any limit is set.
+    fn is_limited(&self) -> bool {
+        self.limits != BudgetLimits::default()
+    }
+
+    /// Returns the price of the most expensive candidate GPU type, or of any
+    /// GPU in the catalog if there are no candidates.
+    fn gpu_rate(
+        &self,
+        gpu_type_ids: Option<&[GpuTypeId]>,
+        price: impl Fn(&GpuPrice) -> Option<f64>,
+    ) -> Result<f64> {
+        let Some(gpu_type_ids) = gpu_type_ids.filter(|ids| !ids.is_empty()) else {
+            let max = self
+                .prices
+                .gpus
+                .values()
+                .filter_map(&price)
+                .reduce(f64::max);
+            return max.ok_or_else(|| Error::Validation("price catalog is empty".to_string()));
+        };
+
+        gpu_type_ids.iter().try_fold(0.0, |max: f64, gpu_type| {
+            let rate = self.prices.gpu(gpu_type).and_then(&price).ok_or_else(|| {
+                let gpu_type = gpu_type_key(gpu_type);
+                Error::Validation(format!("no price known for GPU type '{gpu_type}'"))
+            })?;
+            Ok(max.max(rate))
+        })
+    }
+}
+
+impl<C> BudgetGuard<C>
+where
+    C: PodsService + BillingService,
+{
+    /// Captures the current spend of the account.
+    ///
+    /// Billing history is only fetched if a daily or monthly limit is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn status(&self) -> Result<BudgetStatus> {
+        let at = Utc::now();
+        let query = ListPodsQuery {
+            desired_status: Some(PodStatus::Running),
+            ..Default::default()
+        };
+        let billing = async {
+            if !self.limits.needs_billing() {
+                return Ok(None);
+            }
+            let range = BillingRange::new(start_of_month(at), at, BucketSize::Day);
+            let report =
+                BillingReport::collect_with(&self.inner, &range, &Attribution::default()).await?;
+            Ok(Some(report))
+        };
+        let (pods, billing) = futures::try_join!(self.inner.list_pods(query), billing)?;
+
+        let hourly_rate = pods
+            .iter()
+            .filter(|pod| pod.desired_status == PodStatus::Running)
+            .map(|pod| pod.cost_per_hr)
+            .sum();
+        let (spent_today, spent_this_month) = match billing {
+            Some(report) => {
+                let today = start_of_day(at);
+                let spent_today = report
+                    .records
+                    .iter()
+                    .filter(|record| record.time >= today)
+                    .map(|record| record.amount)
+                    .sum();
+                (spent_today, report.total())
+            }
+            None => (0.0, 0.0),
+        };
+
+        Ok(BudgetStatus {
+            at,
+            hourly_rate,
+            spent_today,
+            spent_this_month,
+        })
+    }
+
+    /// Checks whether adding `incremental` USD per hour stays within the limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BudgetExceeded`] if a limit would be exceeded, or
+    /// another error if the current spend cannot be determined.
+    pub async fn check(&self, incremental: f64) -> Result<()> {
+        if incremental <= 0.0 || !self.is_limited() {
+            return Ok(());
+        }
+
+        let status = self.status().await?;
+        let result = self.limits.check(&status, incremental);
+
+        #[cfg(feature = "tracing")]
+        if let Err(exceeded) = &result {
+            tracing::warn!(
+                period = %exceeded.period,
+                limit = exceeded.limit,
+                projected = exceeded.projected,
+                incremental,
+                "Request refused by budget guard"
+            );
+        }
+
+        result.map_err(Error::from)
+    }
+}
+
+/// Scaling parameters of an endpoint used for cost estimates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointScale<'a> {
+    /// Compute type of the workers.
+    pub compute_type: ComputeType,
+    /// Candidate GPU types of the workers.
+    pub gpu_type_ids: Option<&'a [GpuTypeId]>,
+    /// GPUs per worker.
+    pub gpu_count: i32,
+    /// Minimum number of workers.
+    pub workers_min: i32,
+    /// Maximum number of workers, if capped.
+    pub workers_max: Option<i32>,
+}
+
+impl<'a> From<&'a EndpointCreateInput> for EndpointScale<'a> {
+    fn from(input: &'a EndpointCreateInput) -> Self {
+        Self {
+            compute_type: input.compute_type.unwrap_or_default(),
+            gpu_type_ids: input.gpu_type_ids.as_deref(),
+            gpu_count: input.gpu_count.unwrap_or(1),
+            workers_min: input.workers_min.unwrap_or(0),
+            workers_max: input.workers_max,
+        }
+    }
+}
+
+impl<'a> From<&'a Endpoint> for EndpointScale<'a> {
+    fn from(endpoint: &'a Endpoint) -> Self {
+        Self {
+            compute_type: endpoint.compute_type,
+            gpu_type_ids: endpoint.gpu_type_ids.as_deref(),
+            gpu_count: endpoint.gpu_count.unwrap_or(1),
+            workers_min: endpoint.workers_min,
+            workers_max: Some(endpoint.workers_max),
+        }
+    }
+}
+
+impl<'a> EndpointScale<'a> {
+    /// Applies the scaling fields of `input` to `self`.
+    pub fn updated(self, input: &'a EndpointUpdateInput) -> Self {
+        Self {
+            compute_type: self.compute_type,
+            gpu_type_ids: input.gpu_type_ids.as_deref().or(self.gpu_type_ids),
+            gpu_count: input.gpu_count.unwrap_or(self.gpu_count),
+            workers_min: input.workers_min.unwrap_or(self.workers_min),
+            workers_max: input.workers_max.or(self.workers_max),
+        }
+    }
+}
+
+impl<C> PodsService for BudgetGuard<C>
+where
+    C: PodsService + BillingService,
+{
+    async fn create_pod(&self, input: PodCreateInput) -> Result<Pod> {
+        if self.is_limited() {
+            self.check(self.pod_rate(&input)?).await?;
+        }
+        self.inner.create_pod(input).await
+    }
+
+    async fn list_pods(&self, query: ListPodsQuery) -> Result<Pods> {
+        self.inner.list_pods(query).await
+    }
+
+    async fn get_pod(&self, pod_id: &str, query: GetPodQuery) -> Result<Pod> {
+        self.inner.get_pod(pod_id, query).await
+    }
+
+    async fn update_pod(&self, pod_id: &str, input: PodUpdateInput) -> Result<Pod> {
+        self.inner.update_pod(pod_id, input).await
+    }
+
+    async fn delete_pod(&self, pod_id: &str) -> Result<()> {
+        self.inner.delete_pod(pod_id).await
+    }
+
+    async fn start_pod(&self, pod_id: &str) -> Result<()> {
+        if self.is_limited() {
+            let pod = self.inner.get_pod(pod_id, GetPodQuery::default()).await?;
+            if pod.desired_status != PodStatus::Running {
+                self.check(pod.cost_per_hr).await?;
+            }
+        }
+        self.inner.start_pod(pod_id).await
+    }
+
+    async fn stop_pod(&self, pod_id: &str) -> Result<()> {
+        self.inner.stop_pod(pod_id).await
+    }
+
+    async fn reset_pod(&self, pod_id: &str) -> Result<()> {
+        self.inner.reset_pod(pod_id).await
+    }
+
+    async fn restart_pod(&self, pod_id: &str) -> Result<()> {
+        self.inner.restart_pod(pod_id).await
+    }
+}
+
+impl<C> EndpointsService for BudgetGuard<C>
+where
+    C: EndpointsService + PodsService + BillingService,
+{
+    async fn create_endpoint(&self, input: EndpointCreateInput) -> Result<Endpoint> {
+        if self.is_limited() {
+            self.check(self.endpoint_rate(&EndpointScale::from(&input))?)
+                .await?;
+        }
+        self.inner.create_endpoint(input).await
+    }
+
+    async fn list_endpoints(&self, query: ListEndpointsQuery) -> Result<Endpoints> {
+        self.inner.list_endpoints(query).await
+    }
+
+    async fn get_endpoint(&self, endpoint_id: &str, query: GetEndpointQuery) -> Result<Endpoint> {
+        self.inner.get_endpoint(endpoint_id, query).await
+    }
+
+    async fn update_endpoint(
+        &self,
+        endpoint_id: &str,
+        input: EndpointUpdateInput,
+    ) -> Result<Endpoint> {
+        if self.is_limited() && (input.workers_min.is_some() || input.workers_max.is_some()) {
+            let current = self
+                .inner
+                .get_endpoint(endpoint_id, GetEndpointQuery::default())
+                .await?;
+            let before = EndpointScale::from(&current);
+            let after = before.updated(&input);
+
+            let scales_up = after.workers_min > before.workers_min
+                || after.workers_max.unwrap_or(0) > before.workers_max.unwrap_or(0);
+            if scales_up {
+                let incremental = self.endpoint_rate(&after)? - self.endpoint_rate(&before)?;
+                self.check(incremental).await?;
+            }
+        }
+        self.inner.update_endpoint(endpoint_id, input).await
+    }
+
+    async fn delete_endpoint(&self, endpoint_id: &str) -> Result<()> {
+        self.inner.delete_endpoint(endpoint_id).await
+    }
+}
+
+impl<C> Deref for BudgetGuard<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::v1::CloudType;
+    use crate::testing::Cloud;
+
+    fn guard() -> BudgetGuard<()> {
+        let prices = PriceCatalog::new()
+            .with_gpu(
+                &GpuTypeId::NvidiaA40,
+                GpuPrice {
+                    secure: Some(0.4),
+                    community: Some(0.3),
+                    secure_spot: Some(0.2),
+                    community_spot: Some(0.1),
+                },
+            )
+            .with_gpu(
+                &GpuTypeId::NvidiaH200,
+                GpuPrice {
+                    secure: Some(4.0),
+                    ..Default::default()
+                },
+            );
+        BudgetGuard::new((), BudgetLimits::default(), prices)
+    }
+
+    #[test]
+    fn test_pod_rate() -> Result<()> {
+        let guard = guard();
+        let input = PodCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40]),
+            gpu_count: Some(2),
+            cloud_type: Some(CloudType::Community),
+            interruptible: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(guard.pod_rate(&input)?, 0.2);
+
+        let input = PodCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40, GpuTypeId::NvidiaH200]),
+            ..Default::default()
+        };
+        assert_eq!(guard.pod_rate(&input)?, 4.0);
+
+        let input = PodCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaL4]),
+            ..Default::default()
+        };
+        assert!(matches!(guard.pod_rate(&input), Err(Error::Validation(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_guard_skips_estimates() -> Result<()> {
+        let guard = BudgetGuard::new(Cloud::new(), BudgetLimits::default(), PriceCatalog::new());
+        let input = PodCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaL4]),
+            ..Default::default()
+        };
+        guard.create_pod(input).await?;
+        guard
+            .create_endpoint(EndpointCreateInput::default())
+            .await?;
+        assert_eq!(
+            guard.calls(),
+            ["create_pod pod-1", "create_endpoint endpoint-2"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_limited_guard_refuses_unbounded_endpoints() {
+        let limits = BudgetLimits {
+            hourly: Some(10.0),
+            ..Default::default()
+        };
+        let guard = BudgetGuard::new(Cloud::new(), limits, guard().prices);
+        let input = EndpointCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40]),
+            ..Default::default()
+        };
+        let result = guard.create_endpoint(input).await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+        assert!(guard.calls().is_empty());
+    }
+
+    #[test]
+    fn test_endpoint_rate_uses_workers_max() -> Result<()> {
+        let guard = guard();
+        let input = EndpointCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40]),
+            gpu_count: Some(2),
+            workers_min: Some(1),
+            workers_max: Some(5),
+            ..Default::default()
+        };
+        let scale = EndpointScale::from(&input);
+        assert!((guard.endpoint_rate(&scale)? - 4.0).abs() < 1e-9);
+
+        let update = EndpointUpdateInput {
+            workers_max: Some(10),
+            ..Default::default()
+        };
+        assert!((guard.endpoint_rate(&scale.updated(&update))? - 8.0).abs() < 1e-9);
+        Ok(())
+    }
+}
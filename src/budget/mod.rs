@@ -0,0 +1,259 @@
+//! Budget guardrails for resource creation.
+//!
+//! A [`BudgetGuard`] wraps a client and checks every request that can raise
+//! the account's hourly spend, creating or starting Pods and creating or
+//! scaling up endpoints, against hourly, daily and monthly [`BudgetLimits`].
+//! Requests whose projected spend exceeds a limit fail with
+//! [`Error::BudgetExceeded`](crate::Error::BudgetExceeded) instead of being
+//! sent.
+//!
+//! Projected spend is the current hourly rate of all running Pods plus the
+//! estimated rate of the request, extended to the end of the current UTC day
+//! or month and added to what has already been billed in that period.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::budget::{BudgetGuard, BudgetLimits};
+//! use runpod_sdk::model::v1::{GpuTypeId, PodCreateInput};
+//! use runpod_sdk::pricing::{GpuPrice, PriceCatalog};
+//! use runpod_sdk::service::v1::PodsService;
+//! use runpod_sdk::{Error, Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let limits = BudgetLimits {
+//!     hourly: Some(10.0),
+//!     daily: Some(150.0),
+//!     ..Default::default()
+//! };
+//! let prices = PriceCatalog::new().with_gpu(
+//!     &GpuTypeId::NvidiaA40,
+//!     GpuPrice {
+//!         secure: Some(0.40),
+//!         ..Default::default()
+//!     },
+//! );
+//! let guarded = BudgetGuard::new(client, limits, prices);
+//!
+//! let input = PodCreateInput {
+//!     gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40]),
+//!     gpu_count: Some(8),
+//!     image_name: Some("runpod/pytorch:latest".to_string()),
+//!     ..Default::default()
+//! };
+//! match guarded.create_pod(input).await {
+//!     Ok(pod) => println!("Created pod: {}", pod.id),
+//!     Err(Error::BudgetExceeded(e)) => println!("Refused: {e}"),
+//!     Err(e) => return Err(e),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod guard;
+
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+pub use guard::{BudgetGuard, EndpointScale};
+use serde::{Deserialize, Serialize};
+
+/// Spending limits, in USD. Unset limits are not enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetLimits {
+    /// Maximum hourly spend rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hourly: Option<f64>,
+
+    /// Maximum spend per UTC calendar day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<f64>,
+
+    /// Maximum spend per UTC calendar month.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly: Option<f64>,
+}
+
+impl BudgetLimits {
+    /// Checks the spend projected after adding `incremental` USD per hour.
+    ///
+    /// Limits are checked from the shortest period to the longest; the first
+    /// one exceeded is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BudgetExceeded`] if any projection exceeds its limit.
+    pub fn check(&self, status: &BudgetStatus, incremental: f64) -> Result<(), BudgetExceeded> {
+        let rate = status.hourly_rate + incremental;
+        let checks = [
+            (BudgetPeriod::Hourly, self.hourly, rate),
+            (
+                BudgetPeriod::Daily,
+                self.daily,
+                status.spent_today + rate * status.hours_left_in_day(),
+            ),
+            (
+                BudgetPeriod::Monthly,
+                self.monthly,
+                status.spent_this_month + rate * status.hours_left_in_month(),
+            ),
+        ];
+
+        for (period, limit, projected) in checks {
+            if let Some(limit) = limit.filter(|limit| projected > *limit) {
+                return Err(BudgetExceeded {
+                    period,
+                    limit,
+                    projected,
+                    incremental,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if a daily or monthly limit requires billing history.
+    pub fn needs_billing(&self) -> bool {
+        self.daily.is_some() || self.monthly.is_some()
+    }
+}
+
+/// Current spend of an account, used to project spend of a new request.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    /// Time the status was captured.
+    pub at: DateTime<Utc>,
+
+    /// Combined hourly cost of all running Pods, in USD.
+    pub hourly_rate: f64,
+
+    /// Amount billed since the start of the current UTC day, in USD.
+    pub spent_today: f64,
+
+    /// Amount billed since the start of the current UTC month, in USD.
+    pub spent_this_month: f64,
+}
+
+impl BudgetStatus {
+    /// Hours remaining until the end of the current UTC day.
+    pub fn hours_left_in_day(&self) -> f64 {
+        hours_until(self.at, start_of_day(self.at) + chrono::Duration::days(1))
+    }
+
+    /// Hours remaining until the end of the current UTC month.
+    pub fn hours_left_in_month(&self) -> f64 {
+        let start = start_of_month(self.at);
+        let end = start.checked_add_months(Months::new(1)).unwrap_or(start);
+        hours_until(self.at, end)
+    }
+}
+
+/// Period a [`BudgetLimits`] entry applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    /// Hourly spend rate.
+    Hourly,
+    /// UTC calendar day.
+    Daily,
+    /// UTC calendar month.
+    Monthly,
+}
+
+impl fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hourly => f.write_str("hourly"),
+            Self::Daily => f.write_str("daily"),
+            Self::Monthly => f.write_str("monthly"),
+        }
+    }
+}
+
+/// A request was refused because it would exceed a budget limit.
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+#[error(
+    "{period} budget of ${limit:.2} exceeded: projected ${projected:.2} \
+     with ${incremental:.2}/hr added"
+)]
+pub struct BudgetExceeded {
+    /// Period whose limit was exceeded.
+    pub period: BudgetPeriod,
+
+    /// Configured limit, in USD.
+    pub limit: f64,
+
+    /// Projected spend including the request, in USD.
+    pub projected: f64,
+
+    /// Estimated hourly cost added by the request, in USD.
+    pub incremental: f64,
+}
+
+fn start_of_day(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.date_naive().and_time(Default::default()).and_utc()
+}
+
+fn start_of_month(time: DateTime<Utc>) -> DateTime<Utc> {
+    let date = time.date_naive();
+    NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+        .unwrap_or(date)
+        .and_time(Default::default())
+        .and_utc()
+}
+
+fn hours_until(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds().max(0) as f64 / 3600.0
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn status() -> BudgetStatus {
+        BudgetStatus {
+            // 12 hours left in the day, 30 days and 12 hours left in January.
+            at: Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+            hourly_rate: 2.0,
+            spent_today: 10.0,
+            spent_this_month: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_remaining_hours() {
+        let status = status();
+        assert_eq!(status.hours_left_in_day(), 12.0);
+        assert_eq!(status.hours_left_in_month(), 30.0 * 24.0 + 12.0);
+    }
+
+    #[test]
+    fn test_check_projects_each_period() {
+        let limits = BudgetLimits {
+            hourly: Some(5.0),
+            daily: Some(50.0),
+            monthly: None,
+        };
+        assert_eq!(limits.check(&status(), 1.0), Ok(()));
+
+        let exceeded = limits.check(&status(), 4.0).unwrap_err();
+        assert_eq!(exceeded.period, BudgetPeriod::Hourly);
+        assert_eq!(exceeded.projected, 6.0);
+
+        // 10 + (2 + 2) * 12 = 58.
+        let exceeded = limits.check(&status(), 2.0).unwrap_err();
+        assert_eq!(exceeded.period, BudgetPeriod::Daily);
+        assert_eq!(exceeded.projected, 58.0);
+    }
+
+    #[test]
+    fn test_check_without_limits() {
+        assert_eq!(BudgetLimits::default().check(&status(), 1e6), Ok(()));
+        assert!(!BudgetLimits::default().needs_billing());
+    }
+}
@@ -0,0 +1,552 @@
+//! Capacity planning for network volumes.
+//!
+//! A [`CapacityPlanner`] records how much of each network volume is used over
+//! time, projects when a volume fills up, and recommends growing it in
+//! [`CapacityPolicy::increment_gb`] steps before it does. Usage comes from a
+//! [`UsageProbe`], such as the S3-compatible listing of the volume with the
+//! `volume-data` feature, or from samples reported by a worker, e.g. using
+//! [`directory_size`] on the mounted volume.
+//!
+//! Every [`CapacityPlan`] includes the monthly cost of the volume before and
+//! after resizing, priced from network volume billing when collected. Network
+//! volumes can only grow, so [`resize_volume`] rejects smaller sizes locally.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use chrono::{Duration, Utc};
+//! use runpod_sdk::capacity::{CapacityPlanner, resize_volume};
+//! use runpod_sdk::service::v1::VolumesService;
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let volume = client.get_volume("volume_id").await?;
+//!
+//! let end = Utc::now();
+//! let mut planner = CapacityPlanner::new();
+//! planner
+//!     .collect_billing(&client, &volume.id, end - Duration::days(30), end)
+//!     .await?;
+//! // Reported by a worker with the volume mounted at /runpod-volume.
+//! planner.add_sample(&volume.id, end, 412_000_000_000);
+//!
+//! let plan = planner.plan(&volume);
+//! if let Some(size_gb) = plan.recommended_size_gb {
+//!     println!("+${:.2}/month", plan.monthly_cost_delta());
+//!     resize_volume(&client, &volume, size_gb).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::{BillingRange, BillingWindows};
+use crate::model::v1::{
+    BillingRecord, BucketSize, NetworkVolume, NetworkVolumeBillingQuery, NetworkVolumeUpdateInput,
+};
+use crate::pricing::{HOURS_PER_MONTH, StoragePrices};
+use crate::service::v1::{BillingService, VolumesService};
+use crate::{Error, Result};
+
+/// Bytes in a gigabyte.
+pub const BYTES_PER_GB: u64 = 1_000_000_000;
+
+/// Largest network volume size the API accepts, in gigabytes.
+pub const MAX_VOLUME_SIZE_GB: u32 = 4_000;
+
+/// Source of the used space of a network volume.
+pub trait UsageProbe {
+    /// Returns the bytes used on `volume`.
+    fn used_bytes(&self, volume: &NetworkVolume) -> impl Future<Output = Result<u64>>;
+}
+
+/// Probe summing the sizes of the files listed through the S3-compatible API.
+///
+/// Listing walks every file of the volume, so sample large volumes sparingly.
+#[cfg(feature = "volume-data")]
+#[cfg_attr(docsrs, doc(cfg(feature = "volume-data")))]
+#[derive(Debug, Clone)]
+pub struct S3UsageProbe {
+    credentials: crate::volume_data::S3Credentials,
+    endpoint: Option<String>,
+}
+
+#[cfg(feature = "volume-data")]
+impl S3UsageProbe {
+    /// Creates a probe using `credentials`.
+    pub fn new(credentials: crate::volume_data::S3Credentials) -> Self {
+        Self {
+            credentials,
+            endpoint: None,
+        }
+    }
+
+    /// Replaces the S3 API endpoint, e.g. with a local S3-compatible server.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+}
+
+#[cfg(feature = "volume-data")]
+impl UsageProbe for S3UsageProbe {
+    async fn used_bytes(&self, volume: &NetworkVolume) -> Result<u64> {
+        let mut data = crate::volume_data::VolumeData::new(volume, self.credentials.clone());
+        if let Some(endpoint) = &self.endpoint {
+            data = data.with_endpoint(endpoint.clone());
+        }
+        let objects = data.list("").await?;
+        Ok(objects.iter().map(|object| object.size).sum())
+    }
+}
+
+/// Returns the total size of the files under `path`, without following
+/// symbolic links.
+///
+/// Meant to run on a Pod or worker with the volume mounted, e.g. at
+/// `/runpod-volume`.
+///
+/// # Errors
+///
+/// Returns an error if a directory or file cannot be read.
+pub fn directory_size(path: impl AsRef<Path>) -> Result<u64> {
+    let mut size = 0;
+    let mut directories = vec![path.as_ref().to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in std::fs::read_dir(directory)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                directories.push(entry.path());
+            } else if file_type.is_file() {
+                size += entry.metadata()?.len();
+            }
+        }
+    }
+    Ok(size)
+}
+
+/// Thresholds of a [`CapacityPlanner`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapacityPolicy {
+    /// Size, in gigabytes, volumes are grown by at a time.
+    pub increment_gb: u32,
+
+    /// Highest share of the volume, from 0 to 1, that should be used at the
+    /// end of the lead time.
+    pub target_utilization: f64,
+
+    /// How far ahead usage is projected when recommending a size.
+    pub lead_time: Duration,
+
+    /// Largest size, in gigabytes, the planner recommends.
+    pub max_size_gb: u32,
+}
+
+impl Default for CapacityPolicy {
+    fn default() -> Self {
+        Self {
+            increment_gb: 10,
+            target_utilization: 0.8,
+            lead_time: Duration::days(7),
+            max_size_gb: MAX_VOLUME_SIZE_GB,
+        }
+    }
+}
+
+/// Used space of a network volume at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSample {
+    /// Time of the sample.
+    pub at: DateTime<Utc>,
+
+    /// Bytes used.
+    pub used_bytes: u64,
+}
+
+/// Capacity of a network volume and the recommended size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapacityPlan {
+    /// Network volume ID.
+    pub volume_id: String,
+
+    /// Allocated size in gigabytes.
+    pub allocated_gb: u32,
+
+    /// Bytes used at the latest sample, if any.
+    pub used_bytes: Option<u64>,
+
+    /// Growth in bytes per day, if at least two samples span some time.
+    pub growth_per_day: Option<f64>,
+
+    /// When the volume is projected to be full, if it is growing.
+    pub full_at: Option<DateTime<Utc>>,
+
+    /// Recommended size in gigabytes, if the volume should grow.
+    pub recommended_size_gb: Option<u32>,
+
+    /// Price of a gigabyte per month, in USD.
+    pub price_per_gb_month: f64,
+
+    /// Monthly cost at the allocated size, in USD.
+    pub current_monthly_cost: f64,
+
+    /// Monthly cost at the recommended size, in USD.
+    pub projected_monthly_cost: f64,
+}
+
+impl CapacityPlan {
+    /// Share of the allocated space in use, from 0 to 1.
+    pub fn utilization(&self) -> Option<f64> {
+        let allocated = u64::from(self.allocated_gb) * BYTES_PER_GB;
+        match allocated {
+            0 => None,
+            _ => Some(self.used_bytes? as f64 / allocated as f64),
+        }
+    }
+
+    /// Returns `true` if the volume should grow.
+    pub fn needs_resize(&self) -> bool {
+        self.recommended_size_gb.is_some()
+    }
+
+    /// Additional monthly cost of the recommended size, in USD.
+    pub fn monthly_cost_delta(&self) -> f64 {
+        self.projected_monthly_cost - self.current_monthly_cost
+    }
+
+    /// Returns an update growing the volume to the recommended size.
+    pub fn update_input(&self) -> Option<NetworkVolumeUpdateInput> {
+        Some(NetworkVolumeUpdateInput {
+            size: Some(self.recommended_size_gb?),
+            ..Default::default()
+        })
+    }
+}
+
+/// Plans the size of network volumes from their usage over time.
+///
+/// Growth is projected linearly from the recorded samples. Without billing,
+/// volumes are priced at the default [`StoragePrices::network_volume`] rate.
+#[derive(Debug, Clone, Default)]
+pub struct CapacityPlanner {
+    policy: CapacityPolicy,
+    samples: BTreeMap<String, Vec<UsageSample>>,
+    price_per_gb_month: Option<f64>,
+}
+
+impl CapacityPlanner {
+    /// Creates a planner with the default policy and no history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the planner policy.
+    pub fn with_policy(mut self, policy: CapacityPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the planner policy.
+    pub fn policy(&self) -> &CapacityPolicy {
+        &self.policy
+    }
+
+    /// Returns the recorded samples of a volume, oldest first.
+    pub fn samples(&self, volume_id: &str) -> &[UsageSample] {
+        self.samples.get(volume_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Records that `used_bytes` were used on a volume at `at`.
+    pub fn add_sample(&mut self, volume_id: &str, at: DateTime<Utc>, used_bytes: u64) {
+        let samples = self.samples.entry(volume_id.to_string()).or_default();
+        let index = samples.partition_point(|sample| sample.at <= at);
+        samples.insert(index, UsageSample { at, used_bytes });
+    }
+
+    /// Measures the used space of `volume` now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the probe fails.
+    pub async fn sample<P: UsageProbe>(&mut self, probe: &P, volume: &NetworkVolume) -> Result<()> {
+        let used_bytes = probe.used_bytes(volume).await?;
+        self.add_sample(&volume.id, Utc::now(), used_bytes);
+        Ok(())
+    }
+
+    /// Derives the price of a gigabyte per month from daily volume billing.
+    ///
+    /// Records without billed disk space are ignored. Returns `false` and
+    /// keeps the current price if no record qualifies.
+    pub fn add_billing(&mut self, records: &[BillingRecord]) -> bool {
+        let (amount, gb_hours) = records
+            .iter()
+            .filter_map(|record| {
+                let gb = record.disk_space_billed_gb.filter(|gb| *gb > 0)?;
+                Some((record.amount, f64::from(gb) * 24.0))
+            })
+            .fold((0.0, 0.0), |(amount, gb_hours), (a, g)| {
+                (amount + a, gb_hours + g)
+            });
+        if gb_hours == 0.0 {
+            return false;
+        }
+        self.price_per_gb_month = Some(amount / gb_hours * HOURS_PER_MONTH);
+        true
+    }
+
+    /// Fetches daily billing of `volume_id` between `start` and `end` and
+    /// derives the storage price from it.
+    ///
+    /// Days partially inside the range lower the derived price, so prefer
+    /// ranges of whole days.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails.
+    pub async fn collect_billing<C: BillingService>(
+        &mut self,
+        client: &C,
+        volume_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<()> {
+        let range = BillingRange::new(start, end, BucketSize::Day);
+        let query = NetworkVolumeBillingQuery {
+            network_volume_id: Some(volume_id.to_string()),
+            ..range.volume_query()
+        };
+        let records = BillingWindows::default()
+            .volume_billing(client, query)
+            .await?;
+        self.add_billing(&records);
+        Ok(())
+    }
+
+    /// Plans the capacity of `volume` from its recorded samples.
+    pub fn plan(&self, volume: &NetworkVolume) -> CapacityPlan {
+        let policy = &self.policy;
+        let price = self
+            .price_per_gb_month
+            .unwrap_or(StoragePrices::default().network_volume);
+        let allocated_gb = u32::try_from(volume.size).unwrap_or_default();
+        let allocated = u64::from(allocated_gb) * BYTES_PER_GB;
+
+        let samples = self.samples(&volume.id);
+        let latest = samples.last().copied();
+        let growth_per_day = growth_per_day(samples);
+
+        let full_at = latest.zip(growth_per_day).and_then(|(latest, growth)| {
+            if growth <= 0.0 {
+                return None;
+            }
+            let days = allocated.saturating_sub(latest.used_bytes) as f64 / growth;
+            let seconds = (days * 86_400.0).min(i64::MAX as f64 / 1_000.0) as i64;
+            latest.at.checked_add_signed(Duration::seconds(seconds))
+        });
+
+        let recommended_size_gb = latest.and_then(|latest| {
+            let lead_days = policy.lead_time.num_seconds() as f64 / 86_400.0;
+            let projected =
+                latest.used_bytes as f64 + growth_per_day.unwrap_or_default().max(0.0) * lead_days;
+            let required_gb =
+                projected / policy.target_utilization.max(f64::EPSILON) / BYTES_PER_GB as f64;
+            if required_gb <= f64::from(allocated_gb) {
+                return None;
+            }
+
+            let increment = policy.increment_gb.max(1);
+            let steps = ((required_gb - f64::from(allocated_gb)) / f64::from(increment)).ceil();
+            let size = (f64::from(allocated_gb) + steps * f64::from(increment))
+                .min(f64::from(policy.max_size_gb.min(MAX_VOLUME_SIZE_GB)));
+            let size = size as u32;
+            (size > allocated_gb).then_some(size)
+        });
+
+        let current_monthly_cost = f64::from(allocated_gb) * price;
+        CapacityPlan {
+            volume_id: volume.id.clone(),
+            allocated_gb,
+            used_bytes: latest.map(|latest| latest.used_bytes),
+            growth_per_day,
+            full_at,
+            recommended_size_gb,
+            price_per_gb_month: price,
+            current_monthly_cost,
+            projected_monthly_cost: recommended_size_gb
+                .map_or(current_monthly_cost, |size| f64::from(size) * price),
+        }
+    }
+
+    /// Grows `volume` to its recommended size, if any.
+    ///
+    /// Returns the updated volume, or `None` if it does not need to grow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn grow<C: VolumesService>(
+        &self,
+        client: &C,
+        volume: &NetworkVolume,
+    ) -> Result<Option<NetworkVolume>> {
+        match self.plan(volume).recommended_size_gb {
+            Some(size_gb) => Ok(Some(resize_volume(client, volume, size_gb).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Grows `volume` to `size_gb` gigabytes.
+///
+/// The API only supports growing network volumes, so smaller sizes are
+/// rejected without sending a request. Resizing to the current size returns
+/// the volume unchanged.
+///
+/// # Errors
+///
+/// Returns [`Error::Validation`] if `size_gb` is smaller than the volume or
+/// larger than [`MAX_VOLUME_SIZE_GB`], or an error if the request fails.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip(client, volume), fields(volume_id = %volume.id)))]
+pub async fn resize_volume<C: VolumesService>(
+    client: &C,
+    volume: &NetworkVolume,
+    size_gb: u32,
+) -> Result<NetworkVolume> {
+    let current = u32::try_from(volume.size).unwrap_or_default();
+    if size_gb < current {
+        return Err(Error::Validation(format!(
+            "network volume {} cannot shrink from {current} GB to {size_gb} GB",
+            volume.id
+        )));
+    }
+    if size_gb > MAX_VOLUME_SIZE_GB {
+        return Err(Error::Validation(format!(
+            "network volume {} cannot exceed {MAX_VOLUME_SIZE_GB} GB",
+            volume.id
+        )));
+    }
+    if size_gb == current {
+        return Ok(volume.clone());
+    }
+
+    let input = NetworkVolumeUpdateInput {
+        size: Some(size_gb),
+        ..Default::default()
+    };
+    client.update_volume(&volume.id, input).await
+}
+
+/// Least-squares growth of the samples, in bytes per day.
+fn growth_per_day(samples: &[UsageSample]) -> Option<f64> {
+    let first = samples.first()?.at;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|sample| {
+            let days = (sample.at - first).num_seconds() as f64 / 86_400.0;
+            (days, sample.used_bytes as f64)
+        })
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    Some(covariance / variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::testing::{self, Cloud};
+
+    fn volume(size: i32) -> NetworkVolume {
+        testing::volume(serde_json::json!({ "size": size }))
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_plans_growth_and_cost() {
+        let mut planner = CapacityPlanner::new();
+        planner.add_sample("volume", day(3), 70 * BYTES_PER_GB);
+        planner.add_sample("volume", day(1), 60 * BYTES_PER_GB);
+        planner.add_sample("volume", day(2), 65 * BYTES_PER_GB);
+
+        let record: BillingRecord = serde_json::from_value(serde_json::json!({
+            "amount": 0.2,
+            "diskSpaceBilledGb": 100,
+            "time": "2025-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        assert!(planner.add_billing(&[record]));
+
+        let plan = planner.plan(&volume(100));
+        assert_eq!(plan.growth_per_day, Some(5.0 * BYTES_PER_GB as f64));
+        assert_eq!(plan.utilization(), Some(0.7));
+        assert_eq!(plan.full_at, Some(day(9)));
+        // 70 GB + 7 days of 5 GB at 80% utilization needs 131.25 GB.
+        assert_eq!(plan.recommended_size_gb, Some(140));
+        assert!((plan.price_per_gb_month - 0.2 / 2400.0 * HOURS_PER_MONTH).abs() < 1e-12);
+        assert!((plan.monthly_cost_delta() - 40.0 * plan.price_per_gb_month).abs() < 1e-9);
+        assert_eq!(plan.update_input().unwrap().size, Some(140));
+
+        let plan = planner.plan(&volume(200));
+        assert!(!plan.needs_resize());
+        assert_eq!(plan.monthly_cost_delta(), 0.0);
+        assert_eq!(CapacityPlanner::new().plan(&volume(100)).used_bytes, None);
+    }
+
+    #[tokio::test]
+    async fn test_resize_only_grows() {
+        let cloud = Cloud::new().with_volume(volume(100));
+        let error = resize_volume(&cloud, &volume(100), 50).await.unwrap_err();
+        assert!(matches!(error, Error::Validation(_)));
+        assert!(resize_volume(&cloud, &volume(100), 5000).await.is_err());
+        let unchanged = resize_volume(&cloud, &volume(100), 100).await.unwrap();
+        assert_eq!(unchanged.size, 100);
+        assert!(cloud.calls().is_empty());
+
+        let grown = resize_volume(&cloud, &volume(100), 110).await.unwrap();
+        assert_eq!(grown.size, 110);
+        assert_eq!(cloud.calls(), ["update_volume volume"]);
+
+        let mut planner = CapacityPlanner::new();
+        planner.add_sample("volume", day(1), 95 * BYTES_PER_GB);
+        let grown = planner.grow(&cloud, &volume(110)).await.unwrap();
+        assert_eq!(grown.map(|volume| volume.size), Some(120));
+    }
+
+    #[test]
+    fn test_directory_size() {
+        let directory =
+            std::env::temp_dir().join(format!("runpod-capacity-{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("models")).unwrap();
+        std::fs::write(directory.join("readme.md"), b"hello").unwrap();
+        std::fs::write(directory.join("models").join("weights.bin"), [0; 10]).unwrap();
+
+        assert_eq!(directory_size(&directory).unwrap(), 15);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -74,6 +74,12 @@ pub struct RunpodConfig {
     #[builder(default = "Self::default_graphql_url()")]
     graphql_url: String,
 
+    /// Base URL for the serverless job API.
+    ///
+    /// Defaults to the official Runpod serverless endpoint API.
+    #[builder(default = "Self::default_serverless_url()")]
+    serverless_url: String,
+
     /// Timeout for HTTP requests.
     ///
     /// Controls how long the client will wait for API responses before timing out.
@@ -94,6 +100,11 @@ impl RunpodBuilder {
         "https://api.runpod.io/graphql".to_string()
     }
 
+    /// Returns the default base URL for the serverless job API.
+    fn default_serverless_url() -> String {
+        "https://api.runpod.ai/v2".to_string()
+    }
+
     /// Returns the default timeout.
     fn default_timeout() -> Duration {
         Duration::from_secs(30)
@@ -143,7 +154,8 @@ impl RunpodConfig {
     /// Creates a configuration from environment variables.
     ///
     /// Reads the API key from the `RUNPOD_API_KEY` environment variable.
-    /// Optionally reads `RUNPOD_BASE_URL`, `RUNPOD_GRAPHQL_URL` (with graphql feature), and `RUNPOD_TIMEOUT_SECS` if set.
+    /// Optionally reads `RUNPOD_BASE_URL`, `RUNPOD_GRAPHQL_URL` (with graphql feature),
+    /// `RUNPOD_SERVERLESS_URL`, and `RUNPOD_TIMEOUT_SECS` if set.
     ///
     /// # Errors
     ///
@@ -179,6 +191,11 @@ impl RunpodConfig {
             builder = builder.with_graphql_url(graphql_url);
         }
 
+        // Optional: custom serverless URL
+        if let Ok(serverless_url) = std::env::var("RUNPOD_SERVERLESS_URL") {
+            builder = builder.with_serverless_url(serverless_url);
+        }
+
         // Optional: custom timeout
         if let Ok(timeout_str) = std::env::var("RUNPOD_TIMEOUT_SECS") {
             let timeout_secs = timeout_str.parse::<u64>().map_err(|_| {
@@ -265,6 +282,11 @@ impl RunpodConfig {
         &self.graphql_url
     }
 
+    /// Returns the base URL for the serverless job API.
+    pub fn serverless_url(&self) -> &str {
+        &self.serverless_url
+    }
+
     /// Returns the timeout duration.
     pub fn timeout(&self) -> Duration {
         self.timeout
@@ -322,6 +344,7 @@ impl fmt::Debug for RunpodConfig {
         debug_struct
             .field("api_key", &self.masked_api_key())
             .field("base_url", &self.base_url)
+            .field("serverless_url", &self.serverless_url)
             .field("timeout", &self.timeout);
 
         #[cfg(feature = "graphql")]
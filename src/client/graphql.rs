@@ -0,0 +1,35 @@
+//! GraphQL response handling.
+
+use serde::Deserialize;
+
+use crate::{Error, Result};
+
+/// Envelope of a GraphQL response.
+#[derive(Debug, Deserialize)]
+pub(crate) struct GraphqlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+/// A single error reported by the GraphQL API.
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+impl<T> GraphqlResponse<T> {
+    /// Returns the response data, or an error if the API reported any.
+    pub(crate) fn into_result(self) -> Result<T> {
+        match self.data {
+            Some(data) if self.errors.is_empty() => Ok(data),
+            _ if self.errors.is_empty() => {
+                Err(Error::Graphql("response contains no data".to_string()))
+            }
+            _ => {
+                let messages: Vec<_> = self.errors.into_iter().map(|e| e.message).collect();
+                Err(Error::Graphql(messages.join("; ")))
+            }
+        }
+    }
+}
@@ -7,6 +7,8 @@
 //! - [`RunpodClient`] - Main client for making API requests
 
 mod config;
+#[cfg(feature = "graphql")]
+mod graphql;
 mod runpod;
 pub mod version;
 
@@ -35,6 +35,8 @@ use crate::Result;
 /// - [`VolumesService`](crate::service::v1::VolumesService) - Network volume operations
 /// - [`RegistryService`](crate::service::v1::RegistryService) - Registry authentication
 /// - [`BillingService`](crate::service::v1::BillingService) - Usage and billing information
+/// - [`ServerlessService`](crate::service::v1::ServerlessService) - Serverless job submission and status
+/// - `RuntimeService` - Pod runtime telemetry (requires `graphql` feature)
 ///
 /// # Examples
 ///
@@ -148,6 +150,7 @@ impl<V: ApiVersion> RunpodClient<V> {
     /// - `RUNPOD_API_KEY` - Your RunPod API key (required)
     /// - `RUNPOD_BASE_URL` - Base URL for the API (optional, defaults to <https://rest.runpod.io/v1>)
     /// - `RUNPOD_GRAPHQL_URL` - GraphQL API URL (optional, defaults to <https://api.runpod.io/graphql>, requires `graphql` feature)
+    /// - `RUNPOD_SERVERLESS_URL` - Serverless job API URL (optional, defaults to <https://api.runpod.ai/v2>)
     /// - `RUNPOD_TIMEOUT_SECS` - Request timeout in seconds (optional, defaults to 30)
     ///
     /// # Example
@@ -252,6 +255,50 @@ impl<V: ApiVersion> RunpodClient<V> {
             .timeout(self.inner.config.timeout())
     }
 
+    /// Creates a GET request against the serverless job API.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), fields(method = "GET", path, url))
+    )]
+    pub(crate) fn serverless_get(&self, path: &str) -> RequestBuilder {
+        let url = format!("{}{}", self.inner.config.serverless_url(), path);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            url = %url,
+            method = "GET",
+            "Creating HTTP request"
+        );
+
+        self.inner
+            .client
+            .get(&url)
+            .bearer_auth(self.inner.config.api_key())
+            .timeout(self.inner.config.timeout())
+    }
+
+    /// Creates a POST request against the serverless job API.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), fields(method = "POST", path, url))
+    )]
+    pub(crate) fn serverless_post(&self, path: &str) -> RequestBuilder {
+        let url = format!("{}{}", self.inner.config.serverless_url(), path);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            url = %url,
+            method = "POST",
+            "Creating HTTP request"
+        );
+
+        self.inner
+            .client
+            .post(&url)
+            .bearer_auth(self.inner.config.api_key())
+            .timeout(self.inner.config.timeout())
+    }
+
     /// Executes a GraphQL query.
     ///
     /// # Arguments
@@ -315,6 +362,16 @@ impl<V: ApiVersion> RunpodClient<V> {
         let result = response.json().await?;
         Ok(result)
     }
+
+    /// Executes a GraphQL query and returns its `data`, failing on GraphQL errors.
+    #[cfg(feature = "graphql")]
+    pub(crate) async fn graphql_data<T>(&self, query: &str) -> Result<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let response: super::graphql::GraphqlResponse<T> = self.graphql_query(query).await?;
+        response.into_result()
+    }
 }
 
 impl<V: ApiVersion> fmt::Debug for RunpodClient<V> {
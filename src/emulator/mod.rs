@@ -0,0 +1,343 @@
+//! Local serverless emulator.
+//!
+//! An [`Emulator`] is a local HTTP server exposing the serverless job API
+//! (`/run`, `/runsync`, `/status`, `/stream`, `/cancel` and `/health`)
+//! backed by an in-memory queue, together with the worker protocol (job take,
+//! result and stream posting, heartbeats) that dispatches queued jobs to
+//! workers. Jobs are processed by a handler registered in-process, or by a
+//! separate worker process configured with [`Emulator::worker_config`].
+//!
+//! A [`RunpodClient`] from [`Emulator::client`] talks to the emulator through
+//! [`ServerlessService`](crate::service::v1::ServerlessService), which makes
+//! it possible to exercise the full job lifecycle in integration tests. The
+//! emulator serves a single endpoint: the endpoint ID in request paths is
+//! ignored, and webhooks are not called.
+//!
+//! A worker started with [`worker::start`](crate::worker::start) and the
+//! `--rp_serve_api` argument runs behind an emulator instead of connecting
+//! to RunPod, like `--rp_serve_api` of the Python SDK. The address defaults
+//! to `127.0.0.1:8000` and can be changed with `--rp_api_host` and
+//! `--rp_api_port`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::Result;
+//! use runpod_sdk::emulator::Emulator;
+//! use runpod_sdk::model::v1::RunJobInput;
+//! use runpod_sdk::service::v1::ServerlessService;
+//! use runpod_sdk::worker::Job;
+//! use serde_json::{Value, json};
+//!
+//! async fn echo(job: Job) -> Result<Value> {
+//!     Ok(job.input)
+//! }
+//!
+//! # async fn example() -> Result<()> {
+//! let emulator = Emulator::start().await?.with_handler(echo);
+//! let client = emulator.client()?;
+//!
+//! let job = client
+//!     .run_job_sync("local", RunJobInput::new(json!({"prompt": "Hello"})))
+//!     .await?;
+//! assert_eq!(job.output, Some(json!({"prompt": "Hello"})));
+//!
+//! emulator.shutdown().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod routes;
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::worker::{
+    ConcurrencyModifier, Handler, Process, StreamHandler, Worker, WorkerBuilder, WorkerConfig,
+};
+use crate::{Result, RunpodClient, RunpodConfig};
+
+/// Maximum time `/runsync` waits for a job to finish.
+const RUNSYNC_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Worker ID of workers registered in-process.
+const LOCAL_WORKER_ID: &str = "local";
+
+/// Local HTTP server emulating a serverless endpoint.
+///
+/// The server and any in-process workers run as background tasks until
+/// [`shutdown`](Self::shutdown) is called or the emulator is dropped.
+pub struct Emulator {
+    address: SocketAddr,
+    shutdown: watch::Sender<bool>,
+    server: Option<JoinHandle<()>>,
+    workers: Vec<JoinHandle<Result<()>>>,
+}
+
+impl Emulator {
+    /// Starts an emulator on a free local port.
+    pub async fn start() -> Result<Self> {
+        Self::bind("127.0.0.1:0").await
+    }
+
+    /// Starts an emulator listening on `address`.
+    pub async fn bind(address: impl ToSocketAddrs) -> Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let router = routes::router(Arc::new(routes::Jobs::new(RUNSYNC_TIMEOUT)));
+
+        let (shutdown, mut signal) = watch::channel(false);
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move {
+                    let _ = signal.wait_for(|stop| *stop).await;
+                })
+                .await;
+        });
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(address = %address, "Started serverless emulator");
+
+        Ok(Self {
+            address,
+            shutdown,
+            server: Some(server),
+            workers: Vec::new(),
+        })
+    }
+
+    /// Returns the address the emulator listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns the base URL of the emulator.
+    ///
+    /// Use it as the [`serverless_url`](crate::RunpodBuilder::with_serverless_url)
+    /// of a client.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Returns a client whose serverless job API is served by the emulator.
+    pub fn client(&self) -> Result<RunpodClient> {
+        RunpodConfig::builder()
+            .with_api_key("local")
+            .with_serverless_url(self.url())
+            .build_v1()
+    }
+
+    /// Returns a worker configuration taking jobs from the emulator.
+    ///
+    /// The same URLs can be passed to a worker process through the
+    /// `RUNPOD_WEBHOOK_*` environment variables.
+    pub fn worker_config(&self, worker_id: &str) -> WorkerBuilder {
+        let url = self.url();
+        WorkerConfig::builder()
+            .with_api_key("local")
+            .with_worker_id(worker_id)
+            .with_job_take_url(format!("{url}/job-take/$ID"))
+            .with_job_done_url(format!("{url}/job-done/$RUNPOD_POD_ID/$ID"))
+            .with_stream_url(format!("{url}/job-stream/$RUNPOD_POD_ID/$ID"))
+            .with_ping_url(format!("{url}/ping/$RUNPOD_POD_ID"))
+            .with_poll_interval(Duration::from_millis(10))
+    }
+
+    /// Processes jobs with `handler` in-process.
+    pub fn with_handler<I, H>(self, handler: H) -> Self
+    where
+        I: DeserializeOwned + Send + 'static,
+        H: Handler<I>,
+    {
+        let config = self.local_worker_config();
+        self.with_worker(Worker::new(config, handler))
+    }
+
+    /// Processes jobs with the streaming `handler` in-process.
+    pub fn with_stream_handler<I, H>(self, handler: H) -> Self
+    where
+        I: DeserializeOwned + Send + 'static,
+        H: StreamHandler<I>,
+    {
+        let config = self.local_worker_config();
+        self.with_worker(Worker::streaming(config, handler))
+    }
+
+    /// Runs `worker` in-process until the emulator shuts down.
+    ///
+    /// The worker should be configured with [`worker_config`](Self::worker_config).
+    pub fn with_worker<I, P, M>(mut self, worker: Worker<I, P, M>) -> Self
+    where
+        I: DeserializeOwned + Send + 'static,
+        P: Process<I>,
+        M: ConcurrencyModifier + Send + 'static,
+    {
+        let mut signal = self.shutdown.subscribe();
+        self.workers.push(tokio::spawn(worker.run_until(async move {
+            let _ = signal.wait_for(|stop| *stop).await;
+        })));
+        self
+    }
+
+    /// Runs until `SIGINT` or `SIGTERM` is received, then shuts down.
+    pub async fn run(self) -> Result<()> {
+        crate::worker::shutdown_signal().await;
+        self.shutdown().await
+    }
+
+    /// Stops the emulator after in-process workers finish their jobs.
+    pub async fn shutdown(mut self) -> Result<()> {
+        let _ = self.shutdown.send(true);
+
+        let mut result = Ok(());
+        for worker in std::mem::take(&mut self.workers) {
+            if let Ok(Err(e)) = worker.await {
+                result = result.and(Err(e));
+            }
+        }
+        if let Some(server) = self.server.take() {
+            let _ = server.await;
+        }
+
+        result
+    }
+
+    fn local_worker_config(&self) -> WorkerConfig {
+        self.worker_config(LOCAL_WORKER_ID)
+            .build()
+            .expect("emulator worker configuration is valid")
+    }
+}
+
+impl Drop for Emulator {
+    fn drop(&mut self) {
+        for worker in &self.workers {
+            worker.abort();
+        }
+        if let Some(server) = &self.server {
+            server.abort();
+        }
+    }
+}
+
+impl fmt::Debug for Emulator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Emulator")
+            .field("address", &self.address)
+            .field("workers", &self.workers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the address to serve the emulator on if `--rp_serve_api` was passed.
+pub(crate) fn serve_api_address() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|arg| arg == "--rp_serve_api") {
+        return None;
+    }
+
+    let value = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    let host = value("--rp_api_host").unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = value("--rp_api_port").unwrap_or_else(|| "8000".to_string());
+    Some(format!("{host}:{port}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::model::v1::{JobState, RunJobInput};
+    use crate::service::v1::ServerlessService;
+    use crate::worker::Job;
+
+    async fn echo(job: Job) -> Result<Value, String> {
+        match job.input.get("fail") {
+            Some(_) => Err("requested failure".to_string()),
+            None => Ok(job.input),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_jobs_through_handler() {
+        let emulator = Emulator::start().await.unwrap().with_handler(echo);
+        let client = emulator.client().unwrap();
+
+        let job = client
+            .run_job_sync("local", RunJobInput::new(json!({"value": 1})))
+            .await
+            .unwrap();
+        assert_eq!(job.status, JobState::Completed);
+        assert_eq!(job.output, Some(json!({"value": 1})));
+        assert_eq!(job.worker_id.as_deref(), Some(LOCAL_WORKER_ID));
+
+        let queued = client
+            .run_job("local", RunJobInput::new(json!({"fail": true})))
+            .await
+            .unwrap();
+        assert_eq!(queued.status, JobState::InQueue);
+
+        let failed = loop {
+            let job = client.get_job_status("local", &queued.id).await.unwrap();
+            if job.status.is_final() {
+                break job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(failed.status, JobState::Failed);
+        assert_eq!(failed.error.as_deref(), Some("requested failure"));
+
+        let health = client.get_endpoint_health("local").await.unwrap();
+        assert_eq!(health.jobs.completed, 1);
+        assert_eq!(health.jobs.failed, 1);
+        assert_eq!(health.jobs.pending(), 0);
+        assert_eq!(health.workers.active(), 1);
+
+        emulator.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_streams_and_cancels_jobs() {
+        let emulator = Emulator::start().await.unwrap();
+        let client = emulator.client().unwrap();
+
+        let cancelled = client
+            .run_job("local", RunJobInput::new(json!({"count": 1})))
+            .await
+            .unwrap();
+        let job = client.cancel_job("local", &cancelled.id).await.unwrap();
+        assert_eq!(job.status, JobState::Cancelled);
+
+        let emulator = emulator.with_stream_handler(|job: Job| {
+            let count = job.input["count"].as_u64().unwrap_or_default();
+            futures::stream::iter(0..count).map(Ok::<_, String>)
+        });
+        let job = client
+            .run_job_sync("local", RunJobInput::new(json!({"count": 3})))
+            .await
+            .unwrap();
+        assert_eq!(job.status, JobState::Completed);
+
+        let stream = client.stream_job("local", &job.id).await.unwrap();
+        let outputs: Vec<Value> = stream.stream.into_iter().map(|s| s.output).collect();
+        assert_eq!(outputs, vec![json!(0), json!(1), json!(2)]);
+
+        let cancelled = client.get_job_status("local", &cancelled.id).await.unwrap();
+        assert_eq!(cancelled.status, JobState::Cancelled);
+
+        emulator.shutdown().await.unwrap();
+    }
+}
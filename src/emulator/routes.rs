@@ -0,0 +1,354 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::Json;
+use axum::Router;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::sync::Notify;
+
+use crate::model::v1::{
+    EndpointHealth, JobCounts, JobState, JobStatus, JobStream, RunJobInput, StreamOutput,
+    WorkerCounts,
+};
+
+/// In-memory job queue shared by the serverless API and worker routes.
+pub(crate) struct Jobs {
+    queue: Mutex<Queue>,
+    changed: Notify,
+    next_id: AtomicU64,
+    sync_timeout: Duration,
+}
+
+#[derive(Default)]
+struct Queue {
+    pending: VecDeque<String>,
+    jobs: HashMap<String, JobRecord>,
+    workers: HashSet<String>,
+}
+
+struct JobRecord {
+    input: Value,
+    state: JobState,
+    output: Option<Value>,
+    error: Option<String>,
+    stream: Vec<Value>,
+    worker_id: Option<String>,
+    queued_at: Instant,
+    started_at: Option<Instant>,
+    finished_at: Option<Instant>,
+}
+
+impl JobRecord {
+    fn status(&self, id: &str) -> JobStatus {
+        let millis = |from: Instant, to: Option<Instant>| {
+            to.map(|to| to.duration_since(from).as_millis() as u64)
+        };
+
+        JobStatus {
+            id: id.to_string(),
+            status: self.state,
+            output: self.output.clone(),
+            error: self.error.clone(),
+            delay_time: millis(self.queued_at, self.started_at),
+            execution_time: self
+                .started_at
+                .and_then(|started_at| millis(started_at, self.finished_at)),
+            worker_id: self.worker_id.clone(),
+        }
+    }
+}
+
+/// Result posted by a worker.
+#[derive(Deserialize)]
+struct JobResult {
+    #[serde(default)]
+    output: Option<Value>,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+impl Jobs {
+    pub(crate) fn new(sync_timeout: Duration) -> Self {
+        Self {
+            queue: Mutex::new(Queue::default()),
+            changed: Notify::new(),
+            next_id: AtomicU64::new(1),
+            sync_timeout,
+        }
+    }
+
+    /// Runs `f` on the queue and wakes up everyone waiting for job updates.
+    fn update<T>(&self, f: impl FnOnce(&mut Queue) -> T) -> T {
+        let result = f(&mut self.queue.lock().unwrap_or_else(|e| e.into_inner()));
+        self.changed.notify_waiters();
+        result
+    }
+
+    fn status(&self, job_id: &str) -> Option<JobStatus> {
+        let queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.jobs.get(job_id).map(|job| job.status(job_id))
+    }
+
+    fn enqueue(&self, input: RunJobInput) -> JobStatus {
+        let id = format!("job-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let job = JobRecord {
+            input: input.input,
+            state: JobState::InQueue,
+            output: None,
+            error: None,
+            stream: Vec::new(),
+            worker_id: None,
+            queued_at: Instant::now(),
+            started_at: None,
+            finished_at: None,
+        };
+
+        self.update(|queue| {
+            let status = job.status(&id);
+            queue.pending.push_back(id.clone());
+            queue.jobs.insert(id, job);
+            status
+        })
+    }
+
+    /// Waits until the job is final or the sync timeout elapses.
+    async fn wait(&self, job_id: &str) -> Option<JobStatus> {
+        let deadline = tokio::time::Instant::now() + self.sync_timeout;
+        loop {
+            let changed = self.changed.notified();
+            let status = self.status(job_id)?;
+            if status.status.is_final() {
+                return Some(status);
+            }
+            if tokio::time::timeout_at(deadline, changed).await.is_err() {
+                return Some(status);
+            }
+        }
+    }
+
+    /// Counts jobs by state and workers that took jobs or sent heartbeats.
+    fn health(&self) -> EndpointHealth {
+        let queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut jobs = JobCounts::default();
+        let mut busy = HashSet::new();
+        for job in queue.jobs.values() {
+            match job.state {
+                JobState::InQueue => jobs.in_queue += 1,
+                JobState::InProgress => {
+                    jobs.in_progress += 1;
+                    busy.extend(job.worker_id.as_deref());
+                }
+                JobState::Completed => jobs.completed += 1,
+                JobState::Failed | JobState::TimedOut => jobs.failed += 1,
+                JobState::Cancelled => {}
+            }
+        }
+
+        let running = queue.workers.iter().filter(|id| busy.contains(id.as_str()));
+        let running = running.count() as u64;
+        let workers = WorkerCounts {
+            idle: queue.workers.len() as u64 - running,
+            running,
+            ready: queue.workers.len() as u64 - running,
+            ..Default::default()
+        };
+
+        EndpointHealth {
+            jobs,
+            workers,
+            worker_pods: Vec::new(),
+        }
+    }
+
+    /// Records a worker as connected.
+    fn register(&self, worker_id: &str) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        if !queue.workers.contains(worker_id) {
+            queue.workers.insert(worker_id.to_string());
+        }
+    }
+
+    /// Marks the next queued job as taken by `worker_id`.
+    fn take(&self, worker_id: &str) -> Option<Value> {
+        self.register(worker_id);
+        self.update(|queue| {
+            while let Some(id) = queue.pending.pop_front() {
+                let Some(job) = queue.jobs.get_mut(&id) else {
+                    continue;
+                };
+                if job.state != JobState::InQueue {
+                    continue;
+                }
+
+                job.state = JobState::InProgress;
+                job.started_at = Some(Instant::now());
+                job.worker_id = Some(worker_id.to_string());
+                return Some(json!({ "id": id, "input": job.input }));
+            }
+            None
+        })
+    }
+
+    fn finish(&self, job_id: &str, result: JobResult) -> bool {
+        self.update(|queue| {
+            let Some(job) = queue.jobs.get_mut(job_id) else {
+                return false;
+            };
+            if job.state.is_final() {
+                return true;
+            }
+
+            match result.error {
+                Some(error) => {
+                    job.state = JobState::Failed;
+                    job.error = Some(match error {
+                        Value::String(error) => error,
+                        error => error.to_string(),
+                    });
+                }
+                None => {
+                    job.state = JobState::Completed;
+                    job.output = result.output;
+                }
+            }
+            job.finished_at = Some(Instant::now());
+            true
+        })
+    }
+
+    fn push_stream(&self, job_id: &str, output: Value) -> bool {
+        self.update(|queue| match queue.jobs.get_mut(job_id) {
+            Some(job) => {
+                job.stream.push(output);
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn drain_stream(&self, job_id: &str) -> Option<JobStream> {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        let job = queue.jobs.get_mut(job_id)?;
+        Some(JobStream {
+            status: job.state,
+            stream: job
+                .stream
+                .drain(..)
+                .map(|output| StreamOutput { output })
+                .collect(),
+        })
+    }
+
+    fn cancel(&self, job_id: &str) -> Option<JobStatus> {
+        self.update(|queue| {
+            let job = queue.jobs.get_mut(job_id)?;
+            if !job.state.is_final() {
+                job.state = JobState::Cancelled;
+                job.finished_at = Some(Instant::now());
+            }
+            Some(job.status(job_id))
+        })
+    }
+}
+
+/// Builds the router serving the serverless job API and the worker protocol.
+pub(crate) fn router(jobs: Arc<Jobs>) -> Router {
+    Router::new()
+        .route("/{endpoint_id}/run", post(run))
+        .route("/{endpoint_id}/runsync", post(run_sync))
+        .route("/{endpoint_id}/status/{job_id}", get(status))
+        .route("/{endpoint_id}/stream/{job_id}", get(stream))
+        .route("/{endpoint_id}/cancel/{job_id}", post(cancel))
+        .route("/{endpoint_id}/health", get(health))
+        .route("/job-take/{worker_id}", get(take))
+        .route("/job-done/{worker_id}/{job_id}", post(done))
+        .route("/job-stream/{worker_id}/{job_id}", post(stream_output))
+        .route("/ping/{worker_id}", get(ping))
+        .with_state(jobs)
+}
+
+type JobsState = State<Arc<Jobs>>;
+
+fn not_found(job_id: &str) -> Response {
+    let error = json!({ "error": format!("job {job_id} not found") });
+    (StatusCode::NOT_FOUND, Json(error)).into_response()
+}
+
+async fn run(State(jobs): JobsState, Json(input): Json<RunJobInput>) -> Json<JobStatus> {
+    Json(jobs.enqueue(input))
+}
+
+async fn run_sync(State(jobs): JobsState, Json(input): Json<RunJobInput>) -> Response {
+    let queued = jobs.enqueue(input);
+    match jobs.wait(&queued.id).await {
+        Some(status) => Json(status).into_response(),
+        None => not_found(&queued.id),
+    }
+}
+
+async fn status(State(jobs): JobsState, Path((_, job_id)): Path<(String, String)>) -> Response {
+    match jobs.status(&job_id) {
+        Some(status) => Json(status).into_response(),
+        None => not_found(&job_id),
+    }
+}
+
+async fn stream(State(jobs): JobsState, Path((_, job_id)): Path<(String, String)>) -> Response {
+    match jobs.drain_stream(&job_id) {
+        Some(stream) => Json(stream).into_response(),
+        None => not_found(&job_id),
+    }
+}
+
+async fn cancel(State(jobs): JobsState, Path((_, job_id)): Path<(String, String)>) -> Response {
+    match jobs.cancel(&job_id) {
+        Some(status) => Json(status).into_response(),
+        None => not_found(&job_id),
+    }
+}
+
+async fn health(State(jobs): JobsState, Path(_): Path<String>) -> Json<EndpointHealth> {
+    Json(jobs.health())
+}
+
+async fn take(State(jobs): JobsState, Path(worker_id): Path<String>) -> Response {
+    match jobs.take(&worker_id) {
+        Some(job) => Json(job).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn done(
+    State(jobs): JobsState,
+    Path((_, job_id)): Path<(String, String)>,
+    Json(result): Json<JobResult>,
+) -> Response {
+    match jobs.finish(&job_id, result) {
+        true => StatusCode::OK.into_response(),
+        false => not_found(&job_id),
+    }
+}
+
+async fn stream_output(
+    State(jobs): JobsState,
+    Path((_, job_id)): Path<(String, String)>,
+    Json(result): Json<JobResult>,
+) -> Response {
+    match jobs.push_stream(&job_id, result.output.unwrap_or_default()) {
+        true => StatusCode::OK.into_response(),
+        false => not_found(&job_id),
+    }
+}
+
+async fn ping(State(jobs): JobsState, Path(worker_id): Path<String>) -> StatusCode {
+    jobs.register(&worker_id);
+    StatusCode::OK
+}
@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::AccountSnapshot;
+use crate::Result;
+
+/// A single field whose value differs between two versions of a resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// Dotted path of the field using API field names, e.g. `gpu.displayName`.
+    /// Elements of lists of identified objects are addressed by ID, e.g.
+    /// `workers[pod_123].desiredStatus`.
+    pub path: String,
+
+    /// Previous value, `null` if the field was absent.
+    pub before: Value,
+
+    /// New value, `null` if the field was removed.
+    pub after: Value,
+}
+
+/// Reference to a resource that was added or removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceRef {
+    /// Resource ID.
+    pub id: String,
+
+    /// Resource name, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A resource present in both snapshots with at least one changed field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceChange {
+    /// Resource ID.
+    pub id: String,
+
+    /// Resource name in the newer snapshot, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Changed fields, ordered by path.
+    pub fields: Vec<FieldChange>,
+}
+
+/// Differences for one kind of resource.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDiff {
+    /// Resources only present in the newer snapshot.
+    pub added: Vec<ResourceRef>,
+
+    /// Resources only present in the older snapshot.
+    pub removed: Vec<ResourceRef>,
+
+    /// Resources present in both snapshots whose fields differ.
+    pub changed: Vec<ResourceChange>,
+}
+
+impl ResourceDiff {
+    /// Returns `true` if no resource was added, removed or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Differences between two [`AccountSnapshot`]s, per resource kind.
+///
+/// Resources are matched by ID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    /// Pod differences.
+    pub pods: ResourceDiff,
+
+    /// Serverless endpoint differences.
+    pub endpoints: ResourceDiff,
+
+    /// Template differences.
+    pub templates: ResourceDiff,
+
+    /// Network volume differences.
+    pub network_volumes: ResourceDiff,
+
+    /// Container registry auth differences.
+    pub registry_auths: ResourceDiff,
+}
+
+impl SnapshotDiff {
+    /// Computes the differences from `before` to `after`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a resource cannot be converted to JSON.
+    pub fn compute(before: &AccountSnapshot, after: &AccountSnapshot) -> Result<Self> {
+        Ok(Self {
+            pods: diff_resources(&before.pods, &after.pods)?,
+            endpoints: diff_resources(&before.endpoints, &after.endpoints)?,
+            templates: diff_resources(&before.templates, &after.templates)?,
+            network_volumes: diff_resources(&before.network_volumes, &after.network_volumes)?,
+            registry_auths: diff_resources(&before.registry_auths, &after.registry_auths)?,
+        })
+    }
+
+    /// Returns `true` if both snapshots describe the same resources.
+    pub fn is_empty(&self) -> bool {
+        self.pods.is_empty()
+            && self.endpoints.is_empty()
+            && self.templates.is_empty()
+            && self.network_volumes.is_empty()
+            && self.registry_auths.is_empty()
+    }
+}
+
+fn diff_resources<T: Serialize>(before: &[T], after: &[T]) -> Result<ResourceDiff> {
+    let before = index_by_id(before)?;
+    let after = index_by_id(after)?;
+    let mut diff = ResourceDiff::default();
+
+    for (id, old) in &before {
+        match after.get(id) {
+            None => diff.removed.push(resource_ref(id, old)),
+            Some(new) => {
+                let fields = diff_json(old, new);
+                if !fields.is_empty() {
+                    diff.changed.push(ResourceChange {
+                        id: id.clone(),
+                        name: name_of(new),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+
+    for (id, new) in &after {
+        if !before.contains_key(id) {
+            diff.added.push(resource_ref(id, new));
+        }
+    }
+
+    Ok(diff)
+}
+
+fn index_by_id<T: Serialize>(resources: &[T]) -> Result<BTreeMap<String, Value>> {
+    resources
+        .iter()
+        .map(|resource| {
+            let value = serde_json::to_value(resource)?;
+            let id = value["id"].as_str().unwrap_or_default().to_string();
+            Ok((id, value))
+        })
+        .collect()
+}
+
+fn resource_ref(id: &str, value: &Value) -> ResourceRef {
+    ResourceRef {
+        id: id.to_string(),
+        name: name_of(value),
+    }
+}
+
+fn name_of(value: &Value) -> Option<String> {
+    value["name"].as_str().map(str::to_string)
+}
+
+/// Compares two JSON documents field by field.
+///
+/// Objects are compared recursively. Lists whose elements are all objects
+/// carrying a string `id` are matched by ID; other lists are compared as a
+/// whole. Changes are returned ordered by path.
+pub(crate) fn diff_json(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    diff_value(String::new(), before, after, &mut changes);
+    changes
+}
+
+fn diff_value(path: String, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    if before == after {
+        return;
+    }
+
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                diff_value(
+                    join(&path, key),
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => match (by_id(old), by_id(new)) {
+            (Some(old), Some(new)) => {
+                let ids: BTreeSet<&str> = old.keys().chain(new.keys()).copied().collect();
+                for id in ids {
+                    diff_value(
+                        format!("{path}[{id}]"),
+                        old.get(id).copied().unwrap_or(&Value::Null),
+                        new.get(id).copied().unwrap_or(&Value::Null),
+                        changes,
+                    );
+                }
+            }
+            _ => changes.push(change(path, before, after)),
+        },
+        _ => changes.push(change(path, before, after)),
+    }
+}
+
+fn by_id(items: &[Value]) -> Option<BTreeMap<&str, &Value>> {
+    items
+        .iter()
+        .map(|item| item.get("id").and_then(Value::as_str).map(|id| (id, item)))
+        .collect()
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn change(path: String, before: &Value, after: &Value) -> FieldChange {
+    FieldChange {
+        path,
+        before: before.clone(),
+        after: after.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_json_reports_nested_fields() {
+        let before = json!({ "id": "p1", "gpu": { "count": 1 }, "ports": ["22/tcp"] });
+        let after = json!({ "id": "p1", "gpu": { "count": 2 }, "ports": ["22/tcp", "8888/http"] });
+
+        let paths: Vec<_> = diff_json(&before, &after)
+            .into_iter()
+            .map(|c| c.path)
+            .collect();
+        assert_eq!(paths, ["gpu.count", "ports"]);
+    }
+
+    #[test]
+    fn test_diff_json_matches_list_elements_by_id() {
+        let before = json!({ "workers": [{ "id": "w1", "status": "RUNNING" }, { "id": "w2" }] });
+        let after = json!({ "workers": [{ "id": "w3" }, { "id": "w1", "status": "EXITED" }] });
+
+        let changes = diff_json(&before, &after);
+        let paths: Vec<_> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["workers[w1].status", "workers[w2]", "workers[w3]"]);
+        assert_eq!(changes[1].after, Value::Null);
+    }
+
+    #[test]
+    fn test_diff_resources_by_id() -> Result<()> {
+        let before = vec![
+            json!({ "id": "a", "name": "kept", "size": 10 }),
+            json!({ "id": "b", "name": "gone" }),
+        ];
+        let after = vec![
+            json!({ "id": "a", "name": "kept", "size": 20 }),
+            json!({ "id": "c", "name": "new" }),
+        ];
+
+        let diff = diff_resources(&before, &after)?;
+        assert_eq!(diff.added[0].name.as_deref(), Some("new"));
+        assert_eq!(diff.removed[0].id, "b");
+        assert_eq!(diff.changed[0].fields[0].path, "size");
+        assert_eq!(diff.changed[0].fields[0].after, json!(20));
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+//! Point-in-time inventory of every resource in an account.
+//!
+//! [`AccountSnapshot::capture`] collects pods, serverless endpoints, templates,
+//! network volumes and container registry auths concurrently into a single
+//! serializable snapshot. Two snapshots can be compared with
+//! [`AccountSnapshot::diff`] to audit what was added, removed or changed.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::inventory::AccountSnapshot;
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example(previous: AccountSnapshot) -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//!
+//! let snapshot = AccountSnapshot::capture(&client).await?;
+//! println!("{}", serde_json::to_string_pretty(&snapshot)?);
+//!
+//! let diff = previous.diff(&snapshot)?;
+//! for change in &diff.pods.changed {
+//!     for field in &change.fields {
+//!         println!("pod {}: {} {} -> {}", change.id, field.path, field.before, field.after);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod diff;
+
+use chrono::{DateTime, Utc};
+pub(crate) use diff::diff_json;
+pub use diff::{FieldChange, ResourceChange, ResourceDiff, ResourceRef, SnapshotDiff};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+use crate::model::v1::{
+    ContainerRegistryAuths, Endpoints, ListEndpointsQuery, ListPodsQuery, ListTemplatesQuery,
+    NetworkVolumes, Pods, Templates,
+};
+use crate::service::v1::{
+    EndpointsService, PodsService, RegistryService, TemplatesService, VolumesService,
+};
+
+/// Every resource of an account at a point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshot {
+    /// When the snapshot was captured.
+    pub captured_at: DateTime<Utc>,
+
+    /// Pods, including machine, network volume and savings plan details.
+    pub pods: Pods,
+
+    /// Serverless endpoints, including their workers and templates.
+    pub endpoints: Endpoints,
+
+    /// The account's own templates, including endpoint-bound ones.
+    pub templates: Templates,
+
+    /// Network volumes.
+    pub network_volumes: NetworkVolumes,
+
+    /// Container registry auths.
+    pub registry_auths: ContainerRegistryAuths,
+}
+
+impl AccountSnapshot {
+    /// Captures a snapshot of the account, listing all resources concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing any resource fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn capture<C>(client: &C) -> Result<Self>
+    where
+        C: PodsService + EndpointsService + TemplatesService + VolumesService + RegistryService,
+    {
+        let pods_query = ListPodsQuery {
+            include_machine: Some(true),
+            include_network_volume: Some(true),
+            include_savings_plans: Some(true),
+            ..Default::default()
+        };
+        let endpoints_query = ListEndpointsQuery {
+            include_template: Some(true),
+            include_workers: Some(true),
+        };
+        let templates_query = ListTemplatesQuery {
+            include_endpoint_bound_templates: Some(true),
+            ..Default::default()
+        };
+
+        let captured_at = Utc::now();
+        let (pods, endpoints, templates, network_volumes, registry_auths) = futures::try_join!(
+            client.list_pods(pods_query),
+            client.list_endpoints(endpoints_query),
+            client.list_templates(templates_query),
+            client.list_volumes(),
+            client.list_registry_auths(),
+        )?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            pods = pods.len(),
+            endpoints = endpoints.len(),
+            templates = templates.len(),
+            network_volumes = network_volumes.len(),
+            registry_auths = registry_auths.len(),
+            "Captured account snapshot"
+        );
+
+        Ok(Self {
+            captured_at,
+            pods,
+            endpoints,
+            templates,
+            network_volumes,
+            registry_auths,
+        })
+    }
+
+    /// Computes the differences from this snapshot to a newer one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a resource cannot be converted to JSON.
+    pub fn diff(&self, newer: &AccountSnapshot) -> Result<SnapshotDiff> {
+        SnapshotDiff::compute(self, newer)
+    }
+}
@@ -2,11 +2,40 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
+pub mod analytics;
+pub mod autoscale;
+pub mod budget;
+pub mod capacity;
 mod client;
+#[cfg(feature = "emulator")]
+#[cfg_attr(docsrs, doc(cfg(feature = "emulator")))]
+pub mod emulator;
+pub mod inventory;
+pub mod manifest;
+#[cfg(feature = "migration")]
+#[cfg_attr(docsrs, doc(cfg(feature = "migration")))]
+pub mod migration;
 pub mod model;
 #[doc(hidden)]
 pub mod prelude;
+pub mod pricing;
+pub mod reaper;
+pub mod reconcile;
+#[cfg(feature = "rollout")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rollout")))]
+pub mod rollout;
+pub mod router;
+pub mod schedule;
 pub mod service;
+#[cfg(test)]
+mod testing;
+pub mod versioning;
+#[cfg(feature = "volume-data")]
+#[cfg_attr(docsrs, doc(cfg(feature = "volume-data")))]
+pub mod volume_data;
+#[cfg(feature = "worker")]
+#[cfg_attr(docsrs, doc(cfg(feature = "worker")))]
+pub mod worker;
 
 pub use client::{RunpodBuilder, RunpodClient, RunpodConfig, version};
 
@@ -51,8 +80,6 @@ pub enum Error {
     ///
     /// This occurs when the SDK fails to parse API responses or serialize
     /// request payloads to/from JSON.
-    #[cfg(feature = "graphql")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
@@ -62,6 +89,50 @@ pub enum Error {
     /// the configuration builder and validation fails during the build process.
     #[error("Configuration error: {0}")]
     Config(#[from] RunpodBuilderError),
+
+    /// Local validation error.
+    ///
+    /// This occurs when the SDK rejects input before sending any request,
+    /// for example when a desired-state definition contains duplicate names.
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    /// I/O error.
+    ///
+    /// This occurs when reading or writing local files, such as manifests.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Infrastructure manifest error.
+    ///
+    /// This occurs when a manifest cannot be parsed, fails validation,
+    /// or cannot be applied to the account.
+    #[error("Manifest error: {0}")]
+    Manifest(#[from] crate::manifest::ManifestError),
+
+    /// Budget limit error.
+    ///
+    /// This occurs when a [`BudgetGuard`](crate::budget::BudgetGuard) refuses
+    /// a request whose projected spend exceeds a configured limit.
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(#[from] crate::budget::BudgetExceeded),
+
+    /// GraphQL API error.
+    ///
+    /// This occurs when a GraphQL response reports errors or contains no data.
+    #[cfg(feature = "graphql")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+    #[error("GraphQL error: {0}")]
+    Graphql(String),
+
+    /// S3 API error.
+    ///
+    /// This occurs when the S3-compatible API of a network volume rejects a
+    /// request or returns a response that cannot be parsed.
+    #[cfg(feature = "volume-data")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "volume-data")))]
+    #[error("S3 error: {0}")]
+    S3(String),
 }
 
 /// Result type for RunPod API operations.
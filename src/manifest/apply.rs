@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{Manifest, ManifestError};
+use crate::Result;
+use crate::model::v1::{
+    ContainerRegistryAuthCreateInput, EndpointUpdateInput, ListTemplatesQuery,
+    NetworkVolumeUpdateInput, TemplateUpdateInput,
+};
+use crate::service::v1::{EndpointsService, RegistryService, TemplatesService, VolumesService};
+
+/// Kind of resource managed by a [`Manifest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceKind {
+    /// A container registry auth.
+    RegistryAuth,
+    /// A network volume.
+    NetworkVolume,
+    /// A template.
+    Template,
+    /// A serverless endpoint.
+    Endpoint,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RegistryAuth => f.write_str("registry auth"),
+            Self::NetworkVolume => f.write_str("network volume"),
+            Self::Template => f.write_str("template"),
+            Self::Endpoint => f.write_str("endpoint"),
+        }
+    }
+}
+
+/// What applying a manifest did to a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApplyOperation {
+    /// The resource did not exist and was created.
+    Created,
+    /// The resource existed and was updated in place.
+    Updated,
+    /// The resource already matched the manifest.
+    Unchanged,
+}
+
+/// A single resource touched by [`Manifest::apply`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedResource {
+    /// Kind of the resource.
+    pub kind: ResourceKind,
+    /// Name of the resource in the manifest.
+    pub name: String,
+    /// ID of the resource in the account.
+    pub id: String,
+    /// What was done to the resource.
+    pub operation: ApplyOperation,
+}
+
+impl fmt::Display for AppliedResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = match self.operation {
+            ApplyOperation::Created => "created",
+            ApplyOperation::Updated => "updated",
+            ApplyOperation::Unchanged => "unchanged",
+        };
+        write!(f, "{} {} ({}): {operation}", self.kind, self.name, self.id)
+    }
+}
+
+/// Result of [`Manifest::apply`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyReport {
+    /// Resources in dependency order: registry auths, network volumes,
+    /// templates, then endpoints.
+    pub resources: Vec<AppliedResource>,
+}
+
+impl ApplyReport {
+    /// Returns `true` if no resource was created or updated.
+    pub fn is_unchanged(&self) -> bool {
+        self.resources
+            .iter()
+            .all(|resource| resource.operation == ApplyOperation::Unchanged)
+    }
+
+    fn push(&mut self, kind: ResourceKind, name: &str, id: &str, operation: ApplyOperation) {
+        self.resources.push(AppliedResource {
+            kind,
+            name: name.to_string(),
+            id: id.to_string(),
+            operation,
+        });
+    }
+}
+
+impl Manifest {
+    /// Creates or updates every resource of the manifest in the account.
+    ///
+    /// The manifest is validated first. Resources are matched against the
+    /// account by name and applied in dependency order, resolving name
+    /// references to IDs along the way:
+    ///
+    /// - registry auths are created when missing (their credentials cannot be updated),
+    /// - network volumes are created when missing or grown when larger,
+    /// - templates and endpoints are created when missing or updated when they drift.
+    ///
+    /// Resources present in the account but absent from the manifest are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is invalid, if credentials for a new
+    /// registry auth are missing, if a network volume would have to shrink or
+    /// move, or if any request fails. Resources applied before the failure
+    /// are not rolled back.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn apply<C>(&self, client: &C) -> Result<ApplyReport>
+    where
+        C: RegistryService + VolumesService + TemplatesService + EndpointsService,
+    {
+        self.validate()?;
+
+        let mut report = ApplyReport::default();
+
+        let auth_ids = self.apply_registry_auths(client, &mut report).await?;
+        let volume_ids = self.apply_network_volumes(client, &mut report).await?;
+        let template_ids = self.apply_templates(client, &auth_ids, &mut report).await?;
+        self.apply_endpoints(client, &template_ids, &volume_ids, &mut report)
+            .await?;
+
+        Ok(report)
+    }
+
+    async fn apply_registry_auths<C: RegistryService>(
+        &self,
+        client: &C,
+        report: &mut ApplyReport,
+    ) -> Result<HashMap<String, String>> {
+        let mut ids: HashMap<String, String> = client
+            .list_registry_auths()
+            .await?
+            .into_iter()
+            .map(|auth| (auth.name, auth.id))
+            .collect();
+
+        for auth in &self.registry_auths {
+            if let Some(id) = ids.get(&auth.name) {
+                report.push(
+                    ResourceKind::RegistryAuth,
+                    &auth.name,
+                    id,
+                    ApplyOperation::Unchanged,
+                );
+                continue;
+            }
+
+            let password = match (&auth.password, &auth.password_env) {
+                (Some(password), _) => Some(password.clone()),
+                (None, Some(var)) => std::env::var(var).ok(),
+                (None, None) => None,
+            };
+            let (Some(username), Some(password)) = (auth.username.clone(), password) else {
+                return Err(ManifestError::MissingCredentials(auth.name.clone()).into());
+            };
+
+            let created = client
+                .create_registry_auth(ContainerRegistryAuthCreateInput {
+                    name: auth.name.clone(),
+                    username,
+                    password,
+                })
+                .await?;
+            report.push(
+                ResourceKind::RegistryAuth,
+                &auth.name,
+                &created.id,
+                ApplyOperation::Created,
+            );
+            ids.insert(auth.name.clone(), created.id);
+        }
+
+        Ok(ids)
+    }
+
+    async fn apply_network_volumes<C: VolumesService>(
+        &self,
+        client: &C,
+        report: &mut ApplyReport,
+    ) -> Result<HashMap<String, String>> {
+        let live = client.list_volumes().await?;
+        let mut ids = HashMap::new();
+
+        for volume in &self.network_volumes {
+            let Some(existing) = live.iter().find(|v| v.name == volume.name) else {
+                let created = client.create_volume(volume.clone()).await?;
+                report.push(
+                    ResourceKind::NetworkVolume,
+                    &volume.name,
+                    &created.id,
+                    ApplyOperation::Created,
+                );
+                ids.insert(volume.name.clone(), created.id);
+                continue;
+            };
+
+            if existing.data_center_id != volume.data_center_id {
+                return Err(ManifestError::Invalid(vec![format!(
+                    "network volume '{}' is in {}, not {}",
+                    volume.name, existing.data_center_id, volume.data_center_id
+                )])
+                .into());
+            }
+
+            let live_size = u32::try_from(existing.size).unwrap_or_default();
+            let operation = if volume.size > live_size {
+                let input = NetworkVolumeUpdateInput {
+                    size: Some(volume.size),
+                    ..Default::default()
+                };
+                client.update_volume(&existing.id, input).await?;
+                ApplyOperation::Updated
+            } else if volume.size < live_size {
+                return Err(ManifestError::Invalid(vec![format!(
+                    "network volume '{}' cannot shrink from {live_size} GB to {} GB",
+                    volume.name, volume.size
+                )])
+                .into());
+            } else {
+                ApplyOperation::Unchanged
+            };
+
+            report.push(
+                ResourceKind::NetworkVolume,
+                &volume.name,
+                &existing.id,
+                operation,
+            );
+            ids.insert(volume.name.clone(), existing.id.clone());
+        }
+
+        Ok(ids)
+    }
+
+    async fn apply_templates<C: TemplatesService>(
+        &self,
+        client: &C,
+        auth_ids: &HashMap<String, String>,
+        report: &mut ApplyReport,
+    ) -> Result<HashMap<String, String>> {
+        let query = ListTemplatesQuery {
+            include_endpoint_bound_templates: Some(true),
+            ..Default::default()
+        };
+        let live = client.list_templates(query).await?;
+        let mut ids = HashMap::new();
+
+        for manifest in &self.templates {
+            let mut input = manifest.template.clone();
+            if let Some(auth) = &manifest.registry_auth {
+                input.container_registry_auth_id = auth_ids.get(auth).cloned();
+            }
+
+            let (id, operation) = match live.iter().find(|t| t.name == input.name) {
+                None => {
+                    let created = client.create_template(input).await?;
+                    (created.id, ApplyOperation::Created)
+                }
+                Some(existing) => match TemplateUpdateInput::diff(&input, existing) {
+                    Some(update) => {
+                        client.update_template(&existing.id, update).await?;
+                        (existing.id.clone(), ApplyOperation::Updated)
+                    }
+                    None => (existing.id.clone(), ApplyOperation::Unchanged),
+                },
+            };
+
+            report.push(
+                ResourceKind::Template,
+                &manifest.template.name,
+                &id,
+                operation,
+            );
+            ids.insert(manifest.template.name.clone(), id);
+        }
+
+        Ok(ids)
+    }
+
+    async fn apply_endpoints<C: EndpointsService>(
+        &self,
+        client: &C,
+        template_ids: &HashMap<String, String>,
+        volume_ids: &HashMap<String, String>,
+        report: &mut ApplyReport,
+    ) -> Result<()> {
+        let live = client.list_endpoints(Default::default()).await?;
+
+        for manifest in &self.endpoints {
+            let input = manifest.resolve(template_ids, volume_ids)?;
+            let name = input.name.clone().unwrap_or_default();
+
+            let existing = live
+                .iter()
+                .find(|endpoint| endpoint.name.as_deref() == Some(name.as_str()));
+            let (id, operation) = match existing {
+                None => {
+                    let created = client.create_endpoint(input).await?;
+                    (created.id, ApplyOperation::Created)
+                }
+                Some(existing) => match EndpointUpdateInput::diff(&input, existing) {
+                    Some(update) => {
+                        client.update_endpoint(&existing.id, update).await?;
+                        (existing.id.clone(), ApplyOperation::Updated)
+                    }
+                    None => (existing.id.clone(), ApplyOperation::Unchanged),
+                },
+            };
+
+            report.push(ResourceKind::Endpoint, &name, &id, operation);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::manifest::ManifestFormat;
+    use crate::testing::{Cloud, volume};
+
+    const MANIFEST: &str = r#"{
+        "version": 1,
+        "registryAuths": [{ "name": "ghcr", "username": "me", "password": "secret" }],
+        "networkVolumes": [{ "name": "data", "size": 100, "dataCenterId": "EU-RO-1" }],
+        "templates": [
+            { "name": "worker", "imageName": "ghcr.io/me/worker:1.0", "registryAuth": "ghcr" }
+        ],
+        "endpoints": [
+            { "name": "inference", "template": "worker", "networkVolume": "data", "workersMax": 2 }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn test_apply_resolves_references() -> crate::Result<()> {
+        let cloud = Cloud::new().with_volume(volume(json!({})));
+        let manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+
+        let report = manifest.apply(&cloud).await?;
+        let operations: Vec<_> = report
+            .resources
+            .iter()
+            .map(|r| (r.kind, r.id.as_str(), r.operation))
+            .collect();
+        assert_eq!(
+            operations,
+            [
+                (
+                    ResourceKind::RegistryAuth,
+                    "auth-1",
+                    ApplyOperation::Created
+                ),
+                (
+                    ResourceKind::NetworkVolume,
+                    "volume",
+                    ApplyOperation::Unchanged
+                ),
+                (
+                    ResourceKind::Template,
+                    "template-2",
+                    ApplyOperation::Created
+                ),
+                (
+                    ResourceKind::Endpoint,
+                    "endpoint-3",
+                    ApplyOperation::Created
+                ),
+            ]
+        );
+
+        let template = cloud.template("template-2").unwrap();
+        assert_eq!(
+            template.container_registry_auth_id.as_deref(),
+            Some("auth-1")
+        );
+        let endpoint = cloud.endpoint("endpoint-3").unwrap();
+        assert_eq!(endpoint.template_id, "template-2");
+        assert_eq!(endpoint.network_volume_id.as_deref(), Some("volume"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_rejects_missing_reference() -> crate::Result<()> {
+        let cloud = Cloud::new();
+        let mut manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+        manifest.endpoints[0].template = Some("trainer".to_string());
+
+        let result = manifest.apply(&cloud).await;
+        assert!(matches!(
+            result,
+            Err(crate::Error::Manifest(ManifestError::Invalid(problems)))
+                if problems == ["endpoint 'inference' references unknown template 'trainer'"]
+        ));
+        assert!(cloud.calls().is_empty());
+
+        let result = manifest.endpoints[0].resolve(&HashMap::new(), &HashMap::new());
+        let Err(ManifestError::Invalid(problems)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(problems.len(), 2);
+        Ok(())
+    }
+}
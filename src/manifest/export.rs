@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use super::{EndpointManifest, Manifest, RegistryAuthManifest, TemplateManifest};
+use crate::Result;
+use crate::model::v1::{
+    EndpointCreateInput, ListTemplatesQuery, NetworkVolumeCreateInput, TemplateCreateInput,
+};
+use crate::service::v1::{EndpointsService, RegistryService, TemplatesService, VolumesService};
+
+impl Manifest {
+    /// Builds a manifest describing the live account.
+    ///
+    /// Registry auths, network volumes, the account's own templates (including
+    /// endpoint-bound ones) and serverless endpoints are exported. IDs are
+    /// replaced by name references whenever the referenced resource is part of
+    /// the manifest. Endpoints without a name are exported under their ID.
+    ///
+    /// Registry credentials are never returned by the API and are therefore
+    /// omitted, whereas template environment variables are exported as-is and
+    /// may contain secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing any resource fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn export<C>(client: &C) -> Result<Self>
+    where
+        C: RegistryService + VolumesService + TemplatesService + EndpointsService,
+    {
+        let templates_query = ListTemplatesQuery {
+            include_endpoint_bound_templates: Some(true),
+            ..Default::default()
+        };
+        let (auths, volumes, templates, endpoints) = futures::try_join!(
+            client.list_registry_auths(),
+            client.list_volumes(),
+            client.list_templates(templates_query),
+            client.list_endpoints(Default::default()),
+        )?;
+
+        let auth_names: HashMap<_, _> = auths
+            .iter()
+            .map(|auth| (auth.id.as_str(), auth.name.as_str()))
+            .collect();
+        let volume_names: HashMap<_, _> = volumes
+            .iter()
+            .map(|volume| (volume.id.as_str(), volume.name.as_str()))
+            .collect();
+        let templates: Vec<_> = templates
+            .into_iter()
+            .filter(|template| !template.is_runpod)
+            .collect();
+        let template_names: HashMap<_, _> = templates
+            .iter()
+            .map(|template| (template.id.as_str(), template.name.as_str()))
+            .collect();
+
+        let templates = templates
+            .iter()
+            .map(|template| {
+                let mut template = TemplateCreateInput::from_template(template);
+                let registry_auth = template
+                    .container_registry_auth_id
+                    .as_deref()
+                    .and_then(|id| auth_names.get(id))
+                    .map(|name| name.to_string());
+                if registry_auth.is_some() {
+                    template.container_registry_auth_id = None;
+                }
+                TemplateManifest {
+                    template,
+                    registry_auth,
+                }
+            })
+            .collect();
+
+        let endpoints = endpoints
+            .iter()
+            .map(|endpoint| {
+                let mut input = EndpointCreateInput::from_endpoint(endpoint);
+                input.name.get_or_insert_with(|| endpoint.id.clone());
+                let template = template_names
+                    .get(endpoint.template_id.as_str())
+                    .map(|name| name.to_string());
+                if template.is_some() {
+                    input.template_id.clear();
+                }
+                let network_volume = input
+                    .network_volume_id
+                    .as_deref()
+                    .and_then(|id| volume_names.get(id))
+                    .map(|name| name.to_string());
+                if network_volume.is_some() {
+                    input.network_volume_id = None;
+                }
+                EndpointManifest {
+                    endpoint: input,
+                    template,
+                    network_volume,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            registry_auths: auths
+                .into_iter()
+                .map(|auth| RegistryAuthManifest {
+                    name: auth.name,
+                    ..Default::default()
+                })
+                .collect(),
+            network_volumes: volumes
+                .into_iter()
+                .map(|volume| NetworkVolumeCreateInput {
+                    name: volume.name,
+                    size: u32::try_from(volume.size).unwrap_or_default(),
+                    data_center_id: volume.data_center_id,
+                })
+                .collect(),
+            templates,
+            endpoints,
+            ..Default::default()
+        })
+    }
+}
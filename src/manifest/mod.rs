@@ -0,0 +1,197 @@
+//! Versioned infrastructure manifests for templates, endpoints, network volumes
+//! and container registry auths.
+//!
+//! A [`Manifest`] describes account resources using the SDK request models and
+//! lets resources refer to each other by name instead of ID. Manifests can be
+//! loaded from and saved to JSON, YAML (`yaml` feature) or TOML (`toml`
+//! feature), validated locally, applied to an account, and exported from the
+//! live account.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::manifest::Manifest;
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//!
+//! let manifest = Manifest::load("runpod.json")?;
+//! manifest.validate()?;
+//!
+//! let report = manifest.apply(&client).await?;
+//! for resource in &report.resources {
+//!     println!("{resource}");
+//! }
+//!
+//! // Capture the live account as a manifest.
+//! Manifest::export(&client).await?.save("exported.json")?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod apply;
+mod export;
+mod schema;
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+pub use apply::{AppliedResource, ApplyOperation, ApplyReport, ResourceKind};
+pub use schema::{
+    EndpointManifest, MANIFEST_VERSION, Manifest, RegistryAuthManifest, TemplateManifest,
+};
+
+use crate::Result;
+
+/// Errors specific to loading, validating and applying manifests.
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    /// The manifest is not valid YAML or does not match the schema.
+    #[cfg(feature = "yaml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// The manifest is not valid TOML or does not match the schema.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    #[error("TOML error: {0}")]
+    TomlDe(#[from] toml::de::Error),
+
+    /// The manifest cannot be represented as TOML.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    #[error("TOML error: {0}")]
+    TomlSer(#[from] toml::ser::Error),
+
+    /// The file format is unknown or its feature is not enabled.
+    #[error("unsupported manifest format: {0}")]
+    UnsupportedFormat(String),
+
+    /// The manifest failed validation.
+    #[error("invalid manifest: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+
+    /// A registry auth must be created but its credentials are missing.
+    #[error("missing credentials for registry auth '{0}'")]
+    MissingCredentials(String),
+}
+
+/// Serialization format of a manifest file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// JSON (`.json`).
+    Json,
+
+    /// YAML (`.yaml`, `.yml`). Requires the `yaml` feature.
+    Yaml,
+
+    /// TOML (`.toml`). Requires the `toml` feature.
+    Toml,
+}
+
+impl ManifestFormat {
+    /// Detects the format from the extension of `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::UnsupportedFormat`] for missing or unknown extensions.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        extension.parse()
+    }
+}
+
+impl FromStr for ManifestFormat {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            _ => Err(ManifestError::UnsupportedFormat(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ManifestFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => f.write_str("json"),
+            Self::Yaml => f.write_str("yaml"),
+            Self::Toml => f.write_str("toml"),
+        }
+    }
+}
+
+impl Manifest {
+    /// Parses a manifest from a string in the given format.
+    ///
+    /// The manifest is not validated; call [`Manifest::validate`] afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be parsed or if the format's
+    /// feature is not enabled.
+    pub fn parse_str(s: &str, format: ManifestFormat) -> Result<Self> {
+        match format {
+            ManifestFormat::Json => Ok(serde_json::from_str(s)?),
+            #[cfg(feature = "yaml")]
+            ManifestFormat::Yaml => Ok(serde_yaml::from_str(s).map_err(ManifestError::from)?),
+            #[cfg(feature = "toml")]
+            ManifestFormat::Toml => Ok(toml::from_str(s).map_err(ManifestError::from)?),
+            #[allow(unreachable_patterns)]
+            format => Err(ManifestError::UnsupportedFormat(format.to_string()).into()),
+        }
+    }
+
+    /// Serializes the manifest to a string in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails or if the format's feature is
+    /// not enabled.
+    pub fn to_format_string(&self, format: ManifestFormat) -> Result<String> {
+        match format {
+            ManifestFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            #[cfg(feature = "yaml")]
+            ManifestFormat::Yaml => Ok(serde_yaml::to_string(self).map_err(ManifestError::from)?),
+            #[cfg(feature = "toml")]
+            ManifestFormat::Toml => Ok(toml::to_string(self).map_err(ManifestError::from)?),
+            #[allow(unreachable_patterns)]
+            format => Err(ManifestError::UnsupportedFormat(format.to_string()).into()),
+        }
+    }
+
+    /// Reads a manifest from a file, detecting the format from its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = ManifestFormat::from_path(path)?;
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse_str(&contents, format)
+    }
+
+    /// Writes the manifest to a file, detecting the format from its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be serialized or written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = self.to_format_string(ManifestFormat::from_path(path)?)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::ManifestError;
+use crate::model::v1::{EndpointCreateInput, NetworkVolumeCreateInput, TemplateCreateInput};
+
+/// The manifest schema version understood by this SDK.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Versioned, serializable description of account resources.
+///
+/// A manifest maps directly onto the SDK request models, adding name-based
+/// cross-references that are resolved to IDs when the manifest is applied:
+///
+/// - endpoint → template (`template`)
+/// - endpoint → network volume (`networkVolume`)
+/// - template → registry auth (`registryAuth`)
+///
+/// Field names follow the camelCase naming of the RunPod API in every format.
+///
+/// # Examples
+///
+/// ```rust
+/// use runpod_sdk::manifest::{Manifest, ManifestFormat};
+///
+/// let manifest = Manifest::parse_str(
+///     r#"{
+///         "version": 1,
+///         "templates": [
+///             { "name": "worker", "imageName": "me/worker:1.0", "isServerless": true }
+///         ],
+///         "endpoints": [
+///             { "name": "inference", "template": "worker", "workersMax": 3 }
+///         ]
+///     }"#,
+///     ManifestFormat::Json,
+/// )
+/// .unwrap();
+///
+/// manifest.validate().unwrap();
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    /// Schema version, must be [`MANIFEST_VERSION`].
+    pub version: u32,
+
+    /// Container registry credentials referenced by templates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registry_auths: Vec<RegistryAuthManifest>,
+
+    /// Network volumes referenced by endpoints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_volumes: Vec<NetworkVolumeCreateInput>,
+
+    /// Templates referenced by endpoints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<TemplateManifest>,
+
+    /// Serverless endpoints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<EndpointManifest>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            registry_auths: Vec::new(),
+            network_volumes: Vec::new(),
+            templates: Vec::new(),
+            endpoints: Vec::new(),
+        }
+    }
+}
+
+/// Container registry credentials in a manifest.
+///
+/// Credentials are only needed to create a missing registry auth. Since the API
+/// never returns them, exported manifests contain only the name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryAuthManifest {
+    /// Unique name of the registry auth.
+    pub name: String,
+
+    /// Username for the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Password or access token. Prefer `password_env` to keep secrets out of version control.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Name of an environment variable holding the password, read at apply time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_env: Option<String>,
+}
+
+/// Template definition in a manifest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateManifest {
+    /// Template configuration. `name` must be unique within the manifest.
+    #[serde(flatten)]
+    pub template: TemplateCreateInput,
+
+    /// Name of the registry auth to use. Mutually exclusive with `containerRegistryAuthId`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_auth: Option<String>,
+}
+
+/// Serverless endpoint definition in a manifest.
+///
+/// `templateId` may be omitted when the endpoint refers to its template by
+/// name; `endpoint.template_id` is then left empty until the manifest is
+/// applied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "EndpointManifestRepr", into = "EndpointManifestRepr")]
+pub struct EndpointManifest {
+    /// Endpoint configuration. `name` is required and must be unique within the manifest.
+    pub endpoint: EndpointCreateInput,
+
+    /// Name of the template to use. Mutually exclusive with `templateId`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Name of the network volume to attach. Mutually exclusive with `networkVolumeId`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_volume: Option<String>,
+}
+
+impl EndpointManifest {
+    /// Builds the create input of the endpoint, resolving name references.
+    ///
+    /// `template_ids` and `volume_ids` map template and network volume names
+    /// to their IDs in the account.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::Invalid`] if a referenced template or network
+    /// volume has no ID.
+    pub fn resolve(
+        &self,
+        template_ids: &HashMap<String, String>,
+        volume_ids: &HashMap<String, String>,
+    ) -> Result<EndpointCreateInput, ManifestError> {
+        let mut input = self.endpoint.clone();
+        let name = input.name.as_deref().unwrap_or_default();
+        let mut problems = Vec::new();
+
+        if let Some(template) = &self.template {
+            match template_ids.get(template) {
+                Some(id) => input.template_id = id.clone(),
+                None => problems.push(format!(
+                    "endpoint '{name}' references unknown template '{template}'"
+                )),
+            }
+        }
+        if let Some(volume) = &self.network_volume {
+            match volume_ids.get(volume) {
+                Some(id) => input.network_volume_id = Some(id.clone()),
+                None => problems.push(format!(
+                    "endpoint '{name}' references unknown network volume '{volume}'"
+                )),
+            }
+        }
+
+        match problems.is_empty() {
+            true => Ok(input),
+            false => Err(ManifestError::Invalid(problems)),
+        }
+    }
+}
+
+/// Serialized form of [`EndpointManifest`], where `templateId` is optional.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EndpointManifestRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    template: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    network_volume: Option<String>,
+
+    #[serde(flatten)]
+    endpoint: Map<String, Value>,
+}
+
+impl TryFrom<EndpointManifestRepr> for EndpointManifest {
+    type Error = serde_json::Error;
+
+    fn try_from(repr: EndpointManifestRepr) -> Result<Self, Self::Error> {
+        let mut endpoint = repr.endpoint;
+        if repr.template.is_some() {
+            endpoint
+                .entry("templateId")
+                .or_insert_with(|| Value::String(String::new()));
+        }
+        Ok(Self {
+            endpoint: serde_json::from_value(Value::Object(endpoint))?,
+            template: repr.template,
+            network_volume: repr.network_volume,
+        })
+    }
+}
+
+impl From<EndpointManifest> for EndpointManifestRepr {
+    fn from(manifest: EndpointManifest) -> Self {
+        let mut endpoint = match serde_json::to_value(manifest.endpoint) {
+            Ok(Value::Object(endpoint)) => endpoint,
+            _ => Map::new(),
+        };
+        if endpoint.get("templateId") == Some(&Value::String(String::new())) {
+            endpoint.remove("templateId");
+        }
+        Self {
+            template: manifest.template,
+            network_volume: manifest.network_volume,
+            endpoint,
+        }
+    }
+}
+
+impl Manifest {
+    /// Checks the manifest for structural problems and dangling references.
+    ///
+    /// All problems are collected and returned together.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::Invalid`] listing every problem found.
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        let mut problems = Vec::new();
+
+        if self.version != MANIFEST_VERSION {
+            problems.push(format!(
+                "unsupported version {}, expected {MANIFEST_VERSION}",
+                self.version
+            ));
+        }
+
+        let registry_auths = unique_names(
+            "registry auth",
+            self.registry_auths.iter().map(|auth| auth.name.as_str()),
+            &mut problems,
+        );
+        let volumes = unique_names(
+            "network volume",
+            self.network_volumes
+                .iter()
+                .map(|volume| volume.name.as_str()),
+            &mut problems,
+        );
+        let templates = unique_names(
+            "template",
+            self.templates.iter().map(|t| t.template.name.as_str()),
+            &mut problems,
+        );
+        unique_names(
+            "endpoint",
+            self.endpoints
+                .iter()
+                .map(|e| e.endpoint.name.as_deref().unwrap_or_default()),
+            &mut problems,
+        );
+
+        for auth in &self.registry_auths {
+            if auth.password.is_some() && auth.password_env.is_some() {
+                problems.push(format!(
+                    "registry auth '{}' sets both password and passwordEnv",
+                    auth.name
+                ));
+            }
+        }
+
+        for volume in &self.network_volumes {
+            if volume.size == 0 {
+                problems.push(format!("network volume '{}' has size 0", volume.name));
+            }
+            if volume.data_center_id.is_empty() {
+                problems.push(format!(
+                    "network volume '{}' has no dataCenterId",
+                    volume.name
+                ));
+            }
+        }
+
+        for manifest in &self.templates {
+            let template = &manifest.template;
+            if template.image_name.is_empty() {
+                problems.push(format!("template '{}' has no imageName", template.name));
+            }
+            match (
+                &manifest.registry_auth,
+                &template.container_registry_auth_id,
+            ) {
+                (Some(_), Some(_)) => problems.push(format!(
+                    "template '{}' sets both registryAuth and containerRegistryAuthId",
+                    template.name
+                )),
+                (Some(name), None) if !registry_auths.contains(name.as_str()) => {
+                    problems.push(format!(
+                        "template '{}' references unknown registry auth '{name}'",
+                        template.name
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        for manifest in &self.endpoints {
+            let endpoint = &manifest.endpoint;
+            let name = endpoint.name.as_deref().unwrap_or_default();
+            match (&manifest.template, endpoint.template_id.is_empty()) {
+                (Some(_), false) => problems.push(format!(
+                    "endpoint '{name}' sets both template and templateId"
+                )),
+                (None, true) => problems.push(format!(
+                    "endpoint '{name}' sets neither template nor templateId"
+                )),
+                (Some(template), true) if !templates.contains(template.as_str()) => problems.push(
+                    format!("endpoint '{name}' references unknown template '{template}'"),
+                ),
+                _ => {}
+            }
+            match (&manifest.network_volume, &endpoint.network_volume_id) {
+                (Some(_), Some(_)) => problems.push(format!(
+                    "endpoint '{name}' sets both networkVolume and networkVolumeId"
+                )),
+                (Some(volume), None) if !volumes.contains(volume.as_str()) => problems.push(
+                    format!("endpoint '{name}' references unknown network volume '{volume}'"),
+                ),
+                _ => {}
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ManifestError::Invalid(problems))
+        }
+    }
+}
+
+fn unique_names<'a>(
+    kind: &str,
+    names: impl Iterator<Item = &'a str>,
+    problems: &mut Vec<String>,
+) -> HashSet<&'a str> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if name.is_empty() {
+            problems.push(format!("{kind} without a name"));
+        } else if !seen.insert(name) {
+            problems.push(format!("duplicate {kind} name '{name}'"));
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestFormat;
+
+    const MANIFEST: &str = r#"{
+        "version": 1,
+        "registryAuths": [{ "name": "ghcr", "username": "me", "passwordEnv": "GHCR_TOKEN" }],
+        "networkVolumes": [{ "name": "models", "size": 100, "dataCenterId": "EU-RO-1" }],
+        "templates": [
+            { "name": "worker", "imageName": "ghcr.io/me/worker:1.0", "registryAuth": "ghcr" }
+        ],
+        "endpoints": [
+            { "name": "inference", "template": "worker", "networkVolume": "models", "workersMax": 2 }
+        ]
+    }"#;
+
+    fn problems(manifest: &Manifest) -> Vec<String> {
+        match manifest.validate() {
+            Ok(()) => Vec::new(),
+            Err(ManifestError::Invalid(problems)) => problems,
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_valid_manifest() -> crate::Result<()> {
+        let manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+        assert!(problems(&manifest).is_empty());
+        assert_eq!(manifest.endpoints[0].template.as_deref(), Some("worker"));
+        assert!(manifest.endpoints[0].endpoint.template_id.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation_collects_all_problems() -> crate::Result<()> {
+        let mut manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+        manifest.version = 2;
+        manifest.templates[0].registry_auth = Some("dockerhub".to_string());
+        manifest.endpoints[0].network_volume = Some("datasets".to_string());
+        manifest.endpoints.push(manifest.endpoints[0].clone());
+
+        let problems = problems(&manifest);
+        assert_eq!(problems.len(), 5, "{problems:?}");
+        assert!(problems.iter().any(|p| p.contains("unsupported version")));
+        assert!(problems.iter().any(|p| p.contains("duplicate endpoint")));
+        assert!(problems.iter().any(|p| p.contains("'dockerhub'")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_endpoint_requires_exactly_one_template_reference() -> crate::Result<()> {
+        let mut manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+        manifest.endpoints[0].endpoint.template_id = "tpl_123".to_string();
+        assert!(problems(&manifest)[0].contains("both template and templateId"));
+
+        manifest.endpoints[0].template = None;
+        assert!(problems(&manifest).is_empty());
+        Ok(())
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_round_trip() -> crate::Result<()> {
+        let manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+        let yaml = manifest.to_format_string(ManifestFormat::Yaml)?;
+        let parsed = Manifest::parse_str(&yaml, ManifestFormat::Yaml)?;
+        assert_eq!(
+            parsed.to_format_string(ManifestFormat::Json)?,
+            manifest.to_format_string(ManifestFormat::Json)?
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_round_trip() -> crate::Result<()> {
+        let manifest = Manifest::parse_str(MANIFEST, ManifestFormat::Json)?;
+        let toml = manifest.to_format_string(ManifestFormat::Toml)?;
+        let parsed = Manifest::parse_str(&toml, ManifestFormat::Toml)?;
+        assert_eq!(
+            parsed.to_format_string(ManifestFormat::Json)?,
+            manifest.to_format_string(ManifestFormat::Json)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,544 @@
+//! Pod migration across data centers and GPU types.
+//!
+//! A [`PodMigration`] moves a Pod to another data center when its current one
+//! runs out of capacity. It stops the source Pod, creates a replacement with
+//! the same configuration in the [`MigrationTarget`], and terminates the
+//! source only once the replacement has started. If any step fails, the
+//! replacement and any network volume created for it are deleted and the
+//! source Pod is started again. Once anything has been created, failures to
+//! clean up are collected in [`MigrationReport::cleanup_errors`] rather than
+//! returned, so the IDs of resources left behind are never lost.
+//!
+//! A network volume attached to the source Pod is reused when it already lives
+//! in the target data center. Otherwise an empty volume of the same name and
+//! size is created there, as volume data cannot be copied through the API.
+//! Disable [`MigrationPolicy::terminate_source`] to copy the data from the
+//! source before terminating it.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::migration::{MigrationTarget, PodMigration};
+//! use runpod_sdk::model::v1::{DataCenterId, GpuTypeId};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let target = MigrationTarget::new(DataCenterId::EuRo1)
+//!     .with_gpu_types(vec![GpuTypeId::NvidiaA40, GpuTypeId::NvidiaRtxA6000]);
+//!
+//! let report = PodMigration::new(client).run("pod_id", target).await?;
+//! match report.outcome {
+//!     Ok(()) => println!("migrated to {}", report.pod_id.unwrap_or_default()),
+//!     Err(failure) => eprintln!("rolled back: {failure}"),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::model::v1::{
+    CloudType, DataCenterId, GetPodQuery, GpuTypeId, NetworkVolumeCreateInput, Pod, PodCreateInput,
+    PodStatus, api_value,
+};
+use crate::service::v1::{PodsService, VolumesService};
+use crate::{Error, Result, RunpodClient};
+
+/// Where a [`PodMigration`] moves a Pod.
+#[derive(Debug, Clone)]
+pub struct MigrationTarget {
+    /// Data center of the replacement Pod.
+    pub data_center_id: DataCenterId,
+
+    /// GPU types of the replacement Pod, in order of preference. Empty keeps
+    /// the GPU type of the source Pod.
+    pub gpu_type_ids: Vec<GpuTypeId>,
+
+    /// Cloud of the replacement Pod. `None` keeps the cloud of the source Pod.
+    pub cloud_type: Option<CloudType>,
+
+    /// What to do with the network volume of the source Pod.
+    pub network_volume: VolumeMigration,
+}
+
+impl MigrationTarget {
+    /// Creates a target in `data_center_id` keeping the GPU type and cloud.
+    pub fn new(data_center_id: DataCenterId) -> Self {
+        Self {
+            data_center_id,
+            gpu_type_ids: Vec::new(),
+            cloud_type: None,
+            network_volume: VolumeMigration::default(),
+        }
+    }
+
+    /// Sets the GPU types of the replacement Pod.
+    pub fn with_gpu_types(mut self, gpu_type_ids: Vec<GpuTypeId>) -> Self {
+        self.gpu_type_ids = gpu_type_ids;
+        self
+    }
+
+    /// Sets the cloud of the replacement Pod.
+    pub fn with_cloud_type(mut self, cloud_type: CloudType) -> Self {
+        self.cloud_type = Some(cloud_type);
+        self
+    }
+
+    /// Sets what to do with the network volume of the source Pod.
+    pub fn with_network_volume(mut self, network_volume: VolumeMigration) -> Self {
+        self.network_volume = network_volume;
+        self
+    }
+
+    /// Returns the input creating the replacement of `pod` in this target.
+    ///
+    /// The network volume is left as on the source Pod.
+    pub fn replacement(&self, pod: &Pod) -> PodCreateInput {
+        let mut input = PodCreateInput::from_pod(pod);
+        input.data_center_ids = Some(vec![self.data_center_id.clone()]);
+        if !self.gpu_type_ids.is_empty() {
+            input.gpu_type_ids = Some(self.gpu_type_ids.clone());
+        }
+        if self.cloud_type.is_some() {
+            input.cloud_type = self.cloud_type;
+        }
+        input
+    }
+}
+
+/// Network volume handling of a [`PodMigration`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum VolumeMigration {
+    /// Reuse the volume if it is in the target data center, otherwise create
+    /// an empty volume of the same name and size there.
+    #[default]
+    Create,
+    /// Attach an existing volume in the target data center.
+    Attach(String),
+    /// Create the replacement without a network volume.
+    Detach,
+}
+
+/// Timing of a [`PodMigration`].
+#[derive(Debug, Clone)]
+pub struct MigrationPolicy {
+    /// How long the replacement Pod may take to start before rolling back.
+    pub start_timeout: Duration,
+
+    /// Time between two checks of the replacement Pod.
+    pub poll_interval: Duration,
+
+    /// Terminate the source Pod once the replacement has started. When
+    /// disabled, the source is left stopped.
+    pub terminate_source: bool,
+}
+
+impl Default for MigrationPolicy {
+    fn default() -> Self {
+        Self {
+            start_timeout: Duration::from_secs(10 * 60),
+            poll_interval: Duration::from_secs(10),
+            terminate_source: true,
+        }
+    }
+}
+
+/// Why a [`PodMigration`] was rolled back.
+#[derive(Debug)]
+pub enum MigrationFailure {
+    /// Creating the network volume or the replacement Pod failed.
+    Request(Error),
+    /// The replacement Pod did not start within the start timeout.
+    Timeout,
+    /// The replacement Pod stopped before it started.
+    Stopped(PodStatus),
+}
+
+impl fmt::Display for MigrationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(error) => write!(f, "request failed: {error}"),
+            Self::Timeout => f.write_str("replacement Pod did not start in time"),
+            Self::Stopped(status) => write!(f, "replacement Pod stopped with status {status:?}"),
+        }
+    }
+}
+
+/// Result of a [`PodMigration`].
+#[derive(Debug)]
+pub struct MigrationReport {
+    /// Source Pod ID.
+    pub source_pod_id: String,
+
+    /// Replacement Pod ID, if it was created. The Pod is deleted again when
+    /// the migration is rolled back, unless [`cleanup_errors`] says otherwise.
+    ///
+    /// [`cleanup_errors`]: Self::cleanup_errors
+    pub pod_id: Option<String>,
+
+    /// Network volume attached to the source Pod.
+    pub source_volume_id: Option<String>,
+
+    /// Network volume attached to the replacement Pod.
+    pub volume_id: Option<String>,
+
+    /// Whether the network volume was created by the migration.
+    pub volume_created: bool,
+
+    /// Whether the source Pod was terminated.
+    pub source_terminated: bool,
+
+    /// Time the migration started.
+    pub started_at: DateTime<Utc>,
+
+    /// Time the migration finished.
+    pub finished_at: DateTime<Utc>,
+
+    /// `Ok` if the replacement Pod started, or why the migration was rolled back.
+    pub outcome: Result<(), MigrationFailure>,
+
+    /// Errors of the requests terminating the source Pod or rolling back, in
+    /// the order they happened. Resources whose cleanup failed may still be
+    /// billed.
+    pub cleanup_errors: Vec<Error>,
+}
+
+impl MigrationReport {
+    /// Returns `true` if the migration succeeded and cleaned up after itself.
+    pub fn is_success(&self) -> bool {
+        self.outcome.is_ok() && self.cleanup_errors.is_empty()
+    }
+}
+
+/// Moves Pods to other data centers and GPU types.
+///
+/// Requires the `migration` feature, as waiting for the replacement Pod uses
+/// the Tokio timer.
+#[derive(Debug, Clone)]
+pub struct PodMigration<C = RunpodClient> {
+    client: C,
+    policy: MigrationPolicy,
+}
+
+impl<C> PodMigration<C> {
+    /// Creates a migration with the default policy.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            policy: MigrationPolicy::default(),
+        }
+    }
+
+    /// Replaces the migration policy.
+    pub fn with_policy(mut self, policy: MigrationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the migration policy.
+    pub fn policy(&self) -> &MigrationPolicy {
+        &self.policy
+    }
+}
+
+impl<C> PodMigration<C>
+where
+    C: PodsService + VolumesService,
+{
+    /// Migrates a Pod to `target`.
+    ///
+    /// A failure to create the replacement or to start it is reported in
+    /// [`MigrationReport::outcome`] after rolling back, and failures to roll
+    /// back or to terminate the source Pod in
+    /// [`MigrationReport::cleanup_errors`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source Pod or its network volume cannot be
+    /// fetched or stopped, before anything has been created.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, target)))]
+    pub async fn run(&self, pod_id: &str, target: MigrationTarget) -> Result<MigrationReport> {
+        let started_at = Utc::now();
+        let pod = self.client.get_pod(pod_id, GetPodQuery::default()).await?;
+        let mut input = target.replacement(&pod);
+
+        let source_volume = match (&pod.network_volume_id, &target.network_volume) {
+            (Some(volume_id), VolumeMigration::Create) => {
+                Some(self.client.get_volume(volume_id).await?)
+            }
+            _ => None,
+        };
+
+        let was_running = pod.desired_status == PodStatus::Running;
+        if was_running {
+            self.client.stop_pod(pod_id).await?;
+        }
+
+        let mut report = MigrationReport {
+            source_pod_id: pod.id.clone(),
+            pod_id: None,
+            source_volume_id: pod.network_volume_id.clone(),
+            volume_id: None,
+            volume_created: false,
+            source_terminated: false,
+            started_at,
+            finished_at: started_at,
+            outcome: Ok(()),
+            cleanup_errors: Vec::new(),
+        };
+
+        report.volume_id = match &target.network_volume {
+            VolumeMigration::Detach => None,
+            VolumeMigration::Attach(volume_id) => Some(volume_id.clone()),
+            VolumeMigration::Create => match source_volume {
+                Some(volume) if volume.data_center_id == api_value(&target.data_center_id) => {
+                    Some(volume.id)
+                }
+                Some(volume) => {
+                    let volume = NetworkVolumeCreateInput {
+                        name: volume.name,
+                        size: u32::try_from(volume.size).unwrap_or_default(),
+                        data_center_id: api_value(&target.data_center_id),
+                    };
+                    match self.client.create_volume(volume).await {
+                        Ok(volume) => {
+                            report.volume_created = true;
+                            Some(volume.id)
+                        }
+                        Err(e) => {
+                            return self
+                                .rollback(report, was_running, MigrationFailure::Request(e))
+                                .await;
+                        }
+                    }
+                }
+                None => None,
+            },
+        };
+        input.network_volume_id = report.volume_id.clone();
+
+        let replacement = match self.client.create_pod(input).await {
+            Ok(replacement) => replacement,
+            Err(e) => {
+                return self
+                    .rollback(report, was_running, MigrationFailure::Request(e))
+                    .await;
+            }
+        };
+        report.pod_id = Some(replacement.id.clone());
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(replacement_id = %replacement.id, "Created replacement Pod");
+
+        if let Err(failure) = self.wait_started(&replacement.id).await {
+            return self.rollback(report, was_running, failure).await;
+        }
+
+        if self.policy.terminate_source {
+            match self.client.delete_pod(pod_id).await {
+                Ok(()) => report.source_terminated = true,
+                Err(e) => report.cleanup_errors.push(e),
+            }
+        }
+        report.finished_at = Utc::now();
+        Ok(report)
+    }
+
+    /// Polls the replacement Pod until it has started.
+    async fn wait_started(&self, pod_id: &str) -> Result<(), MigrationFailure> {
+        let deadline = tokio::time::Instant::now() + self.policy.start_timeout;
+        loop {
+            let pod = self
+                .client
+                .get_pod(pod_id, GetPodQuery::default())
+                .await
+                .map_err(MigrationFailure::Request)?;
+            match pod.desired_status {
+                PodStatus::Running if pod.last_started_at.is_some() => return Ok(()),
+                PodStatus::Running => {}
+                status => return Err(MigrationFailure::Stopped(status)),
+            }
+
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                return Err(MigrationFailure::Timeout);
+            }
+            tokio::time::sleep(self.policy.poll_interval.min(remaining)).await;
+        }
+    }
+
+    /// Deletes what the migration created and restarts the source Pod.
+    ///
+    /// Every step is attempted even if an earlier one fails.
+    async fn rollback(
+        &self,
+        mut report: MigrationReport,
+        was_running: bool,
+        failure: MigrationFailure,
+    ) -> Result<MigrationReport> {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(%failure, "Rolling back Pod migration");
+
+        if let Some(pod_id) = &report.pod_id
+            && let Err(e) = self.client.delete_pod(pod_id).await
+        {
+            report.cleanup_errors.push(e);
+        }
+        if report.volume_created
+            && let Some(volume_id) = &report.volume_id
+            && let Err(e) = self.client.delete_volume(volume_id).await
+        {
+            report.cleanup_errors.push(e);
+        }
+        if was_running && let Err(e) = self.client.start_pod(&report.source_pod_id).await {
+            report.cleanup_errors.push(e);
+        }
+
+        report.finished_at = Utc::now();
+        report.outcome = Err(failure);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{self, Cloud};
+
+    fn source() -> Pod {
+        testing::pod(json!({
+            "id": "source",
+            "name": "trainer",
+            "image": "org/trainer:v1",
+            "gpuCount": 1,
+            "ports": ["8888/http"],
+            "env": {"MODEL": "small"},
+            "gpuTypeId": "NVIDIA A40",
+            "networkVolumeId": "volume",
+            "lastStartedAt": testing::STARTED_AT,
+            "machine": {
+                "location": "United States",
+                "dataCenterId": "US-IL-1",
+                "supportPublicIp": true,
+                "secureCloud": true,
+                "costPerHr": 0.4,
+            },
+        }))
+    }
+
+    fn migration(cloud: Cloud) -> PodMigration<Cloud> {
+        let cloud = cloud.with_pod(source()).with_volume(testing::volume(json!({
+            "name": "datasets",
+            "dataCenterId": "US-IL-1",
+        })));
+        PodMigration::new(cloud).with_policy(MigrationPolicy {
+            start_timeout: Duration::from_millis(50),
+            poll_interval: Duration::from_millis(1),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_migrates_pod_and_volume() {
+        let migration = migration(Cloud::new());
+        let target = MigrationTarget::new(DataCenterId::EuRo1);
+        let report = migration.run("source", target).await.unwrap();
+
+        assert!(report.is_success());
+        assert_eq!(report.pod_id.as_deref(), Some("pod-2"));
+        assert_eq!(report.volume_id.as_deref(), Some("volume-1"));
+        assert!(report.volume_created && report.source_terminated);
+        assert_eq!(
+            migration.client.calls(),
+            vec![
+                "stop_pod source",
+                "create_volume volume-1",
+                "create_pod pod-2",
+                "delete_pod source",
+            ]
+        );
+
+        let replacement = migration.client.pod("pod-2").unwrap();
+        assert_eq!(replacement.machine.unwrap().data_center_id, "EU-RO-1");
+        assert_eq!(replacement.network_volume_id.as_deref(), Some("volume-1"));
+        let volume = migration.client.volume("volume-1").unwrap();
+        assert_eq!((volume.name.as_str(), volume.size), ("datasets", 100));
+        assert_eq!(volume.data_center_id, "EU-RO-1");
+    }
+
+    #[tokio::test]
+    async fn test_rolls_back_when_replacement_stops() {
+        let migration = migration(Cloud::new().with_created_pod_status(PodStatus::Exited));
+        let target = MigrationTarget::new(DataCenterId::EuRo1)
+            .with_gpu_types(vec![GpuTypeId::NvidiaRtxA6000]);
+        let report = migration.run("source", target).await.unwrap();
+
+        assert!(matches!(
+            report.outcome,
+            Err(MigrationFailure::Stopped(PodStatus::Exited))
+        ));
+        assert!(!report.source_terminated);
+        assert_eq!(
+            migration.client.calls(),
+            vec![
+                "stop_pod source",
+                "create_volume volume-1",
+                "create_pod pod-2",
+                "delete_pod pod-2",
+                "delete_volume volume-1",
+                "start_pod source",
+            ]
+        );
+        let source = migration.client.pod("source").unwrap();
+        assert_eq!(source.desired_status, PodStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_reports_failed_cleanup() {
+        let cloud = Cloud::new()
+            .with_created_pod_status(PodStatus::Exited)
+            .failing("delete_pod pod-2", 0);
+        let rolling_back = migration(cloud);
+        let target = MigrationTarget::new(DataCenterId::EuRo1);
+        let report = rolling_back.run("source", target).await.unwrap();
+
+        assert!(report.outcome.is_err());
+        assert_eq!(report.cleanup_errors.len(), 1);
+        assert_eq!(report.pod_id.as_deref(), Some("pod-2"));
+        assert_eq!(
+            rolling_back.client.calls()[3..],
+            [
+                "delete_pod pod-2",
+                "delete_volume volume-1",
+                "start_pod source"
+            ]
+        );
+
+        let terminating = migration(Cloud::new().failing("delete_pod source", 0));
+        let target = MigrationTarget::new(DataCenterId::EuRo1);
+        let report = terminating.run("source", target).await.unwrap();
+
+        assert!(report.outcome.is_ok() && !report.is_success());
+        assert!(!report.source_terminated);
+        assert_eq!(report.pod_id.as_deref(), Some("pod-2"));
+    }
+
+    #[test]
+    fn test_replacement_targets_data_center() {
+        let target = MigrationTarget::new(DataCenterId::EuRo1)
+            .with_gpu_types(vec![GpuTypeId::NvidiaRtxA6000])
+            .with_cloud_type(CloudType::Community);
+        let input = target.replacement(&source());
+
+        assert_eq!(input.data_center_ids, Some(vec![DataCenterId::EuRo1]));
+        assert_eq!(input.gpu_type_ids, Some(vec![GpuTypeId::NvidiaRtxA6000]));
+        assert_eq!(input.cloud_type, Some(CloudType::Community));
+        assert_eq!(input.image_name.as_deref(), Some("org/trainer:v1"));
+    }
+}
@@ -410,3 +410,16 @@ pub type EnvVars = HashMap<String, String>;
 /// Maps internal container ports (as strings) to external public ports
 /// (as integers) for network access to the Pod.
 pub type PortMappings = HashMap<String, i32>;
+
+/// Parses an API string, e.g. `NVIDIA A40` or `EU-RO-1`, into its enum.
+pub(crate) fn parse_api_value<T: serde::de::DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).ok()
+}
+
+/// Returns the API string of an enum, e.g. `NVIDIA A40` or `EU-RO-1`.
+pub(crate) fn api_value<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
@@ -0,0 +1,41 @@
+//! Field comparisons shared by the `diff` constructors of update inputs.
+
+use std::collections::HashSet;
+
+pub(super) fn changed<T: PartialEq + Clone>(desired: &Option<T>, actual: &T) -> Option<T> {
+    desired.as_ref().filter(|value| *value != actual).cloned()
+}
+
+pub(super) fn changed_opt<T: PartialEq + Clone>(
+    desired: &Option<T>,
+    actual: &Option<T>,
+) -> Option<T> {
+    desired
+        .as_ref()
+        .filter(|value| Some(*value) != actual.as_ref())
+        .cloned()
+}
+
+/// Like [`changed_opt`], but treats a missing list and an empty list as equal.
+pub(super) fn changed_list(
+    desired: &Option<Vec<String>>,
+    actual: &Option<Vec<String>>,
+) -> Option<Vec<String>> {
+    let actual = actual.as_deref().unwrap_or_default();
+    desired
+        .as_ref()
+        .filter(|value| value.as_slice() != actual)
+        .cloned()
+}
+
+/// Like [`changed`], but ignores ordering.
+pub(super) fn changed_set(desired: &Option<Vec<String>>, actual: &[String]) -> Option<Vec<String>> {
+    desired
+        .as_ref()
+        .filter(|value| {
+            let lhs: HashSet<_> = value.iter().collect();
+            let rhs: HashSet<_> = actual.iter().collect();
+            lhs != rhs
+        })
+        .cloned()
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
 use super::common::*;
+use super::diff::{changed, changed_opt};
 use super::pod::Pod;
 use super::template::Template;
 
@@ -523,6 +524,53 @@ pub struct EndpointCreateInput {
     pub workers_min: Option<i32>,
 }
 
+impl EndpointCreateInput {
+    /// Returns an input creating a copy of `endpoint`.
+    ///
+    /// Scaling, timeouts, GPU types, CUDA versions, data centers and the
+    /// network volume are copied. CPU flavors and vCPU counts are derived from
+    /// the endpoint's instance IDs, e.g. `cpu3c-4-8`. Flash boot is not
+    /// returned by the API and is left unset.
+    pub fn from_endpoint(endpoint: &Endpoint) -> Self {
+        let instances: Vec<(&str, Option<i32>)> = endpoint
+            .instance_ids
+            .iter()
+            .flatten()
+            .map(|id| {
+                let mut parts = id.split('-');
+                let flavor = parts.next().unwrap_or_default();
+                (flavor, parts.next().and_then(|vcpus| vcpus.parse().ok()))
+            })
+            .collect();
+        let mut cpu_flavor_ids: Vec<CpuFlavorId> = Vec::new();
+        for flavor in instances.iter().filter_map(|(f, _)| parse_api_value(f)) {
+            if !cpu_flavor_ids.contains(&flavor) {
+                cpu_flavor_ids.push(flavor);
+            }
+        }
+
+        Self {
+            template_id: endpoint.template_id.clone(),
+            allowed_cuda_versions: endpoint.allowed_cuda_versions.clone(),
+            compute_type: Some(endpoint.compute_type),
+            cpu_flavor_ids: (!cpu_flavor_ids.is_empty()).then_some(cpu_flavor_ids),
+            data_center_ids: Some(endpoint.data_center_ids.clone()),
+            execution_timeout_ms: Some(endpoint.execution_timeout_ms),
+            flashboot: None,
+            gpu_count: endpoint.gpu_count,
+            gpu_type_ids: endpoint.gpu_type_ids.clone(),
+            idle_timeout: Some(endpoint.idle_timeout),
+            name: endpoint.name.clone(),
+            network_volume_id: endpoint.network_volume_id.clone(),
+            scaler_type: Some(endpoint.scaler_type),
+            scaler_value: Some(endpoint.scaler_value),
+            vcpu_count: instances.iter().find_map(|(_, vcpus)| *vcpus),
+            workers_max: Some(endpoint.workers_max),
+            workers_min: Some(endpoint.workers_min),
+        }
+    }
+}
+
 /// Input parameters for updating an existing serverless endpoint.
 ///
 /// This struct allows you to modify endpoint configuration and trigger a rolling
@@ -745,6 +793,59 @@ pub struct EndpointUpdateInput {
     pub workers_min: Option<i32>,
 }
 
+impl EndpointUpdateInput {
+    /// Returns an input updating `endpoint` to match `desired`, or `None` if it
+    /// already matches.
+    ///
+    /// Only fields that are both updatable and reported on [`Endpoint`] are
+    /// compared. Fields left as `None` in `desired` are treated as "don't care".
+    pub fn diff(desired: &EndpointCreateInput, endpoint: &Endpoint) -> Option<Self> {
+        let template_id = (desired.template_id != endpoint.template_id)
+            .then(|| desired.template_id.clone())
+            .filter(|id| !id.is_empty());
+
+        let update = Self {
+            allowed_cuda_versions: changed_opt(
+                &desired.allowed_cuda_versions,
+                &endpoint.allowed_cuda_versions,
+            ),
+            cpu_flavor_ids: None,
+            data_center_ids: changed(&desired.data_center_ids, &endpoint.data_center_ids),
+            execution_timeout_ms: changed(
+                &desired.execution_timeout_ms,
+                &endpoint.execution_timeout_ms,
+            ),
+            flashboot: None,
+            gpu_count: changed_opt(&desired.gpu_count, &endpoint.gpu_count),
+            gpu_type_ids: changed_opt(&desired.gpu_type_ids, &endpoint.gpu_type_ids),
+            idle_timeout: changed(&desired.idle_timeout, &endpoint.idle_timeout),
+            name: None,
+            network_volume_id: changed_opt(&desired.network_volume_id, &endpoint.network_volume_id),
+            scaler_type: changed(&desired.scaler_type, &endpoint.scaler_type),
+            scaler_value: changed(&desired.scaler_value, &endpoint.scaler_value),
+            template_id,
+            vcpu_count: None,
+            workers_max: changed(&desired.workers_max, &endpoint.workers_max),
+            workers_min: changed(&desired.workers_min, &endpoint.workers_min),
+        };
+
+        let is_noop = update.allowed_cuda_versions.is_none()
+            && update.data_center_ids.is_none()
+            && update.execution_timeout_ms.is_none()
+            && update.gpu_count.is_none()
+            && update.gpu_type_ids.is_none()
+            && update.idle_timeout.is_none()
+            && update.network_volume_id.is_none()
+            && update.scaler_type.is_none()
+            && update.scaler_value.is_none()
+            && update.template_id.is_none()
+            && update.workers_max.is_none()
+            && update.workers_min.is_none();
+
+        (!is_noop).then_some(update)
+    }
+}
+
 /// Query parameters for listing serverless endpoints.
 ///
 /// Controls which additional data is included in the response when retrieving
@@ -852,3 +953,43 @@ pub struct GetEndpointQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_workers: Option<bool>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_endpoint_create_input_round_trips() {
+        let endpoint = testing::endpoint(json!({
+            "name": "app",
+            "version": 3,
+            "computeType": "CPU",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "dataCenterIds": ["EU-RO-1"],
+            "instanceIds": ["cpu3c-4-8", "cpu3c-8-16", "cpu5g-4-16"],
+            "networkVolumeId": "volume",
+            "workersMin": 1,
+        }));
+
+        let input = EndpointCreateInput::from_endpoint(&endpoint);
+        assert_eq!(
+            input.cpu_flavor_ids,
+            Some(vec![CpuFlavorId::Cpu3c, CpuFlavorId::Cpu5g])
+        );
+        assert_eq!(input.vcpu_count, Some(4));
+
+        let input = serde_json::to_value(input).unwrap();
+        let endpoint = serde_json::to_value(&endpoint).unwrap();
+        let Value::Object(fields) = input else {
+            panic!("input is not an object");
+        };
+        for (field, value) in fields {
+            if field != "cpuFlavorIds" && field != "vcpuCount" {
+                assert_eq!(endpoint[&field], value, "{field}");
+            }
+        }
+    }
+}
@@ -4,9 +4,13 @@
 
 mod billing;
 mod common;
+mod diff;
 mod endpoint;
 mod pod;
 mod registry;
+#[cfg(feature = "graphql")]
+mod runtime;
+mod serverless;
 mod template;
 mod volume;
 
@@ -15,5 +19,9 @@ pub use common::*;
 pub use endpoint::*;
 pub use pod::*;
 pub use registry::*;
+#[cfg(feature = "graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+pub use runtime::*;
+pub use serverless::*;
 pub use template::*;
 pub use volume::*;
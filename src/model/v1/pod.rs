@@ -1,6 +1,37 @@
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "strum")]
+use strum::{Display, EnumString};
 
 use super::common::*;
+use super::diff::{changed, changed_list, changed_opt, changed_set};
+
+/// Rental priority strategy for GPU types, CPU flavors, and data centers.
+///
+/// Controls how RunPod picks between the candidates listed in `gpu_type_ids`,
+/// `cpu_flavor_ids`, or `data_center_ids` when creating a Pod.
+///
+/// # Examples
+///
+/// ```rust
+/// use runpod_sdk::model::v1::{PodCreateInput, RentalPriority};
+///
+/// let input = PodCreateInput {
+///     gpu_type_priority: Some(RentalPriority::Custom),
+///     data_center_priority: Some(RentalPriority::Availability),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "strum", derive(Display, EnumString))]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "strum", strum(serialize_all = "lowercase"))]
+pub enum RentalPriority {
+    /// Respond to current availability, renting whichever candidate is free.
+    #[default]
+    Availability,
+    /// Always try to rent candidates in the order they were specified.
+    Custom,
+}
 
 /// A Pod resource representing a containerized compute instance on RunPod.
 ///
@@ -165,19 +196,19 @@ pub struct PodCreateInput {
     /// can be attached to the Pod. The order determines the rental priority.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_flavor_ids: Option<Vec<CpuFlavorId>>,
-    /// If the created Pod is a CPU Pod, set to `availability` to respond to
-    /// current CPU flavor availability. Set to `custom` to always try to rent
-    /// CPU flavors in the order specified in `cpu_flavor_ids`.
+    /// If the created Pod is a CPU Pod, set to [`RentalPriority::Availability`] to
+    /// respond to current CPU flavor availability. Set to [`RentalPriority::Custom`]
+    /// to always try to rent CPU flavors in the order specified in `cpu_flavor_ids`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub cpu_flavor_priority: Option<String>,
+    pub cpu_flavor_priority: Option<RentalPriority>,
     /// A list of RunPod data center IDs where the created Pod can be located.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data_center_ids: Option<Vec<DataCenterId>>,
-    /// Set to `availability` to respond to current machine availability.
-    /// Set to `custom` to always try to rent machines from data centers
-    /// in the order specified in `data_center_ids`.
+    /// Set to [`RentalPriority::Availability`] to respond to current machine
+    /// availability. Set to [`RentalPriority::Custom`] to always try to rent
+    /// machines from data centers in the order specified in `data_center_ids`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub data_center_priority: Option<String>,
+    pub data_center_priority: Option<RentalPriority>,
     /// If specified, overrides the ENTRYPOINT for the Docker image.
     /// If empty, uses the ENTRYPOINT defined in the image.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -200,11 +231,11 @@ pub struct PodCreateInput {
     /// can be attached to the Pod. The order determines the rental priority.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gpu_type_ids: Option<Vec<GpuTypeId>>,
-    /// If the created Pod is a GPU Pod, set to `availability` to respond to
-    /// current GPU type availability. Set to `custom` to always try to rent
-    /// GPU types in the order specified in `gpu_type_ids`.
+    /// If the created Pod is a GPU Pod, set to [`RentalPriority::Availability`] to
+    /// respond to current GPU type availability. Set to [`RentalPriority::Custom`]
+    /// to always try to rent GPU types in the order specified in `gpu_type_ids`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub gpu_type_priority: Option<String>,
+    pub gpu_type_priority: Option<RentalPriority>,
     /// The image tag for the container run on the created Pod.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image_name: Option<String>,
@@ -264,6 +295,68 @@ pub struct PodCreateInput {
     pub volume_mount_path: Option<String>,
 }
 
+impl PodCreateInput {
+    /// Returns an input creating a copy of `pod`.
+    ///
+    /// The image, environment, ports, volumes, network volume, registry auth,
+    /// template, and GPU or CPU type are copied. The cloud type and data
+    /// center are taken from the machine the Pod runs on, when known, so the
+    /// copy can attach the same network volume.
+    pub fn from_pod(pod: &Pod) -> Self {
+        let gpu_type_id = pod
+            .gpu_type_id
+            .as_deref()
+            .or(pod.gpu.as_ref().map(|gpu| gpu.id.as_str()))
+            .or(pod.machine.as_ref().and_then(|m| m.gpu_type_id.as_deref()));
+        let is_gpu = pod.gpu_count.is_some_and(|count| count > 0) || gpu_type_id.is_some();
+        let machine = pod.machine.as_ref();
+
+        Self {
+            cloud_type: machine.map(|m| {
+                if m.secure_cloud {
+                    CloudType::Secure
+                } else {
+                    CloudType::Community
+                }
+            }),
+            compute_type: Some(if is_gpu {
+                ComputeType::Gpu
+            } else {
+                ComputeType::Cpu
+            }),
+            container_disk_in_gb: Some(pod.container_disk_in_gb),
+            container_registry_auth_id: pod.container_registry_auth_id.clone(),
+            cpu_flavor_ids: pod
+                .cpu_flavor_id
+                .as_deref()
+                .and_then(parse_api_value)
+                .map(|flavor| vec![flavor]),
+            data_center_ids: machine
+                .and_then(|m| parse_api_value(&m.data_center_id))
+                .map(|data_center| vec![data_center]),
+            docker_entrypoint: pod.docker_entrypoint.clone(),
+            docker_start_cmd: pod.docker_start_cmd.clone(),
+            env: Some(pod.env.clone()),
+            gpu_count: if is_gpu { pod.gpu_count } else { None },
+            gpu_type_ids: gpu_type_id
+                .and_then(parse_api_value)
+                .map(|gpu_type| vec![gpu_type]),
+            image_name: Some(pod.image.clone()),
+            interruptible: Some(pod.interruptible),
+            locked: Some(pod.locked),
+            name: pod.name.clone(),
+            network_volume_id: pod.network_volume_id.clone(),
+            ports: Some(pod.ports.clone()),
+            support_public_ip: pod.public_ip.is_some().then_some(true),
+            template_id: pod.template_id.clone(),
+            vcpu_count: (!is_gpu).then_some(pod.vcpu_count as i32),
+            volume_in_gb: pod.volume_in_gb,
+            volume_mount_path: pod.volume_mount_path.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 /// Input parameters for updating an existing Pod.
 ///
 /// This struct contains the configuration options that can be modified for
@@ -327,6 +420,46 @@ pub struct PodUpdateInput {
     pub volume_mount_path: Option<String>,
 }
 
+impl PodUpdateInput {
+    /// Returns an input updating `pod` to match `desired`, or `None` if it
+    /// already matches.
+    ///
+    /// Only fields that can be changed through an update are compared. Fields
+    /// left as `None` in `desired` are treated as "don't care".
+    pub fn diff(desired: &PodCreateInput, pod: &Pod) -> Option<Self> {
+        let update = Self {
+            container_disk_in_gb: changed(&desired.container_disk_in_gb, &pod.container_disk_in_gb),
+            container_registry_auth_id: changed_opt(
+                &desired.container_registry_auth_id,
+                &pod.container_registry_auth_id,
+            ),
+            docker_entrypoint: changed_list(&desired.docker_entrypoint, &pod.docker_entrypoint),
+            docker_start_cmd: changed_list(&desired.docker_start_cmd, &pod.docker_start_cmd),
+            env: changed(&desired.env, &pod.env),
+            global_networking: None,
+            image_name: changed(&desired.image_name, &pod.image),
+            locked: changed(&desired.locked, &pod.locked),
+            name: None,
+            ports: changed_set(&desired.ports, &pod.ports),
+            volume_in_gb: changed_opt(&desired.volume_in_gb, &pod.volume_in_gb),
+            volume_mount_path: changed_opt(&desired.volume_mount_path, &pod.volume_mount_path),
+        };
+
+        let is_noop = update.container_disk_in_gb.is_none()
+            && update.container_registry_auth_id.is_none()
+            && update.docker_entrypoint.is_none()
+            && update.docker_start_cmd.is_none()
+            && update.env.is_none()
+            && update.image_name.is_none()
+            && update.locked.is_none()
+            && update.ports.is_none()
+            && update.volume_in_gb.is_none()
+            && update.volume_mount_path.is_none();
+
+        (!is_noop).then_some(update)
+    }
+}
+
 /// Query parameters for filtering and configuring Pod list operations.
 ///
 /// This struct provides various filters and options for customizing the
@@ -434,3 +567,80 @@ pub struct GetPodQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_workers: Option<bool>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_pod_create_input_round_trips() {
+        let pod = testing::pod(json!({
+            "name": "app",
+            "image": "org/app:v1",
+            "gpuCount": 2,
+            "vcpuCount": 16.0,
+            "memoryInGb": 64.0,
+            "containerDiskInGb": 50,
+            "volumeInGb": 20,
+            "volumeMountPath": "/workspace",
+            "ports": ["8888/http", "22/tcp"],
+            "publicIp": "1.2.3.4",
+            "env": {"MODEL": "small"},
+            "dockerStartCmd": ["main.py"],
+            "interruptible": true,
+            "gpuTypeId": "NVIDIA A40",
+            "templateId": "template",
+            "networkVolumeId": "volume",
+            "containerRegistryAuthId": "auth",
+        }));
+
+        let input = PodCreateInput::from_pod(&pod);
+        assert_eq!(input.image_name.as_deref(), Some("org/app:v1"));
+        assert_eq!(input.compute_type, Some(ComputeType::Gpu));
+        assert_eq!(input.gpu_type_ids, Some(vec![GpuTypeId::NvidiaA40]));
+        assert_eq!(input.support_public_ip, Some(true));
+        assert_eq!(input.vcpu_count, None);
+
+        let input = serde_json::to_value(input).unwrap();
+        let pod = serde_json::to_value(&pod).unwrap();
+        let Value::Object(fields) = input else {
+            panic!("input is not an object");
+        };
+        let shared: Vec<&String> = fields.keys().filter(|f| pod.get(*f).is_some()).collect();
+        assert_eq!(shared.len(), 13);
+        for field in shared {
+            assert_eq!(pod[field], fields[field], "{field}");
+        }
+    }
+
+    #[test]
+    fn test_rental_priority_rejects_unknown_values() {
+        let priority: RentalPriority = serde_json::from_value(json!("custom")).unwrap();
+        assert_eq!(priority, RentalPriority::Custom);
+        assert!(serde_json::from_value::<RentalPriority>(json!("cusotm")).is_err());
+    }
+
+    #[test]
+    fn test_pod_create_input_from_cpu_pod() {
+        let pod = testing::pod(json!({
+            "image": "org/app:v1",
+            "desiredStatus": "EXITED",
+            "costPerHr": 0.1,
+            "adjustedCostPerHr": 0.1,
+            "vcpuCount": 4.0,
+            "memoryInGb": 8.0,
+            "containerDiskInGb": 10,
+            "cpuFlavorId": "cpu3c",
+        }));
+
+        let input = PodCreateInput::from_pod(&pod);
+        assert_eq!(input.compute_type, Some(ComputeType::Cpu));
+        assert_eq!(input.cpu_flavor_ids, Some(vec![CpuFlavorId::Cpu3c]));
+        assert_eq!(input.vcpu_count, Some(4));
+        assert_eq!(input.gpu_type_ids, None);
+        assert_eq!(input.cloud_type, None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Runtime telemetry of a running Pod or serverless worker.
+///
+/// Only available through the GraphQL API, and only while the Pod is running.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodRuntime {
+    /// The unique identifier of the Pod.
+    pub pod_id: String,
+    /// Seconds since the container started.
+    #[serde(default)]
+    pub uptime_in_seconds: Option<u64>,
+    /// Per-GPU utilization.
+    #[serde(default)]
+    pub gpus: Vec<GpuRuntime>,
+    /// Container resource utilization.
+    #[serde(default)]
+    pub container: Option<ContainerRuntime>,
+    /// Ports exposed by the Pod.
+    #[serde(default)]
+    pub ports: Vec<RuntimePort>,
+}
+
+impl PodRuntime {
+    /// Time since the container started.
+    pub fn uptime(&self) -> Option<Duration> {
+        self.uptime_in_seconds.map(Duration::from_secs)
+    }
+
+    /// Highest compute utilization of any GPU, in percent.
+    pub fn max_gpu_utilization(&self) -> Option<f64> {
+        self.gpus
+            .iter()
+            .filter_map(|gpu| gpu.gpu_util_percent)
+            .reduce(f64::max)
+    }
+
+    /// Average compute utilization across GPUs, in percent.
+    pub fn mean_gpu_utilization(&self) -> Option<f64> {
+        let utilization: Vec<f64> = self
+            .gpus
+            .iter()
+            .filter_map(|gpu| gpu.gpu_util_percent)
+            .collect();
+        if utilization.is_empty() {
+            return None;
+        }
+        Some(utilization.iter().sum::<f64>() / utilization.len() as f64)
+    }
+}
+
+/// List of Pod runtimes.
+pub type PodRuntimes = Vec<PodRuntime>;
+
+/// Utilization of a single GPU attached to a Pod.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuRuntime {
+    /// The identifier of the GPU.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// GPU compute utilization, in percent.
+    #[serde(default)]
+    pub gpu_util_percent: Option<f64>,
+    /// GPU memory utilization, in percent.
+    #[serde(default)]
+    pub memory_util_percent: Option<f64>,
+}
+
+/// Resource utilization of a Pod's container.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerRuntime {
+    /// CPU utilization, in percent.
+    #[serde(default)]
+    pub cpu_percent: Option<f64>,
+    /// Memory utilization, in percent.
+    #[serde(default)]
+    pub memory_percent: Option<f64>,
+}
+
+/// A port exposed by a running Pod.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimePort {
+    /// The IP address the port is reachable at.
+    #[serde(default)]
+    pub ip: Option<String>,
+    /// Whether the IP address is public.
+    #[serde(default)]
+    pub is_ip_public: Option<bool>,
+    /// The port inside the container.
+    #[serde(default)]
+    pub private_port: Option<u16>,
+    /// The port exposed to the network.
+    #[serde(default)]
+    pub public_port: Option<u16>,
+    /// The protocol of the port, `http` or `tcp`.
+    #[serde(default, rename = "type")]
+    pub port_type: Option<String>,
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "strum")]
+use strum::{Display, EnumString};
+
+use super::endpoint::Endpoint;
+use super::pod::Pod;
+
+/// Status of a serverless job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strum", derive(Display, EnumString))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[cfg_attr(feature = "strum", strum(serialize_all = "SCREAMING_SNAKE_CASE"))]
+pub enum JobState {
+    /// The job is waiting for a worker.
+    InQueue,
+    /// A worker is processing the job.
+    InProgress,
+    /// The job finished successfully.
+    Completed,
+    /// The job failed.
+    Failed,
+    /// The job was cancelled.
+    Cancelled,
+    /// The job exceeded its execution timeout.
+    TimedOut,
+}
+
+impl JobState {
+    /// Returns whether the job has finished and its status will not change anymore.
+    pub fn is_final(self) -> bool {
+        !matches!(self, Self::InQueue | Self::InProgress)
+    }
+}
+
+/// Execution policy of a serverless job.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobPolicy {
+    /// Maximum execution time in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_timeout: Option<u64>,
+    /// Time to live of the job in milliseconds, including queue time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+    /// Whether the job may wait for idle workers instead of scaling up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low_priority: Option<bool>,
+}
+
+/// Input for submitting a job to a serverless endpoint.
+///
+/// # Examples
+///
+/// ```rust
+/// use runpod_sdk::model::v1::RunJobInput;
+/// use serde_json::json;
+///
+/// let input = RunJobInput::new(json!({"prompt": "Hello"}));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunJobInput {
+    /// The job input passed to the handler.
+    pub input: Value,
+    /// URL called with the job result once it finishes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    /// Execution policy of the job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<JobPolicy>,
+}
+
+impl RunJobInput {
+    /// Creates a job input without webhook or policy.
+    pub fn new(input: Value) -> Self {
+        Self {
+            input,
+            ..Default::default()
+        }
+    }
+}
+
+/// Status and result of a serverless job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    /// The unique identifier of the job.
+    pub id: String,
+    /// The current status of the job.
+    pub status: JobState,
+    /// The job output, once completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+    /// The error message, if the job failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Milliseconds the job waited in the queue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_time: Option<u64>,
+    /// Milliseconds the worker spent processing the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_time: Option<u64>,
+    /// The worker that processed the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
+}
+
+/// Partial outputs of a streaming serverless job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStream {
+    /// The current status of the job.
+    pub status: JobState,
+    /// Partial outputs produced since the last read.
+    #[serde(default)]
+    pub stream: Vec<StreamOutput>,
+}
+
+/// A partial output of a streaming serverless job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamOutput {
+    /// The partial output.
+    pub output: Value,
+}
+
+/// Job and worker counts of a serverless endpoint.
+///
+/// Returned by the serverless `/health` route. Worker pods from
+/// [`Endpoint::workers`](crate::model::v1::Endpoint::workers) can be attached
+/// with [`with_workers`](Self::with_workers) for a detailed view.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointHealth {
+    /// Job counts of the endpoint.
+    pub jobs: JobCounts,
+    /// Worker counts of the endpoint.
+    pub workers: WorkerCounts,
+    /// Worker pods of the endpoint, if attached.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub worker_pods: Vec<Pod>,
+}
+
+impl EndpointHealth {
+    /// Attaches the worker pods of `endpoint`.
+    ///
+    /// The endpoint must be fetched with `include_workers` set, otherwise no
+    /// pods are attached.
+    pub fn with_workers(mut self, endpoint: &Endpoint) -> Self {
+        self.worker_pods = endpoint.workers.clone().unwrap_or_default();
+        self
+    }
+
+    /// Returns whether no worker is unhealthy.
+    pub fn is_healthy(&self) -> bool {
+        self.workers.unhealthy == 0
+    }
+
+    /// Returns whether jobs are queued while no worker is ready to take them.
+    pub fn is_stalled(&self) -> bool {
+        self.jobs.in_queue > 0 && self.workers.idle == 0 && self.workers.running == 0
+    }
+}
+
+/// Job counts of a serverless endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobCounts {
+    /// Jobs that finished successfully.
+    #[serde(default)]
+    pub completed: u64,
+    /// Jobs that failed.
+    #[serde(default)]
+    pub failed: u64,
+    /// Jobs being processed by a worker.
+    #[serde(default)]
+    pub in_progress: u64,
+    /// Jobs waiting for a worker.
+    #[serde(default)]
+    pub in_queue: u64,
+    /// Jobs that were retried after a worker failure.
+    #[serde(default)]
+    pub retried: u64,
+}
+
+impl JobCounts {
+    /// Returns the number of jobs queued or in progress.
+    pub fn pending(&self) -> u64 {
+        self.in_queue + self.in_progress
+    }
+}
+
+/// Worker counts of a serverless endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerCounts {
+    /// Workers waiting for jobs.
+    #[serde(default)]
+    pub idle: u64,
+    /// Workers processing jobs.
+    #[serde(default)]
+    pub running: u64,
+    /// Workers starting up.
+    #[serde(default)]
+    pub initializing: u64,
+    /// Workers ready to take jobs.
+    #[serde(default)]
+    pub ready: u64,
+    /// Workers waiting for capacity on their machine.
+    #[serde(default)]
+    pub throttled: u64,
+    /// Workers that failed health checks.
+    #[serde(default)]
+    pub unhealthy: u64,
+}
+
+impl WorkerCounts {
+    /// Returns the number of idle and running workers.
+    pub fn active(&self) -> u64 {
+        self.idle + self.running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserializes_endpoint_health() {
+        let health: EndpointHealth = serde_json::from_value(json!({
+            "jobs": {"completed": 12, "failed": 1, "inProgress": 2, "inQueue": 3, "retried": 0},
+            "workers": {"idle": 0, "initializing": 1, "ready": 0, "running": 0, "throttled": 2, "unhealthy": 0}
+        }))
+        .unwrap();
+
+        assert_eq!(health.jobs.pending(), 5);
+        assert_eq!(health.workers.throttled, 2);
+        assert_eq!(health.workers.active(), 0);
+        assert!(health.is_healthy());
+        assert!(health.is_stalled());
+        assert!(health.worker_pods.is_empty());
+    }
+}
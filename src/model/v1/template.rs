@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::common::*;
+use super::diff::{changed, changed_list, changed_opt, changed_set};
 
 /// Compute category classification for templates.
 ///
@@ -272,6 +273,32 @@ pub struct TemplateCreateInput {
     pub volume_mount_path: Option<String>,
 }
 
+impl TemplateCreateInput {
+    /// Returns an input creating a copy of `template`.
+    ///
+    /// Every configurable field is copied, including environment variables
+    /// and the registry auth. Change the name before creating the copy, as
+    /// template names must be unique.
+    pub fn from_template(template: &Template) -> Self {
+        Self {
+            name: template.name.clone(),
+            image_name: template.image_name.clone(),
+            category: template.category.clone(),
+            container_disk_in_gb: Some(template.container_disk_in_gb),
+            container_registry_auth_id: template.container_registry_auth_id.clone(),
+            docker_entrypoint: template.docker_entrypoint.clone(),
+            docker_start_cmd: template.docker_start_cmd.clone(),
+            env: template.env.clone(),
+            is_public: Some(template.is_public),
+            is_serverless: Some(template.is_serverless),
+            ports: template.ports.clone(),
+            readme: template.readme.clone(),
+            volume_in_gb: Some(template.volume_in_gb),
+            volume_mount_path: Some(template.volume_mount_path.clone()),
+        }
+    }
+}
+
 /// Input parameters for updating existing templates.
 ///
 /// Use this struct to modify template configuration. Template updates automatically
@@ -384,6 +411,57 @@ pub struct TemplateUpdateInput {
     pub volume_mount_path: Option<String>,
 }
 
+impl TemplateUpdateInput {
+    /// Returns an input updating `template` to match `desired`, or `None` if it
+    /// already matches.
+    ///
+    /// Fields left as `None` in `desired` are treated as "don't care". Ports
+    /// are compared regardless of their order.
+    pub fn diff(desired: &TemplateCreateInput, template: &Template) -> Option<Self> {
+        let update = Self {
+            container_disk_in_gb: changed(
+                &desired.container_disk_in_gb,
+                &template.container_disk_in_gb,
+            ),
+            container_registry_auth_id: changed_opt(
+                &desired.container_registry_auth_id,
+                &template.container_registry_auth_id,
+            ),
+            docker_entrypoint: changed_list(
+                &desired.docker_entrypoint,
+                &template.docker_entrypoint,
+            ),
+            docker_start_cmd: changed_list(&desired.docker_start_cmd, &template.docker_start_cmd),
+            env: changed_opt(&desired.env, &template.env),
+            image_name: (desired.image_name != template.image_name)
+                .then(|| desired.image_name.clone()),
+            is_public: changed(&desired.is_public, &template.is_public),
+            name: None,
+            ports: changed_set(
+                &desired.ports,
+                template.ports.as_deref().unwrap_or_default(),
+            ),
+            readme: changed_opt(&desired.readme, &template.readme),
+            volume_in_gb: changed(&desired.volume_in_gb, &template.volume_in_gb),
+            volume_mount_path: changed(&desired.volume_mount_path, &template.volume_mount_path),
+        };
+
+        let is_noop = update.container_disk_in_gb.is_none()
+            && update.container_registry_auth_id.is_none()
+            && update.docker_entrypoint.is_none()
+            && update.docker_start_cmd.is_none()
+            && update.env.is_none()
+            && update.image_name.is_none()
+            && update.is_public.is_none()
+            && update.ports.is_none()
+            && update.readme.is_none()
+            && update.volume_in_gb.is_none()
+            && update.volume_mount_path.is_none();
+
+        (!is_noop).then_some(update)
+    }
+}
+
 /// Query parameters for listing templates with filtering options.
 ///
 /// Control which templates are included in the response based on their
@@ -483,3 +561,50 @@ pub struct GetTemplateQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_runpod_templates: Option<bool>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_template_create_input_round_trips() {
+        let template = testing::template(json!({
+            "category": "NVIDIA",
+            "containerRegistryAuthId": "auth",
+            "dockerEntrypoint": ["python"],
+            "dockerStartCmd": ["main.py"],
+            "env": {"MODEL": "small"},
+            "ports": ["8888/http"],
+            "readme": "docs",
+            "volumeInGb": 10,
+        }));
+
+        let input = serde_json::to_value(TemplateCreateInput::from_template(&template)).unwrap();
+        let template = serde_json::to_value(&template).unwrap();
+        let Value::Object(fields) = input else {
+            panic!("input is not an object");
+        };
+        assert_eq!(fields.len(), 14);
+        for (field, value) in fields {
+            assert_eq!(template[&field], value, "{field}");
+        }
+    }
+
+    #[test]
+    fn test_template_update_input_diff() {
+        let template = testing::template(json!({"ports": ["8888/http", "22/tcp"]}));
+        let mut desired = TemplateCreateInput::from_template(&template);
+        assert!(TemplateUpdateInput::diff(&desired, &template).is_none());
+
+        desired.image_name = "org/app:v2".to_string();
+        desired.ports = Some(vec!["22/tcp".to_string(), "8888/http".to_string()]);
+        desired.readme = None;
+        let update = TemplateUpdateInput::diff(&desired, &template).unwrap();
+        assert_eq!(update.image_name.as_deref(), Some("org/app:v2"));
+        assert_eq!(update.ports, None);
+        assert_eq!(update.readme, None);
+    }
+}
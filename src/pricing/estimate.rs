@@ -0,0 +1,401 @@
+use serde::{Deserialize, Serialize};
+
+use super::{PriceCatalog, gpu_type_key};
+use crate::model::v1::{
+    CloudType, ComputeType, EndpointCreateInput, GpuTypeId, NetworkVolume, PodCreateInput,
+};
+
+/// Hours in an average month, used for monthly projections.
+pub const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Container disk size the API uses when none is requested, in gigabytes.
+const DEFAULT_CONTAINER_DISK_GB: i32 = 50;
+
+/// Volume size the API uses when none is requested, in gigabytes.
+const DEFAULT_VOLUME_GB: i32 = 20;
+
+/// Storage prices per gigabyte and month, in USD.
+///
+/// Defaults to the published RunPod rates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoragePrices {
+    /// Container disk of a running Pod.
+    pub container_disk: f64,
+
+    /// Volume disk of a running Pod.
+    pub volume: f64,
+
+    /// Volume disk of a stopped Pod.
+    pub stopped_volume: f64,
+
+    /// Network volume.
+    pub network_volume: f64,
+}
+
+impl Default for StoragePrices {
+    fn default() -> Self {
+        Self {
+            container_disk: 0.10,
+            volume: 0.10,
+            stopped_volume: 0.20,
+            network_volume: 0.07,
+        }
+    }
+}
+
+/// Lowest and highest cost of a set of options, in USD.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostRange {
+    /// Lowest cost.
+    pub min: f64,
+
+    /// Highest cost.
+    pub max: f64,
+}
+
+impl CostRange {
+    /// Returns the range of `costs`, or `None` if empty.
+    pub fn of(costs: impl IntoIterator<Item = f64>) -> Option<Self> {
+        costs.into_iter().fold(None, |range, cost| {
+            Some(match range {
+                None => Self {
+                    min: cost,
+                    max: cost,
+                },
+                Some(Self { min, max }) => Self {
+                    min: min.min(cost),
+                    max: max.max(cost),
+                },
+            })
+        })
+    }
+
+    /// Multiplies both bounds by `factor`.
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            min: self.min * factor,
+            max: self.max * factor,
+        }
+    }
+
+    /// Adds `cost` to both bounds.
+    pub fn offset(self, cost: f64) -> Self {
+        Self {
+            min: self.min + cost,
+            max: self.max + cost,
+        }
+    }
+}
+
+/// Hourly compute cost of one GPU type in one cloud.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuOption {
+    /// GPU type ID, e.g. `"NVIDIA A40"`.
+    pub gpu_type_id: String,
+
+    /// Cloud the price applies to.
+    pub cloud_type: CloudType,
+
+    /// Whether this is a spot (interruptible) price.
+    pub interruptible: bool,
+
+    /// Hourly cost for all GPUs of the Pod or worker, in USD.
+    pub hourly: f64,
+}
+
+/// Estimated cost of a Pod before it is created.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodEstimate {
+    /// Priced GPU options, sorted by hourly cost.
+    pub options: Vec<GpuOption>,
+
+    /// Candidate GPU types without a known price.
+    pub unpriced: Vec<String>,
+
+    /// Hourly cost of container and volume disk while running, in USD.
+    pub storage_hourly: f64,
+
+    /// Hourly cost of the volume disk while stopped, in USD.
+    pub stopped_storage_hourly: f64,
+
+    /// Monthly cost of the attached network volume, in USD.
+    pub network_volume_monthly: f64,
+}
+
+impl PodEstimate {
+    /// Range of hourly GPU cost across all priced options.
+    pub fn compute(&self) -> Option<CostRange> {
+        CostRange::of(self.options.iter().map(|option| option.hourly))
+    }
+
+    /// Range of total hourly cost while running, including storage.
+    pub fn hourly(&self) -> Option<CostRange> {
+        let storage = self.storage_hourly + self.network_volume_monthly / HOURS_PER_MONTH;
+        self.compute().map(|range| range.offset(storage))
+    }
+
+    /// Range of total monthly cost when running continuously.
+    pub fn monthly(&self) -> Option<CostRange> {
+        self.hourly().map(|range| range.scale(HOURS_PER_MONTH))
+    }
+}
+
+/// Estimated cost of a serverless endpoint before it is created.
+///
+/// Worker prices are approximated with Secure Cloud on-demand GPU prices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointEstimate {
+    /// Priced GPU options of a single worker, sorted by hourly cost.
+    pub options: Vec<GpuOption>,
+
+    /// Candidate GPU types without a known price.
+    pub unpriced: Vec<String>,
+
+    /// Minimum number of workers.
+    pub workers_min: i32,
+
+    /// Maximum number of workers, if capped.
+    pub workers_max: Option<i32>,
+
+    /// Monthly cost of the attached network volume, in USD.
+    pub network_volume_monthly: f64,
+}
+
+impl EndpointEstimate {
+    /// Range of hourly cost of a single worker.
+    pub fn worker_hourly(&self) -> Option<CostRange> {
+        CostRange::of(self.options.iter().map(|option| option.hourly))
+    }
+
+    /// Range of monthly cost with `workers_min` workers always running.
+    pub fn monthly_at_min(&self) -> Option<CostRange> {
+        self.monthly_with(self.workers_min)
+    }
+
+    /// Range of monthly cost with `workers_max` workers always running.
+    ///
+    /// Returns `None` if the endpoint has no worker cap.
+    pub fn monthly_at_max(&self) -> Option<CostRange> {
+        self.monthly_with(self.workers_max?)
+    }
+
+    fn monthly_with(&self, workers: i32) -> Option<CostRange> {
+        let workers = f64::from(workers.max(0));
+        self.worker_hourly().map(|range| {
+            range
+                .scale(workers * HOURS_PER_MONTH)
+                .offset(self.network_volume_monthly)
+        })
+    }
+}
+
+impl PriceCatalog {
+    /// Estimates the cost of a Pod created from `input`.
+    ///
+    /// Every candidate GPU type is priced in the requested cloud, or in both
+    /// clouds if none is set, on demand or as spot according to
+    /// `interruptible`, or both if unset. Without candidate GPU types, every
+    /// GPU in the catalog is a candidate. CPU Pods have no GPU options.
+    ///
+    /// Unset disk sizes use the API defaults of 50 GB container disk and
+    /// 20 GB volume.
+    pub fn estimate_pod(
+        &self,
+        input: &PodCreateInput,
+        network_volume: Option<&NetworkVolume>,
+    ) -> PodEstimate {
+        let gpu_count = input.gpu_count.unwrap_or(1);
+        let clouds = match input.cloud_type {
+            Some(cloud_type) => vec![cloud_type],
+            None => vec![CloudType::Secure, CloudType::Community],
+        };
+        let spot = match input.interruptible {
+            Some(interruptible) => vec![interruptible],
+            None => vec![false, true],
+        };
+        let (options, unpriced) = match input.compute_type {
+            Some(ComputeType::Cpu) => Default::default(),
+            _ => self.options(input.gpu_type_ids.as_deref(), &clouds, &spot, gpu_count),
+        };
+
+        let per_hour = |gb: i32, price: f64| f64::from(gb.max(0)) * price / HOURS_PER_MONTH;
+        let container_disk = input
+            .container_disk_in_gb
+            .unwrap_or(DEFAULT_CONTAINER_DISK_GB);
+        let volume = input.volume_in_gb.unwrap_or(DEFAULT_VOLUME_GB);
+
+        PodEstimate {
+            options,
+            unpriced,
+            storage_hourly: per_hour(container_disk, self.storage.container_disk)
+                + per_hour(volume, self.storage.volume),
+            stopped_storage_hourly: per_hour(volume, self.storage.stopped_volume),
+            network_volume_monthly: self.network_volume_monthly(network_volume),
+        }
+    }
+
+    /// Estimates the cost of an endpoint created from `input`.
+    ///
+    /// Without candidate GPU types, every GPU in the catalog is a candidate.
+    /// CPU endpoints have no GPU options.
+    pub fn estimate_endpoint(
+        &self,
+        input: &EndpointCreateInput,
+        network_volume: Option<&NetworkVolume>,
+    ) -> EndpointEstimate {
+        let (options, unpriced) = match input.compute_type {
+            Some(ComputeType::Cpu) => Default::default(),
+            _ => self.options(
+                input.gpu_type_ids.as_deref(),
+                &[CloudType::Secure],
+                &[false],
+                input.gpu_count.unwrap_or(1),
+            ),
+        };
+
+        EndpointEstimate {
+            options,
+            unpriced,
+            workers_min: input.workers_min.unwrap_or(0),
+            workers_max: input.workers_max,
+            network_volume_monthly: self.network_volume_monthly(network_volume),
+        }
+    }
+
+    fn network_volume_monthly(&self, network_volume: Option<&NetworkVolume>) -> f64 {
+        network_volume.map_or(0.0, |volume| {
+            f64::from(volume.size.max(0)) * self.storage.network_volume
+        })
+    }
+
+    /// Prices every combination of candidate GPU type, cloud and spot flag.
+    fn options(
+        &self,
+        gpu_type_ids: Option<&[GpuTypeId]>,
+        clouds: &[CloudType],
+        spot: &[bool],
+        gpu_count: i32,
+    ) -> (Vec<GpuOption>, Vec<String>) {
+        let candidates: Vec<String> = match gpu_type_ids.filter(|ids| !ids.is_empty()) {
+            Some(ids) => ids.iter().map(gpu_type_key).collect(),
+            None => self.gpus.keys().cloned().collect(),
+        };
+
+        let gpu_count = f64::from(gpu_count.max(0));
+        let mut options = Vec::new();
+        let mut unpriced = Vec::new();
+        for gpu_type_id in candidates {
+            let Some(price) = self.gpus.get(&gpu_type_id) else {
+                unpriced.push(gpu_type_id);
+                continue;
+            };
+            for &cloud_type in clouds {
+                for &interruptible in spot {
+                    if let Some(hourly) = price.hourly(cloud_type, interruptible) {
+                        options.push(GpuOption {
+                            gpu_type_id: gpu_type_id.clone(),
+                            cloud_type,
+                            interruptible,
+                            hourly: hourly * gpu_count,
+                        });
+                    }
+                }
+            }
+        }
+        options.sort_by(|a, b| a.hourly.total_cmp(&b.hourly));
+
+        (options, unpriced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::GpuPrice;
+
+    fn catalog() -> PriceCatalog {
+        PriceCatalog::new()
+            .with_gpu(
+                &GpuTypeId::NvidiaA40,
+                GpuPrice {
+                    secure: Some(0.4),
+                    community: Some(0.3),
+                    secure_spot: Some(0.2),
+                    community_spot: None,
+                },
+            )
+            .with_gpu(
+                &GpuTypeId::NvidiaH200,
+                GpuPrice {
+                    secure: Some(4.0),
+                    ..Default::default()
+                },
+            )
+    }
+
+    fn volume(size: i32) -> NetworkVolume {
+        NetworkVolume {
+            id: "vol".to_string(),
+            name: "vol".to_string(),
+            size,
+            data_center_id: "EU-RO-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_pod() {
+        let input = PodCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40, GpuTypeId::NvidiaL4]),
+            gpu_count: Some(2),
+            container_disk_in_gb: Some(73),
+            volume_in_gb: Some(0),
+            ..Default::default()
+        };
+        let estimate = catalog().estimate_pod(&input, Some(&volume(100)));
+
+        assert_eq!(estimate.options.len(), 3);
+        assert_eq!(estimate.unpriced, ["NVIDIA L4"]);
+        assert_eq!(estimate.compute(), Some(CostRange { min: 0.4, max: 0.8 }));
+        assert!((estimate.storage_hourly - 0.01).abs() < 1e-9);
+        assert_eq!(estimate.stopped_storage_hourly, 0.0);
+        assert!((estimate.network_volume_monthly - 7.0).abs() < 1e-9);
+
+        let monthly = estimate.monthly().unwrap();
+        assert!((monthly.max - (0.8 * HOURS_PER_MONTH + 7.3 + 7.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_endpoint() {
+        let input = EndpointCreateInput {
+            gpu_type_ids: Some(vec![GpuTypeId::NvidiaA40, GpuTypeId::NvidiaH200]),
+            workers_min: Some(1),
+            workers_max: Some(3),
+            ..Default::default()
+        };
+        let estimate = catalog().estimate_endpoint(&input, None);
+
+        assert_eq!(
+            estimate.worker_hourly(),
+            Some(CostRange { min: 0.4, max: 4.0 })
+        );
+        let at_min = estimate.monthly_at_min().unwrap();
+        assert!((at_min.max - 4.0 * HOURS_PER_MONTH).abs() < 1e-9);
+        let at_max = estimate.monthly_at_max().unwrap();
+        assert!((at_max.min - 1.2 * HOURS_PER_MONTH).abs() < 1e-9);
+
+        let uncapped = EndpointCreateInput {
+            workers_max: None,
+            ..input
+        };
+        assert_eq!(
+            catalog()
+                .estimate_endpoint(&uncapped, None)
+                .monthly_at_max(),
+            None
+        );
+    }
+}
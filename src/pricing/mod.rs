@@ -0,0 +1,203 @@
+//! GPU pricing used for cost projections.
+//!
+//! A [`PriceCatalog`] maps GPU type IDs to their hourly [`GpuPrice`] in each
+//! cloud, alongside [`StoragePrices`]. Catalogs can be built by hand, from the
+//! GPU details of existing Pods, or, with the `graphql` feature, fetched from
+//! the live GraphQL API.
+//!
+//! Catalogs estimate the cost of Pod and endpoint inputs before they are
+//! submitted, see [`PriceCatalog::estimate_pod`] and
+//! [`PriceCatalog::estimate_endpoint`].
+//!
+//! # Examples
+//!
+//! ```
+//! use runpod_sdk::model::v1::{CloudType, GpuTypeId};
+//! use runpod_sdk::pricing::{GpuPrice, PriceCatalog};
+//!
+//! let catalog = PriceCatalog::new().with_gpu(
+//!     &GpuTypeId::NvidiaA40,
+//!     GpuPrice {
+//!         secure: Some(0.40),
+//!         community: Some(0.35),
+//!         ..Default::default()
+//!     },
+//! );
+//!
+//! let price = catalog.gpu(&GpuTypeId::NvidiaA40).unwrap();
+//! assert_eq!(price.hourly(CloudType::Community, false), Some(0.35));
+//! ```
+
+mod estimate;
+
+use std::collections::BTreeMap;
+
+pub use estimate::{
+    CostRange, EndpointEstimate, GpuOption, HOURS_PER_MONTH, PodEstimate, StoragePrices,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::model::v1::{CloudType, GpuInfo, GpuTypeId, Pod, api_value};
+
+/// Hourly price of a single GPU, in USD.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuPrice {
+    /// On-demand price in Secure Cloud.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure: Option<f64>,
+
+    /// On-demand price in Community Cloud.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub community: Option<f64>,
+
+    /// Spot (interruptible) price in Secure Cloud.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure_spot: Option<f64>,
+
+    /// Spot (interruptible) price in Community Cloud.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub community_spot: Option<f64>,
+}
+
+impl GpuPrice {
+    /// Returns the hourly price in `cloud_type`, or the spot price if `interruptible`.
+    pub fn hourly(&self, cloud_type: CloudType, interruptible: bool) -> Option<f64> {
+        match (cloud_type, interruptible) {
+            (CloudType::Secure, false) => self.secure,
+            (CloudType::Community, false) => self.community,
+            (CloudType::Secure, true) => self.secure_spot,
+            (CloudType::Community, true) => self.community_spot,
+        }
+    }
+
+    /// Returns the highest known price across all clouds.
+    pub fn max(&self) -> Option<f64> {
+        [
+            self.secure,
+            self.community,
+            self.secure_spot,
+            self.community_spot,
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(f64::max)
+    }
+}
+
+impl From<&GpuInfo> for GpuPrice {
+    fn from(gpu: &GpuInfo) -> Self {
+        let known = |price: f64| Some(price).filter(|price| *price > 0.0);
+        Self {
+            secure: known(gpu.secure_price),
+            community: known(gpu.community_price),
+            secure_spot: known(gpu.secure_spot_price),
+            community_spot: known(gpu.community_spot_price),
+        }
+    }
+}
+
+/// Hourly GPU prices keyed by GPU type ID, and storage prices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceCatalog {
+    /// Prices keyed by GPU type ID, as used by the API (e.g. `"NVIDIA A40"`).
+    pub gpus: BTreeMap<String, GpuPrice>,
+
+    /// Storage prices.
+    #[serde(default)]
+    pub storage: StoragePrices,
+}
+
+impl PriceCatalog {
+    /// Creates an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the price of `gpu_type`.
+    pub fn with_gpu(mut self, gpu_type: &GpuTypeId, price: GpuPrice) -> Self {
+        self.gpus.insert(gpu_type_key(gpu_type), price);
+        self
+    }
+
+    /// Builds a catalog from the GPU details of `pods`.
+    ///
+    /// Only Pods listed with their GPU details contribute prices.
+    pub fn from_pods(pods: &[Pod]) -> Self {
+        let gpus = pods
+            .iter()
+            .filter_map(|pod| pod.gpu.as_ref())
+            .map(|gpu| (gpu.id.clone(), GpuPrice::from(gpu)))
+            .collect();
+        Self {
+            gpus,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the price of `gpu_type`, if known.
+    pub fn gpu(&self, gpu_type: &GpuTypeId) -> Option<&GpuPrice> {
+        self.gpus.get(&gpu_type_key(gpu_type))
+    }
+
+    /// Adds GPU prices from `other`, replacing existing entries.
+    pub fn merge(&mut self, other: PriceCatalog) {
+        self.gpus.extend(other.gpus);
+    }
+
+    /// Returns the highest known price of any GPU.
+    pub fn max(&self) -> Option<f64> {
+        self.gpus
+            .values()
+            .filter_map(GpuPrice::max)
+            .reduce(f64::max)
+    }
+}
+
+#[cfg(feature = "graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+impl PriceCatalog {
+    /// Fetches current GPU prices from the GraphQL API.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API reports an error.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn fetch(client: &crate::RunpodClient) -> crate::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct GpuTypes {
+            gpu_types: Vec<GpuType>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct GpuType {
+            id: String,
+            #[serde(flatten)]
+            price: GpuPrice,
+        }
+
+        const QUERY: &str = "query GpuTypes { gpuTypes { id secure: securePrice \
+            community: communityPrice secureSpot: secureSpotPrice \
+            communitySpot: communitySpotPrice } }";
+
+        let response: GpuTypes = client.graphql_data(QUERY).await?;
+        let gpus = response
+            .gpu_types
+            .into_iter()
+            .map(|gpu| (gpu.id, gpu.price))
+            .collect();
+
+        Ok(Self {
+            gpus,
+            ..Default::default()
+        })
+    }
+}
+
+/// Returns the API identifier of `gpu_type`, e.g. `"NVIDIA A40"`.
+pub(crate) fn gpu_type_key(gpu_type: &GpuTypeId) -> String {
+    api_value(gpu_type)
+}
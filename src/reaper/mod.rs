@@ -0,0 +1,714 @@
+//! Idle Pod detection and reaping.
+//!
+//! A [`Reaper`] finds running Pods that have been idle longer than a
+//! [`ReaperPolicy`] allows and stops or terminates them. Idleness is judged
+//! from how long an [`ActivityProbe`] has observed the Pod inactive across
+//! runs, starting from when the Pod last started if it was inactive the first
+//! time it was observed; Pods the probe has no signal for are never reaped. Locked Pods, Pods
+//! opting out through an environment variable, and Pods excluded by name
+//! patterns are never reaped either.
+//!
+//! Every run returns a [`ReapReport`] including the cost billed while each Pod
+//! was idle. Reapers run in dry-run mode by default, where nothing is stopped
+//! and the report is a preview. A [`ReapNotifier`] is called for every planned,
+//! reaped or failed Pod.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use runpod_sdk::model::v1::Pod;
+//! use runpod_sdk::reaper::{ActivityProbe, ReapEvent, Reaper, ReaperPolicy};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! /// Reads the activity of a Pod from an application metrics store.
+//! struct Metrics;
+//!
+//! impl ActivityProbe for Metrics {
+//!     async fn is_active(&self, pod: &Pod) -> Result<Option<bool>> {
+//!         # let _ = pod;
+//!         Ok(None)
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let mut reaper = Reaper::new(client, Metrics)
+//!     .with_policy(ReaperPolicy {
+//!         idle_after: Duration::from_secs(12 * 3600),
+//!         exclude: vec!["prod-*".to_string()],
+//!         ..Default::default()
+//!     })
+//!     .with_notifier(|event: &ReapEvent<'_>| println!("{event}"));
+//!
+//! let report = reaper.run().await?;
+//! for pod in &report.idle {
+//!     println!("{} idle since {}, ${:.2} wasted", pod.pod_id, pod.idle_since, pod.idle_cost);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod probe;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+pub use probe::ActivityProbe;
+#[cfg(feature = "graphql")]
+pub use probe::GpuUtilizationProbe;
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::{BillingWindows, parse_time};
+use crate::model::v1::{
+    BillingGrouping, BucketSize, ListPodsQuery, Pod, PodBillingQuery, PodStatus,
+};
+use crate::service::v1::{BillingService, PodsService};
+use crate::{Error, Result};
+
+/// Environment variable that opts a Pod out of reaping when set to a truthy value.
+pub const OPT_OUT_ENV: &str = "RUNPOD_REAPER_OPT_OUT";
+
+/// What to do with an idle Pod.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReapAction {
+    /// Stop the Pod, keeping its volume.
+    #[default]
+    Stop,
+    /// Terminate the Pod, deleting it and its volume.
+    Terminate,
+}
+
+/// Rules deciding which Pods a [`Reaper`] may reap.
+#[derive(Debug, Clone)]
+pub struct ReaperPolicy {
+    /// How long a Pod must be idle before it is reaped.
+    pub idle_after: Duration,
+
+    /// Action applied to idle Pods.
+    pub action: ReapAction,
+
+    /// Name patterns of Pods that may be reaped; all Pods if empty.
+    ///
+    /// Patterns support `*` (any run of characters) and `?` (any character).
+    pub include: Vec<String>,
+
+    /// Name patterns of Pods that are never reaped.
+    pub exclude: Vec<String>,
+
+    /// Environment variable that opts a Pod out when set to `1`, `true` or `yes`.
+    pub opt_out_env: String,
+
+    /// Report idle Pods without stopping or terminating them. Enabled by default.
+    pub dry_run: bool,
+
+    /// Maximum number of requests in flight. Values below 1 are treated as 1.
+    pub concurrency: usize,
+}
+
+impl Default for ReaperPolicy {
+    fn default() -> Self {
+        Self {
+            idle_after: Duration::from_secs(2 * 3600),
+            action: ReapAction::Stop,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            opt_out_env: OPT_OUT_ENV.to_string(),
+            dry_run: true,
+            concurrency: 4,
+        }
+    }
+}
+
+/// A Pod found idle beyond the policy threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlePod {
+    /// Pod ID.
+    pub pod_id: String,
+
+    /// Pod name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Time since which the Pod is considered idle.
+    pub idle_since: DateTime<Utc>,
+
+    /// Current hourly cost of the Pod, in USD.
+    pub cost_per_hr: f64,
+
+    /// Amount billed for the Pod while idle, in USD.
+    pub idle_cost: f64,
+}
+
+/// Why a running Pod was not reaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SkipReason {
+    /// The Pod is locked.
+    Locked,
+    /// The Pod opted out through its environment.
+    OptedOut,
+    /// The Pod name is not included, or is excluded, by the policy.
+    Excluded,
+    /// The probe reported the Pod active.
+    Active,
+    /// The probe has no activity signal for the Pod.
+    Unknown,
+    /// The Pod has not been idle long enough.
+    Recent,
+}
+
+/// A running Pod that was not reaped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedPod {
+    /// Pod ID.
+    pub pod_id: String,
+
+    /// Pod name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Why the Pod was skipped.
+    pub reason: SkipReason,
+}
+
+/// Outcome of reaping a single Pod.
+#[derive(Debug)]
+pub struct ReapResult {
+    /// Pod ID.
+    pub pod_id: String,
+
+    /// `Ok` if the Pod was stopped or terminated, or the error returned by the API.
+    pub outcome: Result<()>,
+}
+
+/// Result of a [`Reaper::run`].
+#[derive(Debug)]
+pub struct ReapReport {
+    /// Time the run started.
+    pub at: DateTime<Utc>,
+
+    /// Action applied to idle Pods.
+    pub action: ReapAction,
+
+    /// Whether the run was a dry run.
+    pub dry_run: bool,
+
+    /// Pods idle beyond the policy threshold.
+    pub idle: Vec<IdlePod>,
+
+    /// Running Pods that were not reaped.
+    pub skipped: Vec<SkippedPod>,
+
+    /// Per-Pod results. Empty for dry runs.
+    pub results: Vec<ReapResult>,
+}
+
+impl ReapReport {
+    /// Total amount billed for idle Pods while idle, in USD.
+    pub fn idle_cost(&self) -> f64 {
+        self.idle.iter().map(|pod| pod.idle_cost).sum()
+    }
+
+    /// Returns `true` if every Pod was reaped successfully.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.outcome.is_ok())
+    }
+}
+
+/// Event passed to a [`ReapNotifier`].
+#[derive(Debug)]
+pub enum ReapEvent<'a> {
+    /// An idle Pod would be reaped, in dry-run mode.
+    Planned(&'a IdlePod, ReapAction),
+    /// An idle Pod was stopped or terminated.
+    Reaped(&'a IdlePod, ReapAction),
+    /// Stopping or terminating an idle Pod failed.
+    Failed(&'a IdlePod, ReapAction, &'a Error),
+}
+
+impl fmt::Display for ReapEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = |action: &ReapAction| match action {
+            ReapAction::Stop => "stop",
+            ReapAction::Terminate => "terminate",
+        };
+        match self {
+            Self::Planned(pod, action) => {
+                write!(f, "would {} idle pod {}", verb(action), pod.pod_id)
+            }
+            Self::Reaped(pod, action) => {
+                let done = match action {
+                    ReapAction::Stop => "stopped",
+                    ReapAction::Terminate => "terminated",
+                };
+                write!(f, "{done} idle pod {}", pod.pod_id)
+            }
+            Self::Failed(pod, action, e) => {
+                write!(f, "failed to {} idle pod {}: {e}", verb(action), pod.pod_id)
+            }
+        }
+    }
+}
+
+/// Receives [`ReapEvent`]s, e.g. to post them to a chat channel.
+///
+/// Implemented for `()`, which ignores events, and for closures taking a
+/// `&ReapEvent`.
+pub trait ReapNotifier {
+    /// Handles a single event.
+    fn notify(&self, event: &ReapEvent<'_>);
+}
+
+impl ReapNotifier for () {
+    fn notify(&self, _event: &ReapEvent<'_>) {}
+}
+
+impl<F> ReapNotifier for F
+where
+    F: Fn(&ReapEvent<'_>),
+{
+    fn notify(&self, event: &ReapEvent<'_>) {
+        self(event)
+    }
+}
+
+/// Stops or terminates Pods idle beyond a [`ReaperPolicy`].
+///
+/// A Pod is idle once its [`ActivityProbe`] has reported it inactive on every
+/// run for [`ReaperPolicy::idle_after`]. A Pod found inactive on its first
+/// observation is assumed inactive since it last started, so a reaper run once,
+/// e.g. from a cron job, reaps Pods that started long enough ago. Kept alive
+/// and run periodically, the reaper remembers when each Pod was last seen
+/// active and only counts inactivity observed since.
+pub struct Reaper<C, P, N = ()> {
+    client: C,
+    probe: P,
+    notifier: N,
+    policy: ReaperPolicy,
+    /// Time since which each observed Pod is inactive, or `None` if it was
+    /// active or had no signal on the last run.
+    inactive_since: HashMap<String, Option<DateTime<Utc>>>,
+}
+
+impl<C, P> Reaper<C, P> {
+    /// Creates a reaper judging activity with `probe`, with the default policy
+    /// and no notifier.
+    pub fn new(client: C, probe: P) -> Self {
+        Self {
+            client,
+            probe,
+            notifier: (),
+            policy: ReaperPolicy::default(),
+            inactive_since: HashMap::new(),
+        }
+    }
+}
+
+impl<C, P, N> Reaper<C, P, N> {
+    /// Replaces the reaper policy.
+    pub fn with_policy(mut self, policy: ReaperPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Replaces the activity probe, forgetting previous observations.
+    pub fn with_probe<Q: ActivityProbe>(self, probe: Q) -> Reaper<C, Q, N> {
+        Reaper {
+            client: self.client,
+            probe,
+            notifier: self.notifier,
+            policy: self.policy,
+            inactive_since: HashMap::new(),
+        }
+    }
+
+    /// Replaces the notifier.
+    pub fn with_notifier<M: ReapNotifier>(self, notifier: M) -> Reaper<C, P, M> {
+        Reaper {
+            client: self.client,
+            probe: self.probe,
+            notifier,
+            policy: self.policy,
+            inactive_since: self.inactive_since,
+        }
+    }
+
+    /// Returns the reaper policy.
+    pub fn policy(&self) -> &ReaperPolicy {
+        &self.policy
+    }
+
+    /// Decides whether `pod` is idle, returning the time since which it is.
+    fn evaluate(
+        &mut self,
+        pod: &Pod,
+        active: Option<bool>,
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, SkipReason> {
+        if pod.locked {
+            return Err(SkipReason::Locked);
+        }
+        let opted_out = pod.env.get(&self.policy.opt_out_env).is_some_and(|value| {
+            matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+        });
+        if opted_out {
+            return Err(SkipReason::OptedOut);
+        }
+        let name = pod.name.as_deref().unwrap_or(&pod.id);
+        let included = self.policy.include.is_empty()
+            || self.policy.include.iter().any(|p| glob_match(p, name));
+        if !included || self.policy.exclude.iter().any(|p| glob_match(p, name)) {
+            return Err(SkipReason::Excluded);
+        }
+
+        let started = pod
+            .last_started_at
+            .as_deref()
+            .and_then(|time| parse_time(time).ok());
+        let idle_since = match active {
+            Some(true) => {
+                self.inactive_since.insert(pod.id.clone(), None);
+                return Err(SkipReason::Active);
+            }
+            Some(false) => {
+                let first = match self.inactive_since.get(&pod.id) {
+                    Some(since) => since.unwrap_or(now),
+                    None => started.unwrap_or(now),
+                };
+                self.inactive_since.insert(pod.id.clone(), Some(first));
+                started.map_or(first, |started| started.max(first))
+            }
+            None => {
+                self.inactive_since.insert(pod.id.clone(), None);
+                return Err(SkipReason::Unknown);
+            }
+        };
+
+        let idle_for = (now - idle_since).to_std().unwrap_or_default();
+        if idle_for < self.policy.idle_after {
+            return Err(SkipReason::Recent);
+        }
+        Ok(idle_since)
+    }
+}
+
+impl<C, P, N> Reaper<C, P, N>
+where
+    C: PodsService + BillingService,
+    P: ActivityProbe,
+    N: ReapNotifier,
+{
+    /// Finds idle Pods and, unless running in dry-run mode, reaps them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing Pods, probing activity or fetching billing
+    /// fails; no Pod is reaped in that case. Failures to reap individual Pods
+    /// are reported in [`ReapReport::results`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn run(&mut self) -> Result<ReapReport> {
+        let now = Utc::now();
+        let query = ListPodsQuery {
+            desired_status: Some(PodStatus::Running),
+            ..Default::default()
+        };
+        let pods = self.client.list_pods(query).await?;
+        let pods: Vec<Pod> = pods
+            .into_iter()
+            .filter(|pod| pod.desired_status == PodStatus::Running)
+            .collect();
+
+        let probe = &self.probe;
+        let activity: Vec<Option<bool>> = stream::iter(&pods)
+            .map(|pod| probe.is_active(pod))
+            .buffered(self.policy.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        self.inactive_since
+            .retain(|id, _| pods.iter().any(|pod| &pod.id == id));
+
+        let mut idle = Vec::new();
+        let mut skipped = Vec::new();
+        for (pod, active) in pods.iter().zip(activity) {
+            match self.evaluate(pod, active, now) {
+                Ok(idle_since) => idle.push(IdlePod {
+                    pod_id: pod.id.clone(),
+                    name: pod.name.clone(),
+                    idle_since,
+                    cost_per_hr: pod.cost_per_hr,
+                    idle_cost: 0.0,
+                }),
+                Err(reason) => skipped.push(SkippedPod {
+                    pod_id: pod.id.clone(),
+                    name: pod.name.clone(),
+                    reason,
+                }),
+            }
+        }
+        self.attribute_idle_cost(&mut idle, now).await?;
+
+        let action = self.policy.action;
+        let results = if self.policy.dry_run {
+            for pod in &idle {
+                self.notifier.notify(&ReapEvent::Planned(pod, action));
+            }
+            Vec::new()
+        } else {
+            self.reap(&idle).await
+        };
+
+        Ok(ReapReport {
+            at: now,
+            action,
+            dry_run: self.policy.dry_run,
+            idle,
+            skipped,
+            results,
+        })
+    }
+
+    /// Fills in the amount billed for each idle Pod since it became idle.
+    async fn attribute_idle_cost(&self, idle: &mut [IdlePod], now: DateTime<Utc>) -> Result<()> {
+        let Some(start) = idle.iter().map(|pod| pod.idle_since).min() else {
+            return Ok(());
+        };
+        let query = PodBillingQuery {
+            bucket_size: Some(BucketSize::Hour),
+            grouping: Some(BillingGrouping::PodId),
+            start_time: Some(start.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            end_time: Some(now.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            ..Default::default()
+        };
+        let records = BillingWindows::default()
+            .pod_billing(&self.client, query)
+            .await?;
+
+        for pod in idle.iter_mut() {
+            pod.idle_cost = records
+                .iter()
+                .filter(|record| record.pod_id.as_deref() == Some(pod.pod_id.as_str()))
+                .filter(|record| {
+                    parse_time(&record.time)
+                        .is_ok_and(|time| time + chrono::Duration::hours(1) > pod.idle_since)
+                })
+                .map(|record| record.amount)
+                .sum();
+        }
+        Ok(())
+    }
+
+    async fn reap(&self, idle: &[IdlePod]) -> Vec<ReapResult> {
+        let action = self.policy.action;
+        stream::iter(idle)
+            .map(|pod| async move {
+                let outcome = match action {
+                    ReapAction::Stop => self.client.stop_pod(&pod.pod_id).await,
+                    ReapAction::Terminate => self.client.delete_pod(&pod.pod_id).await,
+                };
+
+                #[cfg(feature = "tracing")]
+                match &outcome {
+                    Ok(()) => tracing::info!(pod_id = %pod.pod_id, ?action, "Reaped idle pod"),
+                    Err(e) => tracing::warn!(pod_id = %pod.pod_id, error = %e, "Reaping failed"),
+                }
+
+                match &outcome {
+                    Ok(()) => self.notifier.notify(&ReapEvent::Reaped(pod, action)),
+                    Err(e) => self.notifier.notify(&ReapEvent::Failed(pod, action, e)),
+                }
+                ReapResult {
+                    pod_id: pod.pod_id.clone(),
+                    outcome,
+                }
+            })
+            .buffered(self.policy.concurrency.max(1))
+            .collect()
+            .await
+    }
+}
+
+impl<C: fmt::Debug, P: fmt::Debug, N> fmt::Debug for Reaper<C, P, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reaper")
+            .field("client", &self.client)
+            .field("probe", &self.probe)
+            .field("policy", &self.policy)
+            .field("inactive_since", &self.inactive_since)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Matches `text` against a pattern supporting `*` and `?` wildcards.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some('?') => (p, t) = (p + 1, t + 1),
+            Some(c) if *c == text[t] => (p, t) = (p + 1, t + 1),
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    (p, t) = (star + 1, matched + 1);
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::testing::{self, Cloud};
+
+    fn pod(id: &str, name: &str, started: &str) -> Pod {
+        testing::pod(serde_json::json!({
+            "id": id,
+            "name": name,
+            "lastStartedAt": started,
+        }))
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("dev-*", "dev-alice"));
+        assert!(glob_match("*-gpu-?", "train-gpu-1"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("dev-*", "prod-alice"));
+        assert!(!glob_match("a?c", "abbc"));
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let mut reaper = Reaper::new((), ()).with_policy(ReaperPolicy {
+            exclude: vec!["prod-*".to_string()],
+            ..Default::default()
+        });
+        let earlier = now() - chrono::Duration::hours(3);
+
+        let weekend = pod("a", "dev-a", "2025-01-03T18:00:00Z");
+        let started = Utc.with_ymd_and_hms(2025, 1, 3, 18, 0, 0).unwrap();
+        assert_eq!(reaper.evaluate(&weekend, Some(false), earlier), Ok(started));
+        assert_eq!(reaper.evaluate(&weekend, Some(false), now()), Ok(started));
+
+        let recent = pod("b", "dev-b", "2025-01-06T08:00:00Z");
+        reaper.evaluate(&recent, Some(false), earlier).unwrap_err();
+        assert_eq!(
+            reaper.evaluate(&recent, Some(false), now()),
+            Err(SkipReason::Recent)
+        );
+
+        let prod = pod("c", "prod-c", "2025-01-03T18:00:00Z");
+        assert_eq!(
+            reaper.evaluate(&prod, Some(false), now()),
+            Err(SkipReason::Excluded)
+        );
+
+        let mut locked = weekend.clone();
+        locked.locked = true;
+        assert_eq!(
+            reaper.evaluate(&locked, Some(false), now()),
+            Err(SkipReason::Locked)
+        );
+
+        let mut opted_out = weekend.clone();
+        opted_out
+            .env
+            .insert(OPT_OUT_ENV.to_string(), "true".to_string());
+        assert_eq!(
+            reaper.evaluate(&opted_out, Some(false), now()),
+            Err(SkipReason::OptedOut)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_tracks_inactivity_across_runs() {
+        let mut reaper = Reaper::new((), ());
+        let pod = pod("a", "dev-a", "2025-01-06T08:00:00Z");
+        let started = now() - chrono::Duration::hours(1);
+        let later = now() + chrono::Duration::hours(3);
+
+        assert_eq!(
+            reaper.evaluate(&pod, Some(false), now()),
+            Err(SkipReason::Recent)
+        );
+        assert_eq!(reaper.evaluate(&pod, Some(false), later), Ok(started));
+
+        assert_eq!(
+            reaper.evaluate(&pod, Some(true), later),
+            Err(SkipReason::Active)
+        );
+        assert_eq!(
+            reaper.evaluate(&pod, Some(false), later),
+            Err(SkipReason::Recent)
+        );
+        let much_later = later + chrono::Duration::hours(3);
+        assert_eq!(reaper.evaluate(&pod, Some(false), much_later), Ok(later));
+    }
+
+    #[test]
+    fn test_evaluate_skips_pods_without_signal() {
+        let mut reaper = Reaper::new((), ());
+        let pod = pod("a", "dev-a", "2025-01-03T18:00:00Z");
+        let later = now() + chrono::Duration::hours(3);
+
+        assert!(reaper.policy().dry_run);
+        reaper.evaluate(&pod, Some(false), now()).unwrap();
+        assert_eq!(reaper.evaluate(&pod, None, later), Err(SkipReason::Unknown));
+        assert_eq!(
+            reaper.evaluate(&pod, Some(false), later),
+            Err(SkipReason::Recent)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fresh_reaper_reaps_long_idle_pods() -> Result<()> {
+        struct Inactive;
+
+        impl ActivityProbe for Inactive {
+            async fn is_active(&self, _: &Pod) -> Result<Option<bool>> {
+                Ok(Some(false))
+            }
+        }
+
+        let cloud = Cloud::new()
+            .with_pod(pod("a", "dev-a", testing::STARTED_AT))
+            .with_pod(pod("b", "dev-b", &Utc::now().to_rfc3339()));
+        let mut reaper = Reaper::new(cloud, Inactive).with_policy(ReaperPolicy {
+            dry_run: false,
+            ..Default::default()
+        });
+
+        let report = reaper.run().await?;
+        assert_eq!(report.idle.len(), 1);
+        assert_eq!(report.idle[0].pod_id, "a");
+        assert_eq!(report.skipped[0].reason, SkipReason::Recent);
+        assert!(report.is_success());
+        assert_eq!(reaper.client.calls(), ["stop_pod a"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use std::future::Future;
+
+use crate::Result;
+use crate::model::v1::Pod;
+#[cfg(feature = "graphql")]
+use crate::service::v1::RuntimeService;
+
+/// Source of activity signals for a running Pod.
+///
+/// The [`Reaper`](super::Reaper) samples the probe on every run and only
+/// counts a Pod as idle while the probe keeps reporting it inactive.
+pub trait ActivityProbe {
+    /// Returns whether `pod` is currently active, or `None` if unknown.
+    ///
+    /// Pods without an activity signal are never reaped.
+    fn is_active(&self, pod: &Pod) -> impl Future<Output = Result<Option<bool>>>;
+}
+
+/// Probe reporting a Pod active while any of its GPUs is utilized.
+///
+/// Reads [`PodRuntime`](crate::model::v1::PodRuntime) telemetry from the
+/// GraphQL API. Pods without GPUs or without runtime telemetry yield no signal
+/// and are skipped.
+#[cfg(feature = "graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+#[derive(Debug, Clone)]
+pub struct GpuUtilizationProbe<C = crate::RunpodClient> {
+    client: C,
+    threshold: f64,
+}
+
+#[cfg(feature = "graphql")]
+impl<C> GpuUtilizationProbe<C> {
+    /// Creates a probe counting GPUs at or above `threshold` percent utilization as active.
+    pub fn new(client: C, threshold: f64) -> Self {
+        Self { client, threshold }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl<C: RuntimeService> ActivityProbe for GpuUtilizationProbe<C> {
+    async fn is_active(&self, pod: &Pod) -> Result<Option<bool>> {
+        let runtime = self.client.get_pod_runtime(&pod.id).await?;
+        let utilization = runtime.and_then(|runtime| runtime.max_gpu_utilization());
+        Ok(utilization.map(|utilization| utilization >= self.threshold))
+    }
+}
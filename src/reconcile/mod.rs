@@ -0,0 +1,246 @@
+//! Declarative desired-state reconciliation for pods and endpoints.
+//!
+//! This module compares a [`DesiredState`] (pods and serverless endpoints keyed
+//! by name) with the live account, computes a typed [`Plan`] of create, update,
+//! start/stop and delete actions, and applies it with bounded concurrency.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::reconcile::{DesiredState, ReconcileOptions, Reconciler};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example(desired: DesiredState) -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let reconciler = Reconciler::new(client).with_options(ReconcileOptions {
+//!     dry_run: true,
+//!     ..Default::default()
+//! });
+//!
+//! let report = reconciler.reconcile(&desired).await?;
+//! for action in &report.plan.actions {
+//!     println!("would {action}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod plan;
+mod spec;
+
+use futures::stream::{self, StreamExt};
+pub use plan::{Action, Plan};
+pub use spec::{DesiredState, EndpointSpec, PodSpec};
+
+use crate::model::v1::{ListEndpointsQuery, ListPodsQuery};
+use crate::service::v1::{EndpointsService, PodsService};
+use crate::{Result, RunpodClient};
+
+/// Options controlling how a [`Reconciler`] plans and applies changes.
+#[derive(Debug, Clone)]
+pub struct ReconcileOptions {
+    /// Compute the plan without issuing any mutating request.
+    pub dry_run: bool,
+
+    /// Delete named pods and endpoints that are absent from the desired state.
+    ///
+    /// Disabled by default, since it affects every named resource in the account.
+    pub prune: bool,
+
+    /// Maximum number of actions applied concurrently. Values below 1 are treated as 1.
+    pub concurrency: usize,
+}
+
+impl Default for ReconcileOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            prune: false,
+            concurrency: 4,
+        }
+    }
+}
+
+/// Outcome of applying a single [`Action`].
+#[derive(Debug)]
+pub struct ActionResult {
+    /// The action that was applied.
+    pub action: Action,
+
+    /// The identifier of the affected resource, or the error returned by the API.
+    pub outcome: Result<String>,
+}
+
+/// Result of a [`Reconciler::reconcile`] run.
+#[derive(Debug)]
+pub struct ReconcileReport {
+    /// The plan that was computed.
+    pub plan: Plan,
+
+    /// Per-action results, in plan order. Empty for dry runs.
+    pub results: Vec<ActionResult>,
+}
+
+impl ReconcileReport {
+    /// Returns `true` if every applied action succeeded.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.outcome.is_ok())
+    }
+
+    /// Returns the actions that failed, together with their errors.
+    pub fn failures(&self) -> impl Iterator<Item = &ActionResult> {
+        self.results.iter().filter(|result| result.outcome.is_err())
+    }
+}
+
+/// Drives an account towards a [`DesiredState`].
+///
+/// The reconciler is generic over any client implementing [`PodsService`] and
+/// [`EndpointsService`], which defaults to [`RunpodClient`].
+#[derive(Debug, Clone)]
+pub struct Reconciler<C = RunpodClient> {
+    client: C,
+    options: ReconcileOptions,
+}
+
+impl<C> Reconciler<C> {
+    /// Creates a reconciler with default options.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            options: ReconcileOptions::default(),
+        }
+    }
+
+    /// Replaces the reconciler options.
+    pub fn with_options(mut self, options: ReconcileOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the reconciler options.
+    pub fn options(&self) -> &ReconcileOptions {
+        &self.options
+    }
+}
+
+impl<C> Reconciler<C>
+where
+    C: PodsService + EndpointsService,
+{
+    /// Fetches the live pods and endpoints and computes a plan.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing resources fails or if the desired state
+    /// contains duplicate names.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn plan(&self, desired: &DesiredState) -> Result<Plan> {
+        let (pods, endpoints) = futures::try_join!(
+            self.client.list_pods(ListPodsQuery::default()),
+            self.client.list_endpoints(ListEndpointsQuery::default()),
+        )?;
+
+        Plan::compute(desired, &pods, &endpoints, self.options.prune)
+    }
+
+    /// Applies every action of `plan`, regardless of [`ReconcileOptions::dry_run`].
+    ///
+    /// Actions targeting the same resource are applied sequentially, while
+    /// different resources are processed with at most
+    /// [`ReconcileOptions::concurrency`] requests in flight. A failing action
+    /// does not stop actions on other resources.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(actions = plan.actions.len())))]
+    pub async fn apply(&self, plan: &Plan) -> Vec<ActionResult> {
+        let mut groups: Vec<Vec<Action>> = Vec::new();
+        for action in &plan.actions {
+            match groups.last_mut() {
+                Some(group) if group[0].resource() == action.resource() => {
+                    group.push(action.clone())
+                }
+                _ => groups.push(vec![action.clone()]),
+            }
+        }
+
+        let results: Vec<Vec<ActionResult>> = stream::iter(groups)
+            .map(|group| async move {
+                let mut results = Vec::with_capacity(group.len());
+                for action in group {
+                    let outcome = self.apply_action(&action).await;
+
+                    #[cfg(feature = "tracing")]
+                    match &outcome {
+                        Ok(id) => tracing::debug!(%action, id = %id, "Applied action"),
+                        Err(e) => tracing::warn!(%action, error = %e, "Action failed"),
+                    }
+
+                    results.push(ActionResult { action, outcome });
+                }
+                results
+            })
+            .buffered(self.options.concurrency.max(1))
+            .collect()
+            .await;
+
+        results.into_iter().flatten().collect()
+    }
+
+    /// Plans and, unless running in dry-run mode, applies the changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if planning fails. Failures of individual actions
+    /// are reported in [`ReconcileReport::results`].
+    pub async fn reconcile(&self, desired: &DesiredState) -> Result<ReconcileReport> {
+        let plan = self.plan(desired).await?;
+        let results = if self.options.dry_run {
+            Vec::new()
+        } else {
+            self.apply(&plan).await
+        };
+
+        Ok(ReconcileReport { plan, results })
+    }
+
+    async fn apply_action(&self, action: &Action) -> Result<String> {
+        match action {
+            Action::CreatePod { input, .. } => {
+                let pod = self.client.create_pod((**input).clone()).await?;
+                Ok(pod.id)
+            }
+            Action::UpdatePod { pod_id, input, .. } => {
+                let pod = self.client.update_pod(pod_id, (**input).clone()).await?;
+                Ok(pod.id)
+            }
+            Action::StartPod { pod_id, .. } => {
+                self.client.start_pod(pod_id).await?;
+                Ok(pod_id.clone())
+            }
+            Action::StopPod { pod_id, .. } => {
+                self.client.stop_pod(pod_id).await?;
+                Ok(pod_id.clone())
+            }
+            Action::DeletePod { pod_id, .. } => {
+                self.client.delete_pod(pod_id).await?;
+                Ok(pod_id.clone())
+            }
+            Action::CreateEndpoint { input, .. } => {
+                let endpoint = self.client.create_endpoint((**input).clone()).await?;
+                Ok(endpoint.id)
+            }
+            Action::UpdateEndpoint {
+                endpoint_id, input, ..
+            } => {
+                let endpoint = self
+                    .client
+                    .update_endpoint(endpoint_id, (**input).clone())
+                    .await?;
+                Ok(endpoint.id)
+            }
+            Action::DeleteEndpoint { endpoint_id, .. } => {
+                self.client.delete_endpoint(endpoint_id).await?;
+                Ok(endpoint_id.clone())
+            }
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use super::spec::DesiredState;
+use crate::model::v1::{
+    Endpoint, EndpointCreateInput, EndpointUpdateInput, Pod, PodCreateInput, PodStatus,
+    PodUpdateInput,
+};
+use crate::{Error, Result};
+
+/// A single change required to move the account towards the desired state.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum Action {
+    /// Create a Pod that does not exist yet.
+    CreatePod {
+        /// Name of the Pod.
+        name: String,
+        /// Input sent to [`PodsService::create_pod`](crate::service::v1::PodsService::create_pod).
+        input: Box<PodCreateInput>,
+    },
+    /// Update a Pod whose configuration drifted from the spec.
+    UpdatePod {
+        /// Name of the Pod.
+        name: String,
+        /// Identifier of the existing Pod.
+        pod_id: String,
+        /// Only the fields that differ from the live Pod.
+        input: Box<PodUpdateInput>,
+    },
+    /// Start a Pod that should be running.
+    StartPod {
+        /// Name of the Pod.
+        name: String,
+        /// Identifier of the existing Pod.
+        pod_id: String,
+    },
+    /// Stop a Pod that should not be running.
+    StopPod {
+        /// Name of the Pod.
+        name: String,
+        /// Identifier of the existing Pod.
+        pod_id: String,
+    },
+    /// Delete a Pod that is not part of the desired state.
+    DeletePod {
+        /// Name of the Pod.
+        name: String,
+        /// Identifier of the existing Pod.
+        pod_id: String,
+    },
+    /// Create an endpoint that does not exist yet.
+    CreateEndpoint {
+        /// Name of the endpoint.
+        name: String,
+        /// Input sent to [`EndpointsService::create_endpoint`](crate::service::v1::EndpointsService::create_endpoint).
+        input: Box<EndpointCreateInput>,
+    },
+    /// Update an endpoint whose configuration drifted from the spec.
+    UpdateEndpoint {
+        /// Name of the endpoint.
+        name: String,
+        /// Identifier of the existing endpoint.
+        endpoint_id: String,
+        /// Only the fields that differ from the live endpoint.
+        input: Box<EndpointUpdateInput>,
+    },
+    /// Delete an endpoint that is not part of the desired state.
+    DeleteEndpoint {
+        /// Name of the endpoint.
+        name: String,
+        /// Identifier of the existing endpoint.
+        endpoint_id: String,
+    },
+}
+
+impl Action {
+    /// Returns the name of the resource this action applies to.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::CreatePod { name, .. }
+            | Self::UpdatePod { name, .. }
+            | Self::StartPod { name, .. }
+            | Self::StopPod { name, .. }
+            | Self::DeletePod { name, .. }
+            | Self::CreateEndpoint { name, .. }
+            | Self::UpdateEndpoint { name, .. }
+            | Self::DeleteEndpoint { name, .. } => name,
+        }
+    }
+
+    /// Returns the kind and name of the targeted resource, used to keep
+    /// actions on the same resource in order.
+    pub(crate) fn resource(&self) -> (&'static str, &str) {
+        let kind = match self {
+            Self::CreatePod { .. }
+            | Self::UpdatePod { .. }
+            | Self::StartPod { .. }
+            | Self::StopPod { .. }
+            | Self::DeletePod { .. } => "pod",
+            Self::CreateEndpoint { .. }
+            | Self::UpdateEndpoint { .. }
+            | Self::DeleteEndpoint { .. } => "endpoint",
+        };
+        (kind, self.name())
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreatePod { name, .. } => write!(f, "create pod {name}"),
+            Self::UpdatePod { name, pod_id, .. } => write!(f, "update pod {name} ({pod_id})"),
+            Self::StartPod { name, pod_id } => write!(f, "start pod {name} ({pod_id})"),
+            Self::StopPod { name, pod_id } => write!(f, "stop pod {name} ({pod_id})"),
+            Self::DeletePod { name, pod_id } => write!(f, "delete pod {name} ({pod_id})"),
+            Self::CreateEndpoint { name, .. } => write!(f, "create endpoint {name}"),
+            Self::UpdateEndpoint {
+                name, endpoint_id, ..
+            } => write!(f, "update endpoint {name} ({endpoint_id})"),
+            Self::DeleteEndpoint { name, endpoint_id } => {
+                write!(f, "delete endpoint {name} ({endpoint_id})")
+            }
+        }
+    }
+}
+
+/// Ordered list of actions computed by comparing desired and actual state.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+    /// Actions to apply, in the order they will be issued.
+    pub actions: Vec<Action>,
+
+    /// Names that match more than one live resource and were left untouched.
+    pub ambiguous: Vec<String>,
+}
+
+impl Plan {
+    /// Computes a plan from the desired state and already fetched live resources.
+    ///
+    /// Live resources without a name are never touched. When `prune` is set,
+    /// named resources that are absent from `desired` are scheduled for deletion.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Validation`] if `desired` contains duplicate names.
+    pub fn compute(
+        desired: &DesiredState,
+        pods: &[Pod],
+        endpoints: &[Endpoint],
+        prune: bool,
+    ) -> Result<Self> {
+        let mut plan = Self::default();
+        plan.plan_pods(desired, pods, prune)?;
+        plan.plan_endpoints(desired, endpoints, prune)?;
+        Ok(plan)
+    }
+
+    /// Returns `true` if the account already matches the desired state.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    fn plan_pods(&mut self, desired: &DesiredState, pods: &[Pod], prune: bool) -> Result<()> {
+        ensure_unique("pod", desired.pods.iter().map(|spec| spec.name.as_str()))?;
+
+        let live = index_by_name(
+            pods.iter()
+                .filter(|pod| pod.desired_status != PodStatus::Terminated)
+                .filter_map(|pod| pod.name.as_deref().map(|name| (name, pod))),
+        );
+
+        for spec in &desired.pods {
+            match live.get(spec.name.as_str()).map(Vec::as_slice) {
+                None | Some([]) => {
+                    let mut input = spec.input.clone();
+                    input.name = Some(spec.name.clone());
+                    self.actions.push(Action::CreatePod {
+                        name: spec.name.clone(),
+                        input: Box::new(input),
+                    });
+                }
+                Some([pod]) => {
+                    if let Some(input) = PodUpdateInput::diff(&spec.input, pod) {
+                        self.actions.push(Action::UpdatePod {
+                            name: spec.name.clone(),
+                            pod_id: pod.id.clone(),
+                            input: Box::new(input),
+                        });
+                    }
+
+                    let action = match (spec.desired_status, pod.desired_status) {
+                        (Some(PodStatus::Running), PodStatus::Exited) => Some(Action::StartPod {
+                            name: spec.name.clone(),
+                            pod_id: pod.id.clone(),
+                        }),
+                        (Some(PodStatus::Exited), PodStatus::Running) => Some(Action::StopPod {
+                            name: spec.name.clone(),
+                            pod_id: pod.id.clone(),
+                        }),
+                        _ => None,
+                    };
+                    self.actions.extend(action);
+                }
+                Some(_) => self.ambiguous.push(spec.name.clone()),
+            }
+        }
+
+        if prune {
+            let wanted: HashSet<_> = desired.pods.iter().map(|spec| spec.name.as_str()).collect();
+            for (name, matches) in &live {
+                if wanted.contains(name) {
+                    continue;
+                }
+                for pod in matches {
+                    self.actions.push(Action::DeletePod {
+                        name: name.to_string(),
+                        pod_id: pod.id.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn plan_endpoints(
+        &mut self,
+        desired: &DesiredState,
+        endpoints: &[Endpoint],
+        prune: bool,
+    ) -> Result<()> {
+        ensure_unique(
+            "endpoint",
+            desired.endpoints.iter().map(|spec| spec.name.as_str()),
+        )?;
+
+        let live = index_by_name(
+            endpoints
+                .iter()
+                .filter_map(|endpoint| endpoint.name.as_deref().map(|name| (name, endpoint))),
+        );
+
+        for spec in &desired.endpoints {
+            match live.get(spec.name.as_str()).map(Vec::as_slice) {
+                None | Some([]) => {
+                    let mut input = spec.input.clone();
+                    input.name = Some(spec.name.clone());
+                    self.actions.push(Action::CreateEndpoint {
+                        name: spec.name.clone(),
+                        input: Box::new(input),
+                    });
+                }
+                Some([endpoint]) => {
+                    if let Some(input) = EndpointUpdateInput::diff(&spec.input, endpoint) {
+                        self.actions.push(Action::UpdateEndpoint {
+                            name: spec.name.clone(),
+                            endpoint_id: endpoint.id.clone(),
+                            input: Box::new(input),
+                        });
+                    }
+                }
+                Some(_) => self.ambiguous.push(spec.name.clone()),
+            }
+        }
+
+        if prune {
+            let wanted: HashSet<_> = desired
+                .endpoints
+                .iter()
+                .map(|spec| spec.name.as_str())
+                .collect();
+            for (name, matches) in &live {
+                if wanted.contains(name) {
+                    continue;
+                }
+                for endpoint in matches {
+                    self.actions.push(Action::DeleteEndpoint {
+                        name: name.to_string(),
+                        endpoint_id: endpoint.id.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn ensure_unique<'a>(kind: &str, names: impl Iterator<Item = &'a str>) -> Result<()> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if !seen.insert(name) {
+            return Err(Error::Validation(format!(
+                "duplicate {kind} name in desired state: {name}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn index_by_name<'a, T>(
+    items: impl Iterator<Item = (&'a str, &'a T)>,
+) -> BTreeMap<&'a str, Vec<&'a T>> {
+    let mut index: BTreeMap<&str, Vec<&T>> = BTreeMap::new();
+    for (name, item) in items {
+        index.entry(name).or_default().push(item);
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::reconcile::{EndpointSpec, PodSpec};
+    use crate::testing;
+
+    fn pod(id: &str, name: &str, status: &str, image: &str) -> Pod {
+        testing::pod(json!({
+            "id": id,
+            "name": name,
+            "image": image,
+            "desiredStatus": status,
+            "ports": ["8888/http", "22/tcp"],
+        }))
+    }
+
+    fn endpoint(id: &str, name: &str, workers_max: i32) -> Endpoint {
+        testing::endpoint(json!({
+            "id": id,
+            "name": name,
+            "templateId": "tpl",
+            "workersMax": workers_max,
+        }))
+    }
+
+    fn pod_spec(name: &str, image: &str, status: Option<PodStatus>) -> PodSpec {
+        PodSpec {
+            name: name.to_string(),
+            input: PodCreateInput {
+                image_name: Some(image.to_string()),
+                ports: Some(vec!["22/tcp".to_string(), "8888/http".to_string()]),
+                ..Default::default()
+            },
+            desired_status: status,
+        }
+    }
+
+    #[test]
+    fn test_plan_is_empty_when_in_sync() -> Result<()> {
+        let desired = DesiredState {
+            pods: vec![pod_spec("a", "img:1", Some(PodStatus::Running))],
+            endpoints: vec![],
+        };
+        let pods = [pod("p1", "a", "RUNNING", "img:1")];
+
+        let plan = Plan::compute(&desired, &pods, &[], false)?;
+        assert!(plan.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_creates_updates_and_starts() -> Result<()> {
+        let desired = DesiredState {
+            pods: vec![
+                pod_spec("new", "img:1", None),
+                pod_spec("drifted", "img:2", Some(PodStatus::Running)),
+            ],
+            endpoints: vec![EndpointSpec {
+                name: "api".to_string(),
+                input: EndpointCreateInput {
+                    template_id: "tpl".to_string(),
+                    workers_max: Some(5),
+                    ..Default::default()
+                },
+            }],
+        };
+        let pods = [pod("p1", "drifted", "EXITED", "img:1")];
+        let endpoints = [endpoint("e1", "api", 3)];
+
+        let plan = Plan::compute(&desired, &pods, &endpoints, false)?;
+        let summary: Vec<_> = plan.actions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            summary,
+            [
+                "create pod new",
+                "update pod drifted (p1)",
+                "start pod drifted (p1)",
+                "update endpoint api (e1)",
+            ]
+        );
+
+        let Action::UpdateEndpoint { input, .. } = &plan.actions[3] else {
+            panic!("expected endpoint update");
+        };
+        assert_eq!(input.workers_max, Some(5));
+        assert_eq!(input.template_id, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_prunes_only_when_enabled() -> Result<()> {
+        let desired = DesiredState::default();
+        let pods = [pod("p1", "orphan", "RUNNING", "img:1")];
+        let endpoints = [endpoint("e1", "old-api", 1)];
+
+        let plan = Plan::compute(&desired, &pods, &endpoints, false)?;
+        assert!(plan.is_empty());
+
+        let plan = Plan::compute(&desired, &pods, &endpoints, true)?;
+        let summary: Vec<_> = plan.actions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            summary,
+            ["delete pod orphan (p1)", "delete endpoint old-api (e1)"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_reports_ambiguous_names() -> Result<()> {
+        let desired = DesiredState {
+            pods: vec![pod_spec("dup", "img:2", None)],
+            endpoints: vec![],
+        };
+        let pods = [
+            pod("p1", "dup", "RUNNING", "img:1"),
+            pod("p2", "dup", "RUNNING", "img:1"),
+        ];
+
+        let plan = Plan::compute(&desired, &pods, &[], false)?;
+        assert!(plan.is_empty());
+        assert_eq!(plan.ambiguous, ["dup"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_rejects_duplicate_desired_names() {
+        let desired = DesiredState {
+            pods: vec![pod_spec("a", "img", None), pod_spec("a", "img", None)],
+            endpoints: vec![],
+        };
+
+        let result = Plan::compute(&desired, &[], &[], false);
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+}
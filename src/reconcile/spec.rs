@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::v1::{EndpointCreateInput, PodCreateInput, PodStatus};
+
+/// Desired state of pods and serverless endpoints in an account.
+///
+/// Resources are matched against the live account by `name`, so each name
+/// must be unique within its resource kind. The struct is fully
+/// (de)serializable and is typically loaded from a fleet definition file.
+///
+/// # Examples
+///
+/// ```rust
+/// use runpod_sdk::model::v1::{EndpointCreateInput, PodCreateInput, PodStatus};
+/// use runpod_sdk::reconcile::{DesiredState, EndpointSpec, PodSpec};
+///
+/// let desired = DesiredState {
+///     pods: vec![PodSpec {
+///         name: "trainer".to_string(),
+///         input: PodCreateInput {
+///             image_name: Some("runpod/pytorch:latest".to_string()),
+///             gpu_count: Some(1),
+///             ..Default::default()
+///         },
+///         desired_status: Some(PodStatus::Running),
+///     }],
+///     endpoints: vec![EndpointSpec {
+///         name: "inference".to_string(),
+///         input: EndpointCreateInput {
+///             template_id: "tpl_123".to_string(),
+///             workers_max: Some(3),
+///             ..Default::default()
+///         },
+///     }],
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesiredState {
+    /// Pods that should exist, keyed by name.
+    #[serde(default)]
+    pub pods: Vec<PodSpec>,
+
+    /// Serverless endpoints that should exist, keyed by name.
+    #[serde(default)]
+    pub endpoints: Vec<EndpointSpec>,
+}
+
+/// Desired configuration of a single Pod.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodSpec {
+    /// Name used to match the spec against existing Pods.
+    /// Overrides any `name` set on `input`.
+    pub name: String,
+
+    /// Configuration used when creating the Pod. Fields that are also present
+    /// on [`PodUpdateInput`](crate::model::v1::PodUpdateInput) are compared
+    /// against the live Pod and updated in place when they drift.
+    pub input: PodCreateInput,
+
+    /// Whether the Pod should be running (`RUNNING`) or stopped (`EXITED`).
+    /// If omitted, the current status is left untouched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desired_status: Option<PodStatus>,
+}
+
+/// Desired configuration of a single serverless endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointSpec {
+    /// Name used to match the spec against existing endpoints.
+    /// Overrides any `name` set on `input`.
+    pub name: String,
+
+    /// Configuration used when creating the endpoint. Fields that are also
+    /// present on [`EndpointUpdateInput`](crate::model::v1::EndpointUpdateInput)
+    /// are compared against the live endpoint and updated when they drift.
+    pub input: EndpointCreateInput,
+}
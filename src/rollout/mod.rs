@@ -0,0 +1,705 @@
+//! Safe rolling updates of serverless endpoints.
+//!
+//! A [`Rollout`] moves an endpoint to a new template version instead of
+//! editing its template in place: it creates a new template, points the
+//! endpoint at it, and then watches the endpoint during a bake period. Every
+//! check records the endpoint version, how many workers run the new template
+//! and the job failure rate since the update. If a [`RolloutPolicy`] threshold
+//! is breached, or a request fails while baking, the endpoint is switched back
+//! to its previous template, which is left untouched by the rollout.
+//!
+//! A rollout only completes when the last check shows the update took effect:
+//! the endpoint version increased, at least one worker runs the new template,
+//! and enough jobs finished to judge the failure rate.
+//!
+//! Every rollout returns a [`RolloutReport`] describing the checks made and
+//! the [`RolloutOutcome`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use runpod_sdk::rollout::{Rollout, RolloutPolicy};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let rollout = Rollout::new(client).with_policy(RolloutPolicy {
+//!     bake_time: Duration::from_secs(15 * 60),
+//!     max_failure_rate: 0.02,
+//!     ..Default::default()
+//! });
+//!
+//! let report = rollout.update_image("endpoint_id", "org/app:v2").await?;
+//! if report.outcome.is_rolled_back() {
+//!     eprintln!("rolled back to {}", report.previous_template_id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::v1::{
+    Endpoint, EndpointHealth, EndpointUpdateInput, GetEndpointQuery, GetTemplateQuery, JobCounts,
+    TemplateCreateInput,
+};
+use crate::service::v1::{EndpointsService, ServerlessService, TemplatesService};
+use crate::{Result, RunpodClient};
+
+/// Thresholds and timing of a [`Rollout`].
+#[derive(Debug, Clone)]
+pub struct RolloutPolicy {
+    /// How long the endpoint is watched after the update.
+    pub bake_time: Duration,
+
+    /// Time between two checks during the bake period.
+    pub check_interval: Duration,
+
+    /// Highest tolerated share of failed jobs since the update, from 0 to 1.
+    pub max_failure_rate: f64,
+
+    /// Jobs that must finish before the failure rate is judged.
+    pub min_jobs: u64,
+
+    /// Highest tolerated number of unhealthy workers.
+    pub max_unhealthy_workers: u64,
+
+    /// Switch back to the previous template when a threshold is breached.
+    pub rollback: bool,
+}
+
+impl Default for RolloutPolicy {
+    fn default() -> Self {
+        Self {
+            bake_time: Duration::from_secs(10 * 60),
+            check_interval: Duration::from_secs(30),
+            max_failure_rate: 0.05,
+            min_jobs: 10,
+            max_unhealthy_workers: 0,
+            rollback: true,
+        }
+    }
+}
+
+impl RolloutPolicy {
+    /// Returns the threshold breached by `check`, if any.
+    pub fn evaluate(&self, check: &RolloutCheck) -> Option<RolloutBreach> {
+        if check.unhealthy_workers > self.max_unhealthy_workers {
+            return Some(RolloutBreach::UnhealthyWorkers {
+                count: check.unhealthy_workers,
+                threshold: self.max_unhealthy_workers,
+            });
+        }
+
+        let finished = check.jobs_completed + check.jobs_failed;
+        let rate = check.failure_rate();
+        if finished >= self.min_jobs.max(1) && rate > self.max_failure_rate {
+            return Some(RolloutBreach::FailureRate {
+                rate,
+                threshold: self.max_failure_rate,
+            });
+        }
+        None
+    }
+
+    /// Returns the evidence missing from `check` to complete a rollout of an
+    /// endpoint whose version was `previous_version`, if any.
+    pub fn missing_evidence(
+        &self,
+        previous_version: i32,
+        check: &RolloutCheck,
+    ) -> Option<MissingEvidence> {
+        if check.version <= previous_version {
+            return Some(MissingEvidence::VersionUnchanged {
+                version: check.version,
+            });
+        }
+        if check.updated_workers == 0 {
+            return Some(MissingEvidence::NoUpdatedWorkers);
+        }
+
+        let finished = check.jobs_completed + check.jobs_failed;
+        if finished < self.min_jobs {
+            return Some(MissingEvidence::TooFewJobs {
+                finished,
+                required: self.min_jobs,
+            });
+        }
+        None
+    }
+}
+
+/// State of an endpoint observed during the bake period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutCheck {
+    /// Time of the check.
+    pub at: DateTime<Utc>,
+
+    /// Endpoint version.
+    pub version: i32,
+
+    /// Worker pods of the endpoint.
+    pub workers: u64,
+
+    /// Worker pods running the new template.
+    pub updated_workers: u64,
+
+    /// Workers reported unhealthy.
+    pub unhealthy_workers: u64,
+
+    /// Jobs completed since the update.
+    pub jobs_completed: u64,
+
+    /// Jobs failed since the update.
+    pub jobs_failed: u64,
+}
+
+impl RolloutCheck {
+    /// Builds a check from the endpoint, its health, and the job counts
+    /// observed right before the update.
+    pub fn new(
+        at: DateTime<Utc>,
+        endpoint: &Endpoint,
+        health: &EndpointHealth,
+        baseline: &JobCounts,
+        template_id: &str,
+    ) -> Self {
+        let workers = endpoint.workers.as_deref().unwrap_or_default();
+        let updated = workers
+            .iter()
+            .filter(|pod| pod.template_id.as_deref() == Some(template_id));
+
+        Self {
+            at,
+            version: endpoint.version,
+            workers: workers.len() as u64,
+            updated_workers: updated.count() as u64,
+            unhealthy_workers: health.workers.unhealthy,
+            jobs_completed: health.jobs.completed.saturating_sub(baseline.completed),
+            jobs_failed: health.jobs.failed.saturating_sub(baseline.failed),
+        }
+    }
+
+    /// Share of failed jobs among jobs finished since the update.
+    pub fn failure_rate(&self) -> f64 {
+        let finished = self.jobs_completed + self.jobs_failed;
+        match finished {
+            0 => 0.0,
+            _ => self.jobs_failed as f64 / finished as f64,
+        }
+    }
+}
+
+/// A [`RolloutPolicy`] threshold breached during the bake period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RolloutBreach {
+    /// Too many jobs failed since the update.
+    FailureRate {
+        /// Observed failure rate.
+        rate: f64,
+        /// Highest tolerated failure rate.
+        threshold: f64,
+    },
+    /// Too many workers are unhealthy.
+    UnhealthyWorkers {
+        /// Observed number of unhealthy workers.
+        count: u64,
+        /// Highest tolerated number of unhealthy workers.
+        threshold: u64,
+    },
+}
+
+impl fmt::Display for RolloutBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FailureRate { rate, threshold } => write!(
+                f,
+                "failure rate {:.1}% exceeds {:.1}%",
+                rate * 100.0,
+                threshold * 100.0
+            ),
+            Self::UnhealthyWorkers { count, threshold } => {
+                write!(f, "{count} unhealthy workers exceed {threshold}")
+            }
+        }
+    }
+}
+
+/// Evidence lacking at the end of the bake period to trust the new template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum MissingEvidence {
+    /// The endpoint version did not increase.
+    VersionUnchanged {
+        /// Endpoint version at the last check.
+        version: i32,
+    },
+    /// No worker runs the new template.
+    NoUpdatedWorkers,
+    /// Too few jobs finished to judge the failure rate.
+    TooFewJobs {
+        /// Jobs finished since the update.
+        finished: u64,
+        /// Jobs required by the policy.
+        required: u64,
+    },
+}
+
+impl fmt::Display for MissingEvidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VersionUnchanged { version } => {
+                write!(f, "endpoint version {version} did not increase")
+            }
+            Self::NoUpdatedWorkers => f.write_str("no worker runs the new template"),
+            Self::TooFewJobs { finished, required } => {
+                write!(f, "{finished} jobs finished, {required} required")
+            }
+        }
+    }
+}
+
+/// How a rollout ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum RolloutOutcome {
+    /// The endpoint stayed within the thresholds for the whole bake period
+    /// and the last check showed the new template serving jobs.
+    Completed,
+    /// The endpoint stayed within the thresholds, but the last check lacked
+    /// evidence that the new template serves jobs. The endpoint is left on
+    /// the new template.
+    Inconclusive {
+        /// The missing evidence.
+        missing: MissingEvidence,
+    },
+    /// A threshold was breached and the previous template was restored.
+    RolledBack {
+        /// The breached threshold.
+        breach: RolloutBreach,
+    },
+    /// A threshold was breached and rollback is disabled by the policy.
+    Breached {
+        /// The breached threshold.
+        breach: RolloutBreach,
+    },
+    /// A request failed during the bake period.
+    Aborted {
+        /// The error of the failed request, and of the rollback if it failed too.
+        error: String,
+        /// Whether the previous template was restored.
+        rolled_back: bool,
+    },
+}
+
+impl RolloutOutcome {
+    /// Returns `true` if the endpoint runs the new template after the rollout.
+    pub fn is_completed(&self) -> bool {
+        matches!(self, Self::Completed)
+    }
+
+    /// Returns `true` if the previous template was restored.
+    pub fn is_rolled_back(&self) -> bool {
+        matches!(
+            self,
+            Self::RolledBack { .. }
+                | Self::Aborted {
+                    rolled_back: true,
+                    ..
+                }
+        )
+    }
+}
+
+/// Result of a [`Rollout`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutReport {
+    /// Endpoint ID.
+    pub endpoint_id: String,
+
+    /// Template the endpoint used before the rollout.
+    pub previous_template_id: String,
+
+    /// Template created by the rollout.
+    pub template_id: String,
+
+    /// Endpoint version before the rollout.
+    pub previous_version: i32,
+
+    /// Time the rollout started.
+    pub started_at: DateTime<Utc>,
+
+    /// Time the rollout finished.
+    pub finished_at: DateTime<Utc>,
+
+    /// Checks made during the bake period, oldest first.
+    pub checks: Vec<RolloutCheck>,
+
+    /// How the rollout ended.
+    pub outcome: RolloutOutcome,
+}
+
+/// Rolls out new template versions to serverless endpoints.
+///
+/// Requires the `rollout` feature, as waiting between checks uses the Tokio
+/// timer.
+#[derive(Debug, Clone)]
+pub struct Rollout<C = RunpodClient> {
+    client: C,
+    policy: RolloutPolicy,
+}
+
+impl<C> Rollout<C> {
+    /// Creates a rollout with the default policy.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            policy: RolloutPolicy::default(),
+        }
+    }
+
+    /// Replaces the rollout policy.
+    pub fn with_policy(mut self, policy: RolloutPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the rollout policy.
+    pub fn policy(&self) -> &RolloutPolicy {
+        &self.policy
+    }
+}
+
+impl<C> Rollout<C>
+where
+    C: EndpointsService + TemplatesService + ServerlessService,
+{
+    /// Rolls out a copy of the endpoint's current template using `image_name`.
+    ///
+    /// The new template is named after the current one with a timestamp suffix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint's template cannot be fetched, or for
+    /// the reasons listed on [`run`](Self::run).
+    pub async fn update_image(&self, endpoint_id: &str, image_name: &str) -> Result<RolloutReport> {
+        let endpoint = self.get_endpoint(endpoint_id, false).await?;
+        let template = self
+            .client
+            .get_template(&endpoint.template_id, GetTemplateQuery::default())
+            .await?;
+
+        let mut input = TemplateCreateInput::from_template(&template);
+        input.image_name = image_name.to_string();
+        input.name = format!("{}-{}", template.name, Utc::now().format("%Y%m%d%H%M%S"));
+        self.run(endpoint_id, input).await
+    }
+
+    /// Creates a template from `input`, switches the endpoint to it and bakes it.
+    ///
+    /// The template name must be unused. After the bake period the previous
+    /// template is kept, so that the rollout can be reverted by hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails before the endpoint is updated.
+    /// Failures during the bake period end the rollout with
+    /// [`RolloutOutcome::Aborted`] instead, after switching back to the
+    /// previous template if the policy allows it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, input)))]
+    pub async fn run(
+        &self,
+        endpoint_id: &str,
+        input: TemplateCreateInput,
+    ) -> Result<RolloutReport> {
+        let started_at = Utc::now();
+        let endpoint = self.get_endpoint(endpoint_id, false).await?;
+        let previous_template_id = endpoint.template_id.clone();
+
+        let template = self.client.create_template(input).await?;
+        let baseline = self.client.get_endpoint_health(endpoint_id).await?.jobs;
+        self.switch(endpoint_id, &template.id).await?;
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(template_id = %template.id, "Switched endpoint to new template");
+
+        let mut checks = Vec::new();
+        let baked = self
+            .bake(&endpoint, &template.id, &baseline, &mut checks)
+            .await;
+        let outcome = match baked {
+            Ok(outcome) => outcome,
+            Err(e) => self.abort(endpoint_id, &previous_template_id, e).await,
+        };
+
+        Ok(RolloutReport {
+            endpoint_id: endpoint_id.to_string(),
+            previous_template_id,
+            template_id: template.id,
+            previous_version: endpoint.version,
+            started_at,
+            finished_at: Utc::now(),
+            checks,
+            outcome,
+        })
+    }
+
+    /// Watches the endpoint until the bake period ends or a threshold is breached.
+    async fn bake(
+        &self,
+        previous: &Endpoint,
+        template_id: &str,
+        baseline: &JobCounts,
+        checks: &mut Vec<RolloutCheck>,
+    ) -> Result<RolloutOutcome> {
+        let deadline = tokio::time::Instant::now() + self.policy.bake_time;
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            tokio::time::sleep(self.policy.check_interval.min(remaining)).await;
+
+            let current = self.get_endpoint(&previous.id, true).await?;
+            let health = self.client.get_endpoint_health(&previous.id).await?;
+            let check = RolloutCheck::new(Utc::now(), &current, &health, baseline, template_id);
+            let breach = self.policy.evaluate(&check);
+            let missing = self.policy.missing_evidence(previous.version, &check);
+            checks.push(check);
+
+            if let Some(breach) = breach {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%breach, "Rollout threshold breached");
+
+                if !self.policy.rollback {
+                    return Ok(RolloutOutcome::Breached { breach });
+                }
+                self.switch(&previous.id, &previous.template_id).await?;
+                return Ok(RolloutOutcome::RolledBack { breach });
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Ok(match missing {
+                    Some(missing) => RolloutOutcome::Inconclusive { missing },
+                    None => RolloutOutcome::Completed,
+                });
+            }
+        }
+    }
+
+    /// Ends a rollout whose bake failed with `error`, switching back if allowed.
+    async fn abort(
+        &self,
+        endpoint_id: &str,
+        previous_template_id: &str,
+        error: crate::Error,
+    ) -> RolloutOutcome {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(%error, "Rollout request failed");
+
+        let mut error = error.to_string();
+        let rolled_back = match self.policy.rollback {
+            true => match self.switch(endpoint_id, previous_template_id).await {
+                Ok(_) => true,
+                Err(e) => {
+                    error = format!("{error}; rollback failed: {e}");
+                    false
+                }
+            },
+            false => false,
+        };
+        RolloutOutcome::Aborted { error, rolled_back }
+    }
+
+    async fn get_endpoint(&self, endpoint_id: &str, include_workers: bool) -> Result<Endpoint> {
+        let query = GetEndpointQuery {
+            include_workers: Some(include_workers),
+            ..Default::default()
+        };
+        self.client.get_endpoint(endpoint_id, query).await
+    }
+
+    async fn switch(&self, endpoint_id: &str, template_id: &str) -> Result<Endpoint> {
+        let input = EndpointUpdateInput {
+            template_id: Some(template_id.to_string()),
+            ..Default::default()
+        };
+        self.client.update_endpoint(endpoint_id, input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{self, Cloud};
+
+    fn check(completed: u64, failed: u64, unhealthy: u64) -> RolloutCheck {
+        RolloutCheck {
+            at: Utc.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap(),
+            version: 2,
+            workers: 2,
+            updated_workers: 1,
+            unhealthy_workers: unhealthy,
+            jobs_completed: completed,
+            jobs_failed: failed,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_thresholds() {
+        let policy = RolloutPolicy::default();
+
+        assert_eq!(policy.evaluate(&check(95, 5, 0)), None);
+        assert_eq!(policy.evaluate(&check(2, 3, 0)), None);
+        assert_eq!(
+            policy.evaluate(&check(90, 10, 0)),
+            Some(RolloutBreach::FailureRate {
+                rate: 0.1,
+                threshold: 0.05
+            })
+        );
+        assert_eq!(
+            policy.evaluate(&check(0, 0, 1)),
+            Some(RolloutBreach::UnhealthyWorkers {
+                count: 1,
+                threshold: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_evidence() {
+        let policy = RolloutPolicy::default();
+
+        assert_eq!(policy.missing_evidence(1, &check(10, 0, 0)), None);
+        assert_eq!(
+            policy.missing_evidence(2, &check(10, 0, 0)),
+            Some(MissingEvidence::VersionUnchanged { version: 2 })
+        );
+        assert_eq!(
+            policy.missing_evidence(1, &check(6, 3, 0)),
+            Some(MissingEvidence::TooFewJobs {
+                finished: 9,
+                required: 10
+            })
+        );
+
+        let idle = RolloutCheck {
+            updated_workers: 0,
+            ..check(10, 0, 0)
+        };
+        assert_eq!(
+            policy.missing_evidence(1, &idle),
+            Some(MissingEvidence::NoUpdatedWorkers)
+        );
+    }
+
+    fn health(completed: u64, failed: u64) -> EndpointHealth {
+        serde_json::from_value(json!({
+            "jobs": { "completed": completed, "failed": failed },
+            "workers": { "running": 1 },
+        }))
+        .unwrap()
+    }
+
+    fn rollout(cloud: Cloud) -> Rollout<Cloud> {
+        Rollout::new(cloud).with_policy(RolloutPolicy {
+            bake_time: Duration::from_millis(5),
+            check_interval: Duration::from_millis(1),
+            ..Default::default()
+        })
+    }
+
+    fn input() -> TemplateCreateInput {
+        TemplateCreateInput::from_template(&testing::template(json!({ "name": "app-v2" })))
+    }
+
+    #[tokio::test]
+    async fn test_completes_once_updated_workers_serve_jobs() -> Result<()> {
+        let cloud = Cloud::new()
+            .with_endpoint(testing::endpoint(json!({ "workersMin": 1 })))
+            .with_health("endpoint", [health(100, 0), health(120, 1)]);
+        let rollout = rollout(cloud);
+
+        let report = rollout.run("endpoint", input()).await?;
+        assert_eq!(report.outcome, RolloutOutcome::Completed);
+        assert_eq!(report.template_id, "template-1");
+        let last = report.checks.last().unwrap();
+        assert_eq!((last.version, last.updated_workers), (2, 1));
+        assert_eq!((last.jobs_completed, last.jobs_failed), (20, 1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_inconclusive_without_updated_workers() -> Result<()> {
+        let cloud = Cloud::new()
+            .with_endpoint(testing::endpoint(json!({})))
+            .with_health("endpoint", [health(100, 0), health(120, 0)]);
+        let rollout = rollout(cloud);
+
+        let report = rollout.run("endpoint", input()).await?;
+        assert_eq!(
+            report.outcome,
+            RolloutOutcome::Inconclusive {
+                missing: MissingEvidence::NoUpdatedWorkers
+            }
+        );
+        assert!(!report.outcome.is_completed());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rolls_back_when_bake_request_fails() -> Result<()> {
+        let cloud = Cloud::new()
+            .with_endpoint(testing::endpoint(json!({ "workersMin": 1 })))
+            .with_health("endpoint", [health(100, 0)])
+            .failing("get_endpoint_health endpoint", 1);
+        let rollout = rollout(cloud);
+
+        let report = rollout.run("endpoint", input()).await?;
+        assert!(report.checks.is_empty());
+        assert!(report.outcome.is_rolled_back());
+        let RolloutOutcome::Aborted { error, .. } = &report.outcome else {
+            panic!("unexpected outcome: {:?}", report.outcome);
+        };
+        assert!(error.contains("get_endpoint_health endpoint failed"));
+
+        let endpoint = rollout.client.endpoint("endpoint").unwrap();
+        assert_eq!(endpoint.template_id, "template");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_counts_jobs_since_update() {
+        let endpoint = testing::endpoint(json!({
+            "templateId": "new",
+            "version": 3,
+            "workers": [],
+        }));
+        let health: EndpointHealth = serde_json::from_value(json!({
+            "jobs": {"completed": 120, "failed": 9},
+            "workers": {"running": 2, "unhealthy": 1},
+        }))
+        .unwrap();
+        let baseline = JobCounts {
+            completed: 100,
+            failed: 4,
+            ..Default::default()
+        };
+
+        let check = RolloutCheck::new(Utc::now(), &endpoint, &health, &baseline, "new");
+        assert_eq!(check.version, 3);
+        assert_eq!((check.jobs_completed, check.jobs_failed), (20, 5));
+        assert_eq!(check.failure_rate(), 0.2);
+        assert_eq!(check.unhealthy_workers, 1);
+    }
+}
@@ -0,0 +1,509 @@
+//! Client-side blue/green routing of serverless jobs.
+//!
+//! A [`JobRouter`] sends jobs to a primary endpoint and duplicates a share of
+//! them to a candidate endpoint, so that a new deployment can be validated
+//! against production traffic before cutting over. Callers only ever receive
+//! the primary's result, without waiting for the candidate; the candidate's
+//! result is compared with it by an [`OutputComparator`] once the returned
+//! shadow future is awaited, and then discarded.
+//!
+//! The router records request, error and latency metrics per endpoint, and
+//! the number of matching and mismatching outputs. Once the candidate is
+//! trusted, [`JobRouter::promote`] makes it the primary. Routes are swapped
+//! atomically: every job is routed with a consistent snapshot of them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::model::v1::RunJobInput;
+//! use runpod_sdk::router::JobRouter;
+//! use runpod_sdk::{Result, RunpodClient};
+//! use serde_json::{Value, json};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let router = JobRouter::new(client, "blue")
+//!     .with_candidate("green", 10.0)
+//!     .with_comparator(|primary: &Value, candidate: &Value| {
+//!         primary["label"] == candidate["label"]
+//!     });
+//!
+//! let (job, shadow) = router
+//!     .run_job_sync(RunJobInput::new(json!({"prompt": "Hello"})))
+//!     .await?;
+//! println!("Output: {:?}", job.output);
+//! if let Some(shadow) = shadow {
+//!     shadow.await;
+//! }
+//!
+//! let metrics = router.metrics();
+//! if metrics.comparisons.mismatched == 0 && metrics.comparisons.matched >= 100 {
+//!     router.promote();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use futures::future::{self, Either};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::v1::{Endpoint, GetEndpointQuery, JobState, JobStatus, RunJobInput};
+use crate::service::v1::{EndpointsService, ServerlessService};
+use crate::{Result, RunpodClient};
+
+/// Compares the output of the primary endpoint with the candidate's.
+///
+/// Implemented for `()`, which requires equal outputs, and for closures
+/// taking both outputs.
+pub trait OutputComparator {
+    /// Returns `true` if the candidate output is acceptable.
+    fn matches(&self, primary: &Value, candidate: &Value) -> bool;
+}
+
+impl OutputComparator for () {
+    fn matches(&self, primary: &Value, candidate: &Value) -> bool {
+        primary == candidate
+    }
+}
+
+impl<F> OutputComparator for F
+where
+    F: Fn(&Value, &Value) -> bool,
+{
+    fn matches(&self, primary: &Value, candidate: &Value) -> bool {
+        self(primary, candidate)
+    }
+}
+
+/// Endpoints a [`JobRouter`] sends jobs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Routes {
+    /// Endpoint whose results are returned to callers.
+    pub primary: String,
+
+    /// Endpoint receiving a copy of a share of the jobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate: Option<String>,
+
+    /// Percentage of jobs duplicated to the candidate, from 0 to 100.
+    pub shadow_percent: f64,
+}
+
+/// Request metrics of a single endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointMetrics {
+    /// Jobs sent to the endpoint.
+    pub requests: u64,
+
+    /// Jobs that failed or whose request returned an error.
+    pub errors: u64,
+
+    /// Sum of job latencies, including queue time.
+    pub total_latency: Duration,
+
+    /// Highest job latency.
+    pub max_latency: Duration,
+}
+
+impl EndpointMetrics {
+    /// Share of jobs that failed, from 0 to 1.
+    pub fn error_rate(&self) -> f64 {
+        match self.requests {
+            0 => 0.0,
+            requests => self.errors as f64 / requests as f64,
+        }
+    }
+
+    /// Mean job latency.
+    pub fn mean_latency(&self) -> Duration {
+        match u32::try_from(self.requests) {
+            Ok(0) | Err(_) => Duration::ZERO,
+            Ok(requests) => self.total_latency / requests,
+        }
+    }
+
+    fn record(&mut self, latency: Duration, failed: bool) {
+        self.requests += 1;
+        self.errors += u64::from(failed);
+        self.total_latency += latency;
+        self.max_latency = self.max_latency.max(latency);
+    }
+}
+
+/// Outcomes of comparing candidate outputs with primary outputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonMetrics {
+    /// Candidate outputs accepted by the comparator.
+    pub matched: u64,
+
+    /// Candidate outputs rejected by the comparator.
+    pub mismatched: u64,
+
+    /// Shadowed jobs not compared because either job did not complete.
+    pub skipped: u64,
+}
+
+/// Metrics recorded by a [`JobRouter`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterMetrics {
+    /// Request metrics by endpoint ID.
+    pub endpoints: BTreeMap<String, EndpointMetrics>,
+
+    /// Outcomes of output comparisons.
+    pub comparisons: ComparisonMetrics,
+}
+
+impl RouterMetrics {
+    /// Returns the metrics of `endpoint_id`, if it received jobs.
+    pub fn endpoint(&self, endpoint_id: &str) -> Option<&EndpointMetrics> {
+        self.endpoints.get(endpoint_id)
+    }
+}
+
+/// Routes serverless jobs to a primary endpoint and shadows them to a candidate.
+///
+/// The router is shared by reference between tasks; wrap it in an `Arc` to
+/// share it between threads.
+pub struct JobRouter<C = RunpodClient, K = ()> {
+    client: C,
+    comparator: K,
+    routes: RwLock<Routes>,
+    metrics: Mutex<RouterMetrics>,
+    shadowed: Mutex<f64>,
+}
+
+impl<C> JobRouter<C> {
+    /// Creates a router sending every job to `primary` only.
+    pub fn new(client: C, primary: impl Into<String>) -> Self {
+        Self {
+            client,
+            comparator: (),
+            routes: RwLock::new(Routes {
+                primary: primary.into(),
+                candidate: None,
+                shadow_percent: 0.0,
+            }),
+            metrics: Mutex::new(RouterMetrics::default()),
+            shadowed: Mutex::new(0.0),
+        }
+    }
+
+    /// Creates a router sending every job to `endpoint` only.
+    pub fn from_endpoint(client: C, endpoint: &Endpoint) -> Self {
+        Self::new(client, endpoint.id.clone())
+    }
+}
+
+impl<C, K> JobRouter<C, K> {
+    /// Duplicates `percent` of the jobs, from 0 to 100, to `candidate`.
+    pub fn with_candidate(self, candidate: impl Into<String>, percent: f64) -> Self {
+        self.set_candidate(candidate, percent);
+        self
+    }
+
+    /// Replaces the output comparator.
+    pub fn with_comparator<L: OutputComparator>(self, comparator: L) -> JobRouter<C, L> {
+        JobRouter {
+            client: self.client,
+            comparator,
+            routes: self.routes,
+            metrics: self.metrics,
+            shadowed: self.shadowed,
+        }
+    }
+
+    /// Returns a snapshot of the current routes.
+    pub fn routes(&self) -> Routes {
+        self.routes
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Returns a snapshot of the recorded metrics.
+    pub fn metrics(&self) -> RouterMetrics {
+        self.metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Clears the recorded metrics.
+    pub fn reset_metrics(&self) {
+        *self.metrics.lock().unwrap_or_else(|e| e.into_inner()) = RouterMetrics::default();
+    }
+
+    /// Starts duplicating `percent` of the jobs, from 0 to 100, to `candidate`.
+    pub fn set_candidate(&self, candidate: impl Into<String>, percent: f64) {
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        routes.candidate = Some(candidate.into());
+        routes.shadow_percent = percent.clamp(0.0, 100.0);
+    }
+
+    /// Stops duplicating jobs.
+    pub fn clear_candidate(&self) {
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        routes.candidate = None;
+        routes.shadow_percent = 0.0;
+    }
+
+    /// Makes the candidate the primary, and the primary the candidate.
+    ///
+    /// The shadow percentage is kept, so the previous primary keeps receiving
+    /// copies of jobs and can be switched back to. Returns the new routes, or
+    /// `None` without changes if there is no candidate.
+    pub fn promote(&self) -> Option<Routes> {
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        let candidate = routes.candidate.take()?;
+        let previous = std::mem::replace(&mut routes.primary, candidate);
+        routes.candidate = Some(previous);
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(primary = %routes.primary, "Promoted candidate endpoint");
+
+        Some(routes.clone())
+    }
+
+    /// Sends every job to `primary` from now on, returning the previous primary.
+    pub fn switch_primary(&self, primary: impl Into<String>) -> String {
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        let primary = primary.into();
+        if routes.candidate.as_deref() == Some(primary.as_str()) {
+            routes.candidate = None;
+        }
+        std::mem::replace(&mut routes.primary, primary)
+    }
+
+    /// Decides whether the next job is duplicated, spreading shadowed jobs evenly.
+    fn should_shadow(&self, percent: f64) -> bool {
+        let mut shadowed = self.shadowed.lock().unwrap_or_else(|e| e.into_inner());
+        *shadowed += percent / 100.0;
+        if *shadowed >= 1.0 {
+            *shadowed -= 1.0;
+            return true;
+        }
+        false
+    }
+
+    fn record(&self, endpoint_id: &str, latency: Duration, result: &Result<JobStatus>) {
+        let failed = match result {
+            Ok(job) => matches!(job.status, JobState::Failed | JobState::TimedOut),
+            Err(_) => true,
+        };
+
+        let mut metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        let endpoint = metrics
+            .endpoints
+            .entry(endpoint_id.to_string())
+            .or_default();
+        endpoint.record(latency, failed);
+    }
+}
+
+impl<C: ServerlessService, K: OutputComparator> JobRouter<C, K> {
+    /// Runs a job on the primary endpoint and waits for its result.
+    ///
+    /// If the job is selected for shadowing, it also runs on the candidate
+    /// endpoint concurrently, and the primary's result is returned as soon as
+    /// it arrives, together with a future finishing the shadow job. That
+    /// future waits for the candidate's result and compares both outputs; it
+    /// does nothing unless awaited, so callers can respond with the primary's
+    /// result first and await it afterwards. The candidate's result never
+    /// affects the returned one.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the request to the primary endpoint. The shadow
+    /// job is then abandoned and its comparison counted as skipped.
+    pub async fn run_job_sync(
+        &self,
+        input: RunJobInput,
+    ) -> Result<(JobStatus, Option<impl Future<Output = ()> + '_>)> {
+        let routes = self.routes();
+        let candidate = routes
+            .candidate
+            .filter(|_| self.should_shadow(routes.shadow_percent));
+
+        let primary = Box::pin(self.timed(routes.primary, input.clone()));
+        let Some(candidate) = candidate else {
+            return Ok((primary.await?, None));
+        };
+
+        let shadow = Box::pin(self.timed(candidate.clone(), input));
+        let (primary, shadow) = match future::select(primary, shadow).await {
+            Either::Left((primary, shadow)) => (primary, Either::Left(shadow)),
+            Either::Right((shadow, primary)) => {
+                (primary.await, Either::Right(future::ready(shadow)))
+            }
+        };
+
+        let primary = match primary {
+            Ok(primary) => primary,
+            Err(e) => {
+                self.compare(None, None);
+                return Err(e);
+            }
+        };
+
+        let expected = (primary.status == JobState::Completed)
+            .then(|| primary.output.clone().unwrap_or(Value::Null));
+        let compare = async move {
+            let shadow = shadow.await.ok();
+            if !self.compare(expected, shadow) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(candidate, "Candidate output does not match primary");
+            }
+        };
+
+        Ok((primary, Some(compare)))
+    }
+
+    /// Compares the candidate's result with the primary's completed output.
+    ///
+    /// Returns `false` if the comparator rejected the candidate's output.
+    fn compare(&self, expected: Option<Value>, shadow: Option<JobStatus>) -> bool {
+        let outputs = match (expected, shadow) {
+            (Some(expected), Some(shadow)) if shadow.status == JobState::Completed => {
+                Some((expected, shadow.output.unwrap_or(Value::Null)))
+            }
+            _ => None,
+        };
+
+        let mut metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        let comparisons = &mut metrics.comparisons;
+        match outputs {
+            Some((primary, shadow)) => match self.comparator.matches(&primary, &shadow) {
+                true => comparisons.matched += 1,
+                false => {
+                    comparisons.mismatched += 1;
+                    return false;
+                }
+            },
+            None => comparisons.skipped += 1,
+        }
+        true
+    }
+
+    async fn timed(&self, endpoint_id: String, input: RunJobInput) -> Result<JobStatus> {
+        let started = Instant::now();
+        let result = self.client.run_job_sync(&endpoint_id, input).await;
+        self.record(&endpoint_id, started.elapsed(), &result);
+        result
+    }
+}
+
+impl<C: EndpointsService, K> JobRouter<C, K> {
+    /// Fetches the primary and candidate endpoints, checking that both exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either endpoint cannot be fetched.
+    pub async fn endpoints(&self) -> Result<(Endpoint, Option<Endpoint>)> {
+        let routes = self.routes();
+        let primary = self
+            .client
+            .get_endpoint(&routes.primary, GetEndpointQuery::default())
+            .await?;
+        let candidate = match &routes.candidate {
+            Some(candidate) => Some(
+                self.client
+                    .get_endpoint(candidate, GetEndpointQuery::default())
+                    .await?,
+            ),
+            None => None,
+        };
+        Ok((primary, candidate))
+    }
+}
+
+impl<C: fmt::Debug, K> fmt::Debug for JobRouter<C, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobRouter")
+            .field("client", &self.client)
+            .field("routes", &self.routes())
+            .field("metrics", &self.metrics())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::Cloud;
+
+    #[test]
+    fn test_should_shadow_spreads_jobs() {
+        let router = JobRouter::new((), "blue");
+        let shadowed = (0..100).filter(|_| router.should_shadow(25.0)).count();
+        assert_eq!(shadowed, 25);
+        assert!(!(0..100).any(|_| router.should_shadow(0.0)));
+    }
+
+    #[tokio::test]
+    async fn test_shadows_and_promotes() {
+        let router = JobRouter::new(Cloud::new(), "blue")
+            .with_candidate("green", 50.0)
+            .with_comparator(|a: &Value, b: &Value| a["x"] == b["x"]);
+
+        for x in 0..4 {
+            let (job, shadow) = router
+                .run_job_sync(RunJobInput::new(json!({ "x": x })))
+                .await
+                .unwrap();
+            assert_eq!(job.output.unwrap()["v"], "blue");
+            if let Some(shadow) = shadow {
+                shadow.await;
+            }
+        }
+
+        let metrics = router.metrics();
+        assert_eq!(metrics.endpoint("blue").unwrap().requests, 4);
+        assert_eq!(metrics.endpoint("green").unwrap().requests, 2);
+        assert_eq!(metrics.comparisons.matched, 2);
+
+        let routes = router.promote().unwrap();
+        assert_eq!(routes.primary, "green");
+        assert_eq!(routes.candidate.as_deref(), Some("blue"));
+
+        let (job, _) = router
+            .run_job_sync(RunJobInput::new(json!({})))
+            .await
+            .unwrap();
+        assert_eq!(job.output.unwrap()["v"], "green");
+        assert_eq!(router.switch_primary("blue"), "green");
+        assert_eq!(router.routes().candidate, None);
+    }
+
+    #[tokio::test]
+    async fn test_returns_primary_before_shadow() {
+        let cloud = Cloud::new().with_delay("green", Duration::from_millis(50));
+        let router = JobRouter::new(cloud, "blue")
+            .with_candidate("green", 100.0)
+            .with_comparator(|a: &Value, b: &Value| a["x"] == b["x"]);
+
+        let (job, shadow) = router
+            .run_job_sync(RunJobInput::new(json!({ "x": 1 })))
+            .await
+            .unwrap();
+        assert_eq!(job.output.unwrap()["v"], "blue");
+        assert_eq!(router.metrics().endpoint("green"), None);
+        assert_eq!(router.metrics().comparisons.matched, 0);
+
+        shadow.unwrap().await;
+        let metrics = router.metrics();
+        assert_eq!(metrics.endpoint("green").unwrap().requests, 1);
+        assert_eq!(metrics.comparisons.matched, 1);
+    }
+}
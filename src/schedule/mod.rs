@@ -0,0 +1,592 @@
+//! Scheduled scaling of endpoint worker minimums.
+//!
+//! A [`ScalingScheduler`] keeps the `workers_min` of serverless endpoints in
+//! line with time-of-day [`ScheduleRule`]s, e.g. four warm workers during
+//! office hours and none overnight. Each run compares the desired minimum of
+//! every [`EndpointSchedule`] with the endpoint's current one and only updates
+//! endpoints that differ, so running it repeatedly is safe.
+//!
+//! Rules are written as `<days> <HH:MM>-<HH:MM> <workers_min>` in UTC:
+//!
+//! - days are `daily`, `weekdays`, `weekends`, a day such as `mon`, a range
+//!   such as `mon-fri`, or a comma-separated list such as `mon,wed,fri`;
+//! - the time range includes its start and excludes its end, and wraps past
+//!   midnight if the end is earlier than the start, e.g. `22:00-06:00`.
+//!
+//! Around rule boundaries the scheduler tolerates clock skew by scaling up
+//! early and down late. In dry-run mode nothing is updated, and the current
+//! time comes from a [`Clock`] that tests can replace.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::schedule::{EndpointSchedule, ScalingScheduler};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let schedule =
+//!     EndpointSchedule::new("endpoint_id", 0).with_rule("weekdays 08:00-20:00 4".parse()?);
+//! let scheduler = ScalingScheduler::new(client).with_schedule(schedule);
+//!
+//! for result in scheduler.run().await?.results {
+//!     println!("{}: {:?}", result.endpoint_id, result.outcome);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc, Weekday};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::model::v1::{EndpointUpdateInput, GetEndpointQuery};
+use crate::service::v1::EndpointsService;
+use crate::{Error, Result, RunpodClient};
+
+/// Source of the current time.
+///
+/// Implemented for [`SystemClock`] and for closures returning a time.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// [`Clock`] reading the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> DateTime<Utc>,
+{
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+/// A time window on some days of the week with a worker minimum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleRule {
+    /// Days the window starts on.
+    pub days: Vec<Weekday>,
+
+    /// Start of the window, in UTC.
+    pub start: NaiveTime,
+
+    /// End of the window, in UTC, excluded.
+    ///
+    /// Midnight, written `24:00`, ends the window at the end of the day it
+    /// starts on.
+    pub end: NaiveTime,
+
+    /// Worker minimum during the window.
+    pub workers_min: i32,
+}
+
+impl ScheduleRule {
+    /// Returns whether `time` falls within the window.
+    ///
+    /// A window wrapping past midnight belongs to the day it starts on.
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        let clock = time.time();
+        let today = time.weekday();
+        if self.start < self.end {
+            return self.days.contains(&today) && clock >= self.start && clock < self.end;
+        }
+        (self.days.contains(&today) && clock >= self.start)
+            || (self.days.contains(&today.pred()) && clock < self.end)
+    }
+}
+
+impl FromStr for ScheduleRule {
+    type Err = Error;
+
+    fn from_str(rule: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| Error::Validation(format!("invalid schedule rule {rule:?}: {reason}"));
+        let parts: Vec<&str> = rule.split_whitespace().collect();
+        let [days, window, workers_min] = parts[..] else {
+            return Err(invalid("expected `<days> <HH:MM>-<HH:MM> <workers_min>`"));
+        };
+
+        let days = parse_days(days).ok_or_else(|| invalid("unknown days"))?;
+        let (start, end, end_of_day) = window
+            .split_once('-')
+            .and_then(|(start, end)| Some((parse_time(start)?, parse_time(end)?, end == "24:00")))
+            .ok_or_else(|| invalid("expected a `HH:MM-HH:MM` time range"))?;
+        if start == end && !end_of_day {
+            return Err(invalid("empty time range"));
+        }
+        let workers_min = workers_min
+            .trim_start_matches("workers_min=")
+            .parse()
+            .ok()
+            .filter(|min: &i32| *min >= 0)
+            .ok_or_else(|| invalid("expected a non-negative worker minimum"))?;
+
+        Ok(Self {
+            days,
+            start,
+            end,
+            workers_min,
+        })
+    }
+}
+
+impl fmt::Display for ScheduleRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days: Vec<String> = self
+            .days
+            .iter()
+            .map(|day| day.to_string().to_ascii_lowercase())
+            .collect();
+        let end = match self.end == NaiveTime::MIN {
+            true => "24:00".to_string(),
+            false => self.end.format("%H:%M").to_string(),
+        };
+        write!(
+            f,
+            "{} {}-{end} {}",
+            days.join(","),
+            self.start.format("%H:%M"),
+            self.workers_min
+        )
+    }
+}
+
+/// Worker minimum rules of a single endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointSchedule {
+    /// Endpoint ID.
+    pub endpoint_id: String,
+
+    /// Rules in order of precedence; the first matching rule applies.
+    pub rules: Vec<ScheduleRule>,
+
+    /// Worker minimum outside of all rules.
+    pub default_workers_min: i32,
+}
+
+impl EndpointSchedule {
+    /// Creates a schedule without rules.
+    pub fn new(endpoint_id: impl Into<String>, default_workers_min: i32) -> Self {
+        Self {
+            endpoint_id: endpoint_id.into(),
+            rules: Vec::new(),
+            default_workers_min,
+        }
+    }
+
+    /// Adds a rule with lower precedence than the existing ones.
+    pub fn with_rule(mut self, rule: ScheduleRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Returns the worker minimum at `time`.
+    pub fn workers_min_at(&self, time: DateTime<Utc>) -> i32 {
+        self.rules
+            .iter()
+            .find(|rule| rule.contains(time))
+            .map_or(self.default_workers_min, |rule| rule.workers_min)
+    }
+
+    /// Returns the worker minimum at `time`, tolerating `skew` in either direction.
+    ///
+    /// The higher of the minimums at `time - skew` and `time + skew` wins, so
+    /// workers are added early and removed late.
+    pub fn desired_at(&self, time: DateTime<Utc>, skew: Duration) -> i32 {
+        let skew = chrono::Duration::from_std(skew).unwrap_or_default();
+        self.workers_min_at(time - skew)
+            .max(self.workers_min_at(time + skew))
+    }
+}
+
+/// Settings of a [`ScalingScheduler`].
+#[derive(Debug, Clone)]
+pub struct SchedulerPolicy {
+    /// Tolerated difference between the local clock and the schedule.
+    pub skew_tolerance: Duration,
+
+    /// Report changes without updating endpoints.
+    pub dry_run: bool,
+
+    /// Maximum number of requests in flight. Values below 1 are treated as 1.
+    pub concurrency: usize,
+}
+
+impl Default for SchedulerPolicy {
+    fn default() -> Self {
+        Self {
+            skew_tolerance: Duration::from_secs(60),
+            dry_run: false,
+            concurrency: 4,
+        }
+    }
+}
+
+/// What a scheduler run did to an endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduleAction {
+    /// The endpoint already had the desired worker minimum.
+    Unchanged,
+    /// The worker minimum would be updated, in dry-run mode.
+    Planned,
+    /// The worker minimum was updated.
+    Updated,
+}
+
+/// Outcome of applying a schedule to a single endpoint.
+#[derive(Debug)]
+pub struct ScheduleResult {
+    /// Endpoint ID.
+    pub endpoint_id: String,
+
+    /// Worker minimum before the run, if the endpoint could be fetched.
+    pub previous: Option<i32>,
+
+    /// Worker minimum desired by the schedule.
+    pub desired: i32,
+
+    /// What was done, or the error returned by the API.
+    pub outcome: Result<ScheduleAction>,
+}
+
+/// Result of a [`ScalingScheduler::run`].
+#[derive(Debug)]
+pub struct ScheduleReport {
+    /// Time the schedules were evaluated at.
+    pub at: DateTime<Utc>,
+
+    /// Whether the run was a dry run.
+    pub dry_run: bool,
+
+    /// Per-endpoint results, in schedule order.
+    pub results: Vec<ScheduleResult>,
+}
+
+impl ScheduleReport {
+    /// Returns `true` if no request failed.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.outcome.is_ok())
+    }
+}
+
+/// Applies [`EndpointSchedule`]s to endpoints.
+///
+/// Run it periodically, e.g. every minute; every run is idempotent.
+pub struct ScalingScheduler<C = RunpodClient, K = SystemClock> {
+    client: C,
+    clock: K,
+    policy: SchedulerPolicy,
+    schedules: Vec<EndpointSchedule>,
+}
+
+impl<C> ScalingScheduler<C> {
+    /// Creates a scheduler with the default policy, the system clock and no schedules.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            clock: SystemClock,
+            policy: SchedulerPolicy::default(),
+            schedules: Vec::new(),
+        }
+    }
+}
+
+impl<C, K> ScalingScheduler<C, K> {
+    /// Replaces the scheduler policy.
+    pub fn with_policy(mut self, policy: SchedulerPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Replaces the clock.
+    pub fn with_clock<L: Clock>(self, clock: L) -> ScalingScheduler<C, L> {
+        ScalingScheduler {
+            client: self.client,
+            clock,
+            policy: self.policy,
+            schedules: self.schedules,
+        }
+    }
+
+    /// Adds the schedule of an endpoint.
+    pub fn with_schedule(mut self, schedule: EndpointSchedule) -> Self {
+        self.schedules.push(schedule);
+        self
+    }
+
+    /// Returns the scheduler policy.
+    pub fn policy(&self) -> &SchedulerPolicy {
+        &self.policy
+    }
+
+    /// Returns the endpoint schedules.
+    pub fn schedules(&self) -> &[EndpointSchedule] {
+        &self.schedules
+    }
+}
+
+impl<C, K> ScalingScheduler<C, K>
+where
+    C: EndpointsService,
+    K: Clock,
+{
+    /// Updates every endpoint whose worker minimum differs from its schedule.
+    ///
+    /// # Errors
+    ///
+    /// Failures to fetch or update individual endpoints are reported in
+    /// [`ScheduleReport::results`]; the returned `Result` is reserved for
+    /// failures affecting the whole run.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn run(&self) -> Result<ScheduleReport> {
+        let at = self.clock.now();
+        let results = stream::iter(&self.schedules)
+            .map(|schedule| self.apply(schedule, at))
+            .buffered(self.policy.concurrency.max(1))
+            .collect()
+            .await;
+
+        Ok(ScheduleReport {
+            at,
+            dry_run: self.policy.dry_run,
+            results,
+        })
+    }
+
+    async fn apply(&self, schedule: &EndpointSchedule, at: DateTime<Utc>) -> ScheduleResult {
+        let endpoint_id = &schedule.endpoint_id;
+        let desired = schedule.desired_at(at, self.policy.skew_tolerance);
+        let result = |previous, outcome| ScheduleResult {
+            endpoint_id: endpoint_id.clone(),
+            previous,
+            desired,
+            outcome,
+        };
+
+        let endpoint = match self
+            .client
+            .get_endpoint(endpoint_id, GetEndpointQuery::default())
+            .await
+        {
+            Ok(endpoint) => endpoint,
+            Err(e) => return result(None, Err(e)),
+        };
+        let previous = Some(endpoint.workers_min);
+        if endpoint.workers_min == desired {
+            return result(previous, Ok(ScheduleAction::Unchanged));
+        }
+        if self.policy.dry_run {
+            return result(previous, Ok(ScheduleAction::Planned));
+        }
+
+        let input = EndpointUpdateInput {
+            workers_min: Some(desired),
+            ..Default::default()
+        };
+        let outcome = self.client.update_endpoint(endpoint_id, input).await;
+
+        #[cfg(feature = "tracing")]
+        match &outcome {
+            Ok(_) => tracing::info!(%endpoint_id, desired, "Updated endpoint worker minimum"),
+            Err(e) => tracing::warn!(%endpoint_id, error = %e, "Updating worker minimum failed"),
+        }
+
+        result(previous, outcome.map(|_| ScheduleAction::Updated))
+    }
+}
+
+impl<C: fmt::Debug, K: fmt::Debug> fmt::Debug for ScalingScheduler<C, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScalingScheduler")
+            .field("client", &self.client)
+            .field("clock", &self.clock)
+            .field("policy", &self.policy)
+            .field("schedules", &self.schedules)
+            .finish()
+    }
+}
+
+fn parse_days(days: &str) -> Option<Vec<Weekday>> {
+    use Weekday::*;
+    let all = [Mon, Tue, Wed, Thu, Fri, Sat, Sun];
+    let range = |from: Weekday, to: Weekday| {
+        let (from, to) = (from.num_days_from_monday(), to.num_days_from_monday());
+        let days: Vec<Weekday> = match from <= to {
+            true => all[from as usize..=to as usize].to_vec(),
+            false => all[from as usize..]
+                .iter()
+                .chain(&all[..=to as usize])
+                .copied()
+                .collect(),
+        };
+        days
+    };
+
+    match days.to_ascii_lowercase().as_str() {
+        "daily" | "*" => return Some(all.to_vec()),
+        "weekdays" => return Some(range(Mon, Fri)),
+        "weekends" => return Some(range(Sat, Sun)),
+        _ => {}
+    }
+
+    let mut parsed = Vec::new();
+    for part in days.split(',') {
+        let days = match part.split_once('-') {
+            Some((from, to)) => range(from.parse().ok()?, to.parse().ok()?),
+            None => vec![part.parse().ok()?],
+        };
+        for day in days {
+            if !parsed.contains(&day) {
+                parsed.push(day);
+            }
+        }
+    }
+    Some(parsed)
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    match time {
+        "24:00" => NaiveTime::from_hms_opt(0, 0, 0),
+        _ => NaiveTime::parse_from_str(time, "%H:%M")
+            .ok()
+            .filter(|time| time.second() == 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{self, Cloud};
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2025-01-06 is a Monday.
+        Utc.with_ymd_and_hms(2025, 1, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rule: ScheduleRule = "weekdays 08:00-20:00 workers_min=4".parse().unwrap();
+        assert_eq!(
+            rule.days,
+            vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri
+            ]
+        );
+        assert_eq!(rule.workers_min, 4);
+        assert_eq!(rule.to_string(), "mon,tue,wed,thu,fri 08:00-20:00 4");
+
+        let rule: ScheduleRule = "fri-mon 22:00-06:00 1".parse().unwrap();
+        assert_eq!(
+            rule.days,
+            vec![Weekday::Fri, Weekday::Sat, Weekday::Sun, Weekday::Mon]
+        );
+
+        let rule: ScheduleRule = "daily 00:00-24:00 4".parse().unwrap();
+        assert!(rule.contains(at(6, 0, 0)));
+        assert!(rule.contains(at(12, 23, 59)));
+        assert_eq!(rule.to_string().parse::<ScheduleRule>().unwrap(), rule);
+
+        let rule: ScheduleRule = "sat 20:00-24:00 1".parse().unwrap();
+        assert_eq!(rule.to_string(), "sat 20:00-24:00 1");
+        assert!(rule.contains(at(11, 23, 0)));
+        assert!(!rule.contains(at(12, 0, 0)));
+
+        assert!("weekdays 08:00 4".parse::<ScheduleRule>().is_err());
+        assert!("someday 08:00-20:00 4".parse::<ScheduleRule>().is_err());
+        assert!("daily 08:00-08:00 4".parse::<ScheduleRule>().is_err());
+        assert!("daily 00:00-00:00 4".parse::<ScheduleRule>().is_err());
+        assert!("daily 08:00-20:00 -1".parse::<ScheduleRule>().is_err());
+    }
+
+    #[test]
+    fn test_rules_match_windows() {
+        let office: ScheduleRule = "weekdays 08:00-20:00 4".parse().unwrap();
+        assert!(office.contains(at(6, 8, 0)));
+        assert!(!office.contains(at(6, 20, 0)));
+        assert!(!office.contains(at(11, 12, 0)));
+
+        let night: ScheduleRule = "fri 22:00-06:00 2".parse().unwrap();
+        assert!(night.contains(at(10, 23, 0)));
+        assert!(night.contains(at(11, 5, 59)));
+        assert!(!night.contains(at(11, 22, 0)));
+    }
+
+    #[test]
+    fn test_desired_tolerates_skew() {
+        let schedule = EndpointSchedule::new("endpoint", 0)
+            .with_rule("weekdays 08:00-20:00 4".parse().unwrap());
+        let skew = Duration::from_secs(60);
+
+        assert_eq!(schedule.desired_at(at(6, 7, 58), skew), 0);
+        assert_eq!(schedule.desired_at(at(6, 7, 59), skew), 4);
+        assert_eq!(schedule.desired_at(at(6, 20, 0), skew), 4);
+        assert_eq!(schedule.desired_at(at(6, 20, 2), skew), 0);
+        assert_eq!(schedule.desired_at(at(11, 12, 0), skew), 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_is_idempotent() {
+        let schedule = EndpointSchedule::new("endpoint", 0)
+            .with_rule("weekdays 08:00-20:00 4".parse().unwrap());
+        let cloud = Cloud::new().with_endpoint(testing::endpoint(json!({"workersMax": 8})));
+        let scheduler = ScalingScheduler::new(cloud)
+            .with_schedule(schedule)
+            .with_clock(|| at(6, 9, 0))
+            .with_policy(SchedulerPolicy {
+                dry_run: true,
+                ..Default::default()
+            });
+
+        let report = scheduler.run().await.unwrap();
+        assert!(matches!(
+            report.results[0].outcome,
+            Ok(ScheduleAction::Planned)
+        ));
+        assert_eq!(
+            scheduler.client.endpoint("endpoint").unwrap().workers_min,
+            0
+        );
+
+        let scheduler = scheduler.with_policy(SchedulerPolicy::default());
+        let report = scheduler.run().await.unwrap();
+        assert!(matches!(
+            report.results[0].outcome,
+            Ok(ScheduleAction::Updated)
+        ));
+        assert_eq!(report.results[0].previous, Some(0));
+        assert_eq!(
+            scheduler.client.endpoint("endpoint").unwrap().workers_min,
+            4
+        );
+
+        let report = scheduler.run().await.unwrap();
+        assert!(matches!(
+            report.results[0].outcome,
+            Ok(ScheduleAction::Unchanged)
+        ));
+    }
+}
@@ -6,6 +6,9 @@ mod billing;
 mod endpoints;
 mod pods;
 mod registry;
+#[cfg(feature = "graphql")]
+mod runtime;
+mod serverless;
 mod templates;
 mod volumes;
 
@@ -13,5 +16,9 @@ pub use billing::*;
 pub use endpoints::*;
 pub use pods::*;
 pub use registry::*;
+#[cfg(feature = "graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+pub use runtime::*;
+pub use serverless::*;
 pub use templates::*;
 pub use volumes::*;
@@ -0,0 +1,168 @@
+use std::future::Future;
+
+use serde::Deserialize;
+
+use crate::model::v1::{ContainerRuntime, GpuRuntime, PodRuntime, PodRuntimes, RuntimePort};
+use crate::version::V1;
+use crate::{Result, RunpodClient};
+
+/// Trait for retrieving runtime telemetry of running Pods.
+///
+/// Runtime telemetry (uptime, GPU and container utilization, exposed ports) is
+/// not part of the REST API and is read from the GraphQL API instead.
+/// Serverless workers are Pods, so their telemetry is available as well.
+///
+/// This trait is implemented on the [`RunpodClient`](crate::client::RunpodClient)
+/// when the `graphql` feature is enabled.
+pub trait RuntimeService {
+    /// Gets the runtime telemetry of a Pod.
+    ///
+    /// # Arguments
+    ///
+    /// * `pod_id` - The unique identifier of the pod
+    ///
+    /// # Returns
+    ///
+    /// Returns the runtime telemetry, or `None` if the Pod is not running.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::service::v1::RuntimeService;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// if let Some(runtime) = client.get_pod_runtime("pod_id").await? {
+    ///     println!("GPU utilization: {:?}%", runtime.max_gpu_utilization());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_pod_runtime(&self, pod_id: &str) -> impl Future<Output = Result<Option<PodRuntime>>>;
+
+    /// Lists the runtime telemetry of all running Pods.
+    ///
+    /// # Returns
+    ///
+    /// Returns the runtime telemetry of every Pod that is currently running.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::service::v1::RuntimeService;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// for runtime in client.list_pod_runtimes().await? {
+    ///     println!("{}: up {:?}", runtime.pod_id, runtime.uptime());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_pod_runtimes(&self) -> impl Future<Output = Result<PodRuntimes>>;
+}
+
+/// Selection of runtime fields shared by all runtime queries.
+const RUNTIME_FIELDS: &str = "id runtime { uptimeInSeconds \
+    gpus { id gpuUtilPercent memoryUtilPercent } \
+    container { cpuPercent memoryPercent } \
+    ports { ip isIpPublic privatePort publicPort type } }";
+
+#[derive(Debug, Deserialize)]
+struct PodNode {
+    id: String,
+    runtime: Option<RuntimeNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeNode {
+    uptime_in_seconds: Option<u64>,
+    gpus: Option<Vec<GpuRuntime>>,
+    container: Option<ContainerRuntime>,
+    ports: Option<Vec<RuntimePort>>,
+}
+
+impl PodNode {
+    fn into_runtime(self) -> Option<PodRuntime> {
+        let runtime = self.runtime?;
+        Some(PodRuntime {
+            pod_id: self.id,
+            uptime_in_seconds: runtime.uptime_in_seconds,
+            gpus: runtime.gpus.unwrap_or_default(),
+            container: runtime.container,
+            ports: runtime.ports.unwrap_or_default(),
+        })
+    }
+}
+
+impl RuntimeService for RunpodClient<V1> {
+    async fn get_pod_runtime(&self, pod_id: &str) -> Result<Option<PodRuntime>> {
+        #[derive(Deserialize)]
+        struct Data {
+            pod: Option<PodNode>,
+        }
+
+        let pod_id = serde_json::to_string(pod_id)?;
+        let query = format!(
+            "query PodRuntime {{ pod(input: {{podId: {pod_id}}}) {{ {RUNTIME_FIELDS} }} }}"
+        );
+        let data: Data = self.graphql_data(&query).await?;
+        Ok(data.pod.and_then(PodNode::into_runtime))
+    }
+
+    async fn list_pod_runtimes(&self) -> Result<PodRuntimes> {
+        #[derive(Deserialize)]
+        struct Data {
+            myself: Myself,
+        }
+
+        #[derive(Deserialize)]
+        struct Myself {
+            pods: Vec<PodNode>,
+        }
+
+        let query = format!("query PodRuntimes {{ myself {{ pods {{ {RUNTIME_FIELDS} }} }} }}");
+        let data: Data = self.graphql_data(&query).await?;
+        Ok(data
+            .myself
+            .pods
+            .into_iter()
+            .filter_map(PodNode::into_runtime)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_node_without_runtime_is_skipped() {
+        let node: PodNode = serde_json::from_str(r#"{"id": "pod", "runtime": null}"#).unwrap();
+        assert!(node.into_runtime().is_none());
+    }
+
+    #[test]
+    fn test_pod_node_into_runtime() {
+        let node: PodNode = serde_json::from_str(
+            r#"{"id": "pod", "runtime": {
+                "uptimeInSeconds": 90,
+                "gpus": [{"id": "0", "gpuUtilPercent": 20, "memoryUtilPercent": 5},
+                         {"id": "1", "gpuUtilPercent": 60, "memoryUtilPercent": 7}],
+                "container": {"cpuPercent": 3, "memoryPercent": 12},
+                "ports": null
+            }}"#,
+        )
+        .unwrap();
+
+        let runtime = node.into_runtime().unwrap();
+        assert_eq!(runtime.pod_id, "pod");
+        assert_eq!(runtime.uptime(), Some(std::time::Duration::from_secs(90)));
+        assert_eq!(runtime.max_gpu_utilization(), Some(60.0));
+        assert_eq!(runtime.mean_gpu_utilization(), Some(40.0));
+        assert!(runtime.ports.is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+use std::future::Future;
+
+use crate::model::v1::{EndpointHealth, JobStatus, JobStream, RunJobInput};
+use crate::version::V1;
+use crate::{Result, RunpodClient};
+
+/// Trait for submitting jobs to serverless endpoints.
+///
+/// Provides methods for running jobs asynchronously or synchronously, polling
+/// their status, reading streamed outputs, and cancelling them. Requests go to
+/// the serverless job API configured by
+/// [`serverless_url`](crate::RunpodConfig::serverless_url).
+/// This trait is implemented on the [`RunpodClient`](crate::client::RunpodClient).
+pub trait ServerlessService {
+    /// Submits a job to an endpoint's queue.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_id` - The unique identifier of the endpoint
+    /// * `input` - The job input, webhook and policy
+    ///
+    /// # Returns
+    ///
+    /// Returns the status of the queued job, including its ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::model::v1::RunJobInput;
+    /// # use runpod_sdk::service::v1::ServerlessService;
+    /// # use serde_json::json;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// let input = RunJobInput::new(json!({"prompt": "Hello"}));
+    /// let job = client.run_job("endpoint_id", input).await?;
+    /// println!("Queued job {}", job.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn run_job(
+        &self,
+        endpoint_id: &str,
+        input: RunJobInput,
+    ) -> impl Future<Output = Result<JobStatus>>;
+
+    /// Submits a job and waits for its result.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_id` - The unique identifier of the endpoint
+    /// * `input` - The job input, webhook and policy
+    ///
+    /// # Returns
+    ///
+    /// Returns the job status, with its output if the job finished in time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::model::v1::RunJobInput;
+    /// # use runpod_sdk::service::v1::ServerlessService;
+    /// # use serde_json::json;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// let input = RunJobInput::new(json!({"prompt": "Hello"}));
+    /// let job = client.run_job_sync("endpoint_id", input).await?;
+    /// println!("Output: {:?}", job.output);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn run_job_sync(
+        &self,
+        endpoint_id: &str,
+        input: RunJobInput,
+    ) -> impl Future<Output = Result<JobStatus>>;
+
+    /// Gets the status of a job.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_id` - The unique identifier of the endpoint
+    /// * `job_id` - The unique identifier of the job
+    ///
+    /// # Returns
+    ///
+    /// Returns the job status, with its output once completed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::service::v1::ServerlessService;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// let job = client.get_job_status("endpoint_id", "job_id").await?;
+    /// println!("Status: {:?}", job.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_job_status(
+        &self,
+        endpoint_id: &str,
+        job_id: &str,
+    ) -> impl Future<Output = Result<JobStatus>>;
+
+    /// Reads the partial outputs a streaming job produced since the last read.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_id` - The unique identifier of the endpoint
+    /// * `job_id` - The unique identifier of the job
+    ///
+    /// # Returns
+    ///
+    /// Returns the job status and the new partial outputs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::service::v1::ServerlessService;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// loop {
+    ///     let chunk = client.stream_job("endpoint_id", "job_id").await?;
+    ///     for output in chunk.stream {
+    ///         print!("{}", output.output);
+    ///     }
+    ///     if chunk.status.is_final() {
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn stream_job(
+        &self,
+        endpoint_id: &str,
+        job_id: &str,
+    ) -> impl Future<Output = Result<JobStream>>;
+
+    /// Cancels a queued or running job.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_id` - The unique identifier of the endpoint
+    /// * `job_id` - The unique identifier of the job
+    ///
+    /// # Returns
+    ///
+    /// Returns the status of the cancelled job.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::service::v1::ServerlessService;
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// client.cancel_job("endpoint_id", "job_id").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn cancel_job(
+        &self,
+        endpoint_id: &str,
+        job_id: &str,
+    ) -> impl Future<Output = Result<JobStatus>>;
+
+    /// Gets the job and worker counts of an endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_id` - The unique identifier of the endpoint
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of queued, running, completed, failed and retried
+    /// jobs, and the number of workers in each state.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use runpod_sdk::{RunpodClient, Result};
+    /// # use runpod_sdk::model::v1::GetEndpointQuery;
+    /// # use runpod_sdk::service::v1::{EndpointsService, ServerlessService};
+    /// # async fn example() -> Result<()> {
+    /// let client = RunpodClient::from_env()?;
+    ///
+    /// let health = client.get_endpoint_health("endpoint_id").await?;
+    /// println!("{} jobs queued", health.jobs.in_queue);
+    ///
+    /// let query = GetEndpointQuery {
+    ///     include_workers: Some(true),
+    ///     ..Default::default()
+    /// };
+    /// let endpoint = client.get_endpoint("endpoint_id", query).await?;
+    /// for pod in health.with_workers(&endpoint).worker_pods {
+    ///     println!("Worker {}: {:?}", pod.id, pod.desired_status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_endpoint_health(
+        &self,
+        endpoint_id: &str,
+    ) -> impl Future<Output = Result<EndpointHealth>>;
+}
+
+impl ServerlessService for RunpodClient<V1> {
+    async fn run_job(&self, endpoint_id: &str, input: RunJobInput) -> Result<JobStatus> {
+        let path = format!("/{}/run", endpoint_id);
+        let response = self.serverless_post(&path).json(&input).send().await?;
+        let job = response.json().await?;
+        Ok(job)
+    }
+
+    async fn run_job_sync(&self, endpoint_id: &str, input: RunJobInput) -> Result<JobStatus> {
+        let path = format!("/{}/runsync", endpoint_id);
+        let response = self.serverless_post(&path).json(&input).send().await?;
+        let job = response.json().await?;
+        Ok(job)
+    }
+
+    async fn get_job_status(&self, endpoint_id: &str, job_id: &str) -> Result<JobStatus> {
+        let path = format!("/{}/status/{}", endpoint_id, job_id);
+        let response = self.serverless_get(&path).send().await?;
+        let job = response.json().await?;
+        Ok(job)
+    }
+
+    async fn stream_job(&self, endpoint_id: &str, job_id: &str) -> Result<JobStream> {
+        let path = format!("/{}/stream/{}", endpoint_id, job_id);
+        let response = self.serverless_get(&path).send().await?;
+        let stream = response.json().await?;
+        Ok(stream)
+    }
+
+    async fn cancel_job(&self, endpoint_id: &str, job_id: &str) -> Result<JobStatus> {
+        let path = format!("/{}/cancel/{}", endpoint_id, job_id);
+        let response = self.serverless_post(&path).send().await?;
+        let job = response.json().await?;
+        Ok(job)
+    }
+
+    async fn get_endpoint_health(&self, endpoint_id: &str) -> Result<EndpointHealth> {
+        let path = format!("/{}/health", endpoint_id);
+        let response = self.serverless_get(&path).send().await?;
+        let health = response.json().await?;
+        Ok(health)
+    }
+}
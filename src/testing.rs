@@ -0,0 +1,652 @@
+//! Fixtures and an in-memory API stand-in shared by the unit tests.
+//!
+//! The fixture functions build models from a minimal valid API response,
+//! overridden by the given JSON fields. [`Cloud`] keeps Pods, endpoints,
+//! templates, network volumes and registry auths in memory and implements
+//! the service traits on top of them.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::model::v1::{
+    BillingRecords, ContainerRegistryAuth, ContainerRegistryAuthCreateInput,
+    ContainerRegistryAuths, Endpoint, EndpointBillingQuery, EndpointCreateInput, EndpointHealth,
+    EndpointUpdateInput, Endpoints, GetEndpointQuery, GetPodQuery, GetTemplateQuery, JobState,
+    JobStatus, JobStream, ListEndpointsQuery, ListPodsQuery, ListTemplatesQuery, NetworkVolume,
+    NetworkVolumeBillingQuery, NetworkVolumeCreateInput, NetworkVolumeUpdateInput, NetworkVolumes,
+    Pod, PodBillingQuery, PodCreateInput, PodStatus, PodUpdateInput, Pods, RunJobInput, Template,
+    TemplateCreateInput, TemplateUpdateInput, Templates, api_value,
+};
+use crate::service::v1::{
+    BillingService, EndpointsService, PodsService, RegistryService, ServerlessService,
+    TemplatesService, VolumesService,
+};
+use crate::{Error, Result};
+
+/// Time the Pods created by [`Cloud`] report as started.
+pub(crate) const STARTED_AT: &str = "2025-01-06T09:00:00Z";
+
+/// Returns a running Pod with `fields` overriding the defaults.
+pub(crate) fn pod(fields: Value) -> Pod {
+    build(
+        json!({
+            "id": "pod",
+            "image": "runpod/pytorch:latest",
+            "consumerUserId": "user",
+            "machineId": "machine",
+            "desiredStatus": "RUNNING",
+            "costPerHr": 0.5,
+            "adjustedCostPerHr": 0.5,
+            "vcpuCount": 8.0,
+            "memoryInGb": 32.0,
+            "containerDiskInGb": 20,
+            "volumeEncrypted": false,
+            "ports": [],
+            "env": {},
+            "interruptible": false,
+            "locked": false,
+        }),
+        fields,
+    )
+}
+
+/// Returns a GPU endpoint with `fields` overriding the defaults.
+pub(crate) fn endpoint(fields: Value) -> Endpoint {
+    build(
+        json!({
+            "id": "endpoint",
+            "userId": "user",
+            "templateId": "template",
+            "version": 1,
+            "computeType": "GPU",
+            "createdAt": "2025-01-06T00:00:00Z",
+            "dataCenterIds": [],
+            "executionTimeoutMs": 600000,
+            "idleTimeout": 5,
+            "scalerType": "QUEUE_DELAY",
+            "scalerValue": 4,
+            "workersMax": 3,
+            "workersMin": 0,
+        }),
+        fields,
+    )
+}
+
+/// Returns a serverless template with `fields` overriding the defaults.
+pub(crate) fn template(fields: Value) -> Template {
+    build(
+        json!({
+            "id": "template",
+            "name": "app",
+            "imageName": "org/app:v1",
+            "isPublic": false,
+            "isRunpod": false,
+            "isServerless": true,
+            "containerDiskInGb": 20,
+            "volumeInGb": 0,
+            "volumeMountPath": "/workspace",
+        }),
+        fields,
+    )
+}
+
+/// Returns a network volume with `fields` overriding the defaults.
+pub(crate) fn volume(fields: Value) -> NetworkVolume {
+    build(
+        json!({
+            "id": "volume",
+            "name": "data",
+            "size": 100,
+            "dataCenterId": "EU-RO-1",
+        }),
+        fields,
+    )
+}
+
+fn build<T: DeserializeOwned>(mut value: Value, fields: Value) -> T {
+    merge(&mut value, fields);
+    serde_json::from_value(value).expect("fixture does not match the model")
+}
+
+/// Copies the non-null top-level fields of `fields` into `value`.
+fn merge(value: &mut Value, fields: Value) {
+    let Value::Object(fields) = fields else {
+        panic!("fixture fields must be an object");
+    };
+    for (field, field_value) in fields {
+        if !field_value.is_null() {
+            value[field] = field_value;
+        }
+    }
+}
+
+/// Applies the fields set in `input` to `model`.
+fn update<T: Serialize + DeserializeOwned>(model: &T, input: impl Serialize) -> Result<T> {
+    let mut value = serde_json::to_value(model)?;
+    merge(&mut value, serde_json::to_value(input)?);
+    Ok(serde_json::from_value(value)?)
+}
+
+fn not_found(kind: &str, id: &str) -> Error {
+    Error::Validation(format!("{kind} {id} not found"))
+}
+
+/// In-memory stand-in of the RunPod API.
+///
+/// Created resources get sequential IDs such as `pod-1` or `volume-2`, and
+/// Pods start right away. Every mutating call and every synchronous job is
+/// recorded as `"<method> <id>"`, e.g. `"stop_pod source"`, and any call,
+/// reading ones included, can be made to fail with [`Cloud::failing`].
+#[derive(Debug, Default)]
+pub(crate) struct Cloud {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    pods: BTreeMap<String, Pod>,
+    endpoints: BTreeMap<String, Endpoint>,
+    templates: BTreeMap<String, Template>,
+    volumes: BTreeMap<String, NetworkVolume>,
+    registry_auths: BTreeMap<String, ContainerRegistryAuth>,
+    health: HashMap<String, VecDeque<EndpointHealth>>,
+    delays: HashMap<String, Duration>,
+    /// Calls that fail, with how many times each succeeds first.
+    failing: HashMap<String, usize>,
+    created_pod_status: Option<PodStatus>,
+    calls: Vec<String>,
+    next_id: usize,
+}
+
+impl Cloud {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn with_pod(self, pod: Pod) -> Self {
+        self.state().pods.insert(pod.id.clone(), pod);
+        self
+    }
+
+    pub(crate) fn with_endpoint(self, endpoint: Endpoint) -> Self {
+        self.state().endpoints.insert(endpoint.id.clone(), endpoint);
+        self
+    }
+
+    pub(crate) fn with_template(self, template: Template) -> Self {
+        self.state().templates.insert(template.id.clone(), template);
+        self
+    }
+
+    pub(crate) fn with_volume(self, volume: NetworkVolume) -> Self {
+        self.state().volumes.insert(volume.id.clone(), volume);
+        self
+    }
+
+    /// Sets the status of the Pods created from now on.
+    #[cfg(feature = "migration")]
+    pub(crate) fn with_created_pod_status(self, status: PodStatus) -> Self {
+        self.state().created_pod_status = Some(status);
+        self
+    }
+
+    /// Queues health reports of `endpoint_id`; the last one keeps being returned.
+    #[cfg(feature = "rollout")]
+    pub(crate) fn with_health(
+        self,
+        endpoint_id: &str,
+        health: impl IntoIterator<Item = EndpointHealth>,
+    ) -> Self {
+        let queue = health.into_iter().collect();
+        self.state().health.insert(endpoint_id.to_string(), queue);
+        self
+    }
+
+    /// Delays the serverless jobs of `endpoint_id`.
+    pub(crate) fn with_delay(self, endpoint_id: &str, delay: Duration) -> Self {
+        self.state().delays.insert(endpoint_id.to_string(), delay);
+        self
+    }
+
+    /// Makes `call`, e.g. `"delete_pod source"`, fail after succeeding `after` times.
+    #[cfg(any(feature = "migration", feature = "rollout"))]
+    pub(crate) fn failing(self, call: &str, after: usize) -> Self {
+        self.state().failing.insert(call.to_string(), after);
+        self
+    }
+
+    pub(crate) fn calls(&self) -> Vec<String> {
+        self.state().calls.clone()
+    }
+
+    #[cfg(feature = "migration")]
+    pub(crate) fn pod(&self, pod_id: &str) -> Option<Pod> {
+        self.state().pods.get(pod_id).cloned()
+    }
+
+    pub(crate) fn endpoint(&self, endpoint_id: &str) -> Option<Endpoint> {
+        self.state().endpoints.get(endpoint_id).cloned()
+    }
+
+    pub(crate) fn template(&self, template_id: &str) -> Option<Template> {
+        self.state().templates.get(template_id).cloned()
+    }
+
+    #[cfg(feature = "migration")]
+    pub(crate) fn volume(&self, volume_id: &str) -> Option<NetworkVolume> {
+        self.state().volumes.get(volume_id).cloned()
+    }
+
+    /// Fails the call if configured to, recording it if `record` is set.
+    fn call(&self, call: String, record: bool) -> Result<MutexGuard<'_, State>> {
+        let mut state = self.state();
+        let failed = match state.failing.get_mut(&call) {
+            Some(0) => true,
+            Some(after) => {
+                *after -= 1;
+                false
+            }
+            None => false,
+        };
+        if record {
+            state.calls.push(call.clone());
+        }
+        match failed {
+            true => Err(Error::Validation(format!("{call} failed"))),
+            false => Ok(state),
+        }
+    }
+
+    fn record(&self, call: String) -> Result<MutexGuard<'_, State>> {
+        self.call(call, true)
+    }
+
+    fn read(&self, call: String) -> Result<MutexGuard<'_, State>> {
+        self.call(call, false)
+    }
+}
+
+impl State {
+    fn next_id(&mut self, kind: &str) -> String {
+        self.next_id += 1;
+        format!("{kind}-{}", self.next_id)
+    }
+
+    fn set_pod_status(&mut self, pod_id: &str, status: PodStatus) -> Result<()> {
+        let pod = self
+            .pods
+            .get_mut(pod_id)
+            .ok_or_else(|| not_found("pod", pod_id))?;
+        pod.desired_status = status;
+        Ok(())
+    }
+}
+
+impl PodsService for Cloud {
+    async fn create_pod(&self, input: PodCreateInput) -> Result<Pod> {
+        let id = self.state().next_id("pod");
+        let mut state = self.record(format!("create_pod {id}"))?;
+
+        let status = state.created_pod_status.unwrap_or(PodStatus::Running);
+        let data_center = input.data_center_ids.as_ref().and_then(|ids| ids.first());
+        let pod = pod(json!({
+            "id": id,
+            "name": input.name,
+            "image": input.image_name,
+            "desiredStatus": status,
+            "lastStartedAt": (status == PodStatus::Running).then_some(STARTED_AT),
+            "gpuTypeId": input.gpu_type_ids.as_ref().and_then(|ids| ids.first()),
+            "templateId": input.template_id,
+            "networkVolumeId": input.network_volume_id,
+            "env": input.env,
+            "ports": input.ports,
+            "machine": data_center.map(|data_center| json!({
+                "location": "",
+                "dataCenterId": api_value(data_center),
+                "supportPublicIp": false,
+                "secureCloud": true,
+                "costPerHr": 0.5,
+            })),
+        }));
+        state.pods.insert(id, pod.clone());
+        Ok(pod)
+    }
+
+    async fn list_pods(&self, query: ListPodsQuery) -> Result<Pods> {
+        let state = self.read("list_pods".to_string())?;
+        let pods = state.pods.values().filter(|pod| {
+            query
+                .desired_status
+                .is_none_or(|status| pod.desired_status == status)
+        });
+        Ok(pods.cloned().collect())
+    }
+
+    async fn get_pod(&self, pod_id: &str, _: GetPodQuery) -> Result<Pod> {
+        let state = self.read(format!("get_pod {pod_id}"))?;
+        let pod = state.pods.get(pod_id);
+        pod.cloned().ok_or_else(|| not_found("pod", pod_id))
+    }
+
+    async fn update_pod(&self, pod_id: &str, input: PodUpdateInput) -> Result<Pod> {
+        let mut state = self.record(format!("update_pod {pod_id}"))?;
+        let pod = state
+            .pods
+            .get_mut(pod_id)
+            .ok_or_else(|| not_found("pod", pod_id))?;
+        let mut input = serde_json::to_value(input)?;
+        if let Some(image) = input.as_object_mut().and_then(|i| i.remove("imageName")) {
+            input["image"] = image;
+        }
+        *pod = update(pod, input)?;
+        Ok(pod.clone())
+    }
+
+    async fn delete_pod(&self, pod_id: &str) -> Result<()> {
+        let mut state = self.record(format!("delete_pod {pod_id}"))?;
+        state.pods.remove(pod_id);
+        Ok(())
+    }
+
+    async fn start_pod(&self, pod_id: &str) -> Result<()> {
+        let mut state = self.record(format!("start_pod {pod_id}"))?;
+        state.set_pod_status(pod_id, PodStatus::Running)
+    }
+
+    async fn stop_pod(&self, pod_id: &str) -> Result<()> {
+        let mut state = self.record(format!("stop_pod {pod_id}"))?;
+        state.set_pod_status(pod_id, PodStatus::Exited)
+    }
+
+    async fn reset_pod(&self, pod_id: &str) -> Result<()> {
+        drop(self.record(format!("reset_pod {pod_id}"))?);
+        Ok(())
+    }
+
+    async fn restart_pod(&self, pod_id: &str) -> Result<()> {
+        let mut state = self.record(format!("restart_pod {pod_id}"))?;
+        state.set_pod_status(pod_id, PodStatus::Running)
+    }
+}
+
+impl EndpointsService for Cloud {
+    async fn create_endpoint(&self, input: EndpointCreateInput) -> Result<Endpoint> {
+        let id = self.state().next_id("endpoint");
+        let mut state = self.record(format!("create_endpoint {id}"))?;
+
+        let mut fields = serde_json::to_value(input)?;
+        fields["id"] = json!(id);
+        let endpoint = endpoint(fields);
+        state.endpoints.insert(id, endpoint.clone());
+        Ok(endpoint)
+    }
+
+    async fn list_endpoints(&self, _: ListEndpointsQuery) -> Result<Endpoints> {
+        let state = self.read("list_endpoints".to_string())?;
+        Ok(state.endpoints.values().cloned().collect())
+    }
+
+    /// Lists `workersMin` workers running the current template when workers
+    /// are included.
+    async fn get_endpoint(&self, endpoint_id: &str, query: GetEndpointQuery) -> Result<Endpoint> {
+        let state = self.read(format!("get_endpoint {endpoint_id}"))?;
+        let mut endpoint = state
+            .endpoints
+            .get(endpoint_id)
+            .cloned()
+            .ok_or_else(|| not_found("endpoint", endpoint_id))?;
+        if query.include_workers == Some(true) {
+            let workers = (0..endpoint.workers_min).map(|i| {
+                pod(json!({
+                    "id": format!("{endpoint_id}-worker-{i}"),
+                    "endpointId": endpoint_id,
+                    "templateId": endpoint.template_id,
+                }))
+            });
+            endpoint.workers = Some(workers.collect());
+        }
+        Ok(endpoint)
+    }
+
+    /// Bumps the endpoint version on every update.
+    async fn update_endpoint(
+        &self,
+        endpoint_id: &str,
+        input: EndpointUpdateInput,
+    ) -> Result<Endpoint> {
+        let mut state = self.record(format!("update_endpoint {endpoint_id}"))?;
+        let endpoint = state
+            .endpoints
+            .get_mut(endpoint_id)
+            .ok_or_else(|| not_found("endpoint", endpoint_id))?;
+        *endpoint = update(endpoint, input)?;
+        endpoint.version += 1;
+        Ok(endpoint.clone())
+    }
+
+    async fn delete_endpoint(&self, endpoint_id: &str) -> Result<()> {
+        let mut state = self.record(format!("delete_endpoint {endpoint_id}"))?;
+        state.endpoints.remove(endpoint_id);
+        Ok(())
+    }
+}
+
+impl TemplatesService for Cloud {
+    async fn create_template(&self, input: TemplateCreateInput) -> Result<Template> {
+        let id = self.state().next_id("template");
+        let mut state = self.record(format!("create_template {id}"))?;
+
+        let mut fields = serde_json::to_value(input)?;
+        fields["id"] = json!(id);
+        let template = template(fields);
+        state.templates.insert(id, template.clone());
+        Ok(template)
+    }
+
+    async fn list_templates(&self, _: ListTemplatesQuery) -> Result<Templates> {
+        let state = self.read("list_templates".to_string())?;
+        Ok(state.templates.values().cloned().collect())
+    }
+
+    async fn get_template(&self, template_id: &str, _: GetTemplateQuery) -> Result<Template> {
+        let state = self.read(format!("get_template {template_id}"))?;
+        let template = state.templates.get(template_id);
+        template
+            .cloned()
+            .ok_or_else(|| not_found("template", template_id))
+    }
+
+    async fn update_template(
+        &self,
+        template_id: &str,
+        input: TemplateUpdateInput,
+    ) -> Result<Template> {
+        let mut state = self.record(format!("update_template {template_id}"))?;
+        let template = state
+            .templates
+            .get_mut(template_id)
+            .ok_or_else(|| not_found("template", template_id))?;
+        *template = update(template, input)?;
+        Ok(template.clone())
+    }
+
+    async fn delete_template(&self, template_id: &str) -> Result<()> {
+        let mut state = self.record(format!("delete_template {template_id}"))?;
+        state.templates.remove(template_id);
+        Ok(())
+    }
+}
+
+impl VolumesService for Cloud {
+    async fn create_volume(&self, input: NetworkVolumeCreateInput) -> Result<NetworkVolume> {
+        let id = self.state().next_id("volume");
+        let mut state = self.record(format!("create_volume {id}"))?;
+
+        let mut fields = serde_json::to_value(input)?;
+        fields["id"] = json!(id);
+        let volume = volume(fields);
+        state.volumes.insert(id, volume.clone());
+        Ok(volume)
+    }
+
+    async fn list_volumes(&self) -> Result<NetworkVolumes> {
+        let state = self.read("list_volumes".to_string())?;
+        Ok(state.volumes.values().cloned().collect())
+    }
+
+    async fn get_volume(&self, volume_id: &str) -> Result<NetworkVolume> {
+        let state = self.read(format!("get_volume {volume_id}"))?;
+        let volume = state.volumes.get(volume_id);
+        volume
+            .cloned()
+            .ok_or_else(|| not_found("volume", volume_id))
+    }
+
+    async fn update_volume(
+        &self,
+        volume_id: &str,
+        input: NetworkVolumeUpdateInput,
+    ) -> Result<NetworkVolume> {
+        let mut state = self.record(format!("update_volume {volume_id}"))?;
+        let volume = state
+            .volumes
+            .get_mut(volume_id)
+            .ok_or_else(|| not_found("volume", volume_id))?;
+        *volume = update(volume, input)?;
+        Ok(volume.clone())
+    }
+
+    async fn delete_volume(&self, volume_id: &str) -> Result<()> {
+        let mut state = self.record(format!("delete_volume {volume_id}"))?;
+        state.volumes.remove(volume_id);
+        Ok(())
+    }
+}
+
+impl RegistryService for Cloud {
+    async fn create_registry_auth(
+        &self,
+        input: ContainerRegistryAuthCreateInput,
+    ) -> Result<ContainerRegistryAuth> {
+        let id = self.state().next_id("auth");
+        let mut state = self.record(format!("create_registry_auth {id}"))?;
+
+        let auth = ContainerRegistryAuth {
+            id: id.clone(),
+            name: input.name,
+        };
+        state.registry_auths.insert(id, auth.clone());
+        Ok(auth)
+    }
+
+    async fn list_registry_auths(&self) -> Result<ContainerRegistryAuths> {
+        let state = self.read("list_registry_auths".to_string())?;
+        Ok(state.registry_auths.values().cloned().collect())
+    }
+
+    async fn get_registry_auth(&self, auth_id: &str) -> Result<ContainerRegistryAuth> {
+        let state = self.read(format!("get_registry_auth {auth_id}"))?;
+        let auth = state.registry_auths.get(auth_id);
+        auth.cloned()
+            .ok_or_else(|| not_found("registry auth", auth_id))
+    }
+
+    async fn delete_registry_auth(&self, auth_id: &str) -> Result<()> {
+        let mut state = self.record(format!("delete_registry_auth {auth_id}"))?;
+        state.registry_auths.remove(auth_id);
+        Ok(())
+    }
+}
+
+/// No usage is billed.
+impl BillingService for Cloud {
+    async fn get_pod_billing(&self, _: PodBillingQuery) -> Result<BillingRecords> {
+        drop(self.read("get_pod_billing".to_string())?);
+        Ok(BillingRecords::new())
+    }
+
+    async fn get_endpoint_billing(&self, _: EndpointBillingQuery) -> Result<BillingRecords> {
+        drop(self.read("get_endpoint_billing".to_string())?);
+        Ok(BillingRecords::new())
+    }
+
+    async fn get_volume_billing(&self, _: NetworkVolumeBillingQuery) -> Result<BillingRecords> {
+        drop(self.read("get_volume_billing".to_string())?);
+        Ok(BillingRecords::new())
+    }
+}
+
+/// Jobs echo their input with a `"v"` field set to the endpoint ID.
+impl ServerlessService for Cloud {
+    async fn run_job(&self, endpoint_id: &str, _: RunJobInput) -> Result<JobStatus> {
+        drop(self.record(format!("run_job {endpoint_id}"))?);
+        Ok(job(endpoint_id, JobState::InQueue, None))
+    }
+
+    async fn run_job_sync(&self, endpoint_id: &str, input: RunJobInput) -> Result<JobStatus> {
+        let delay = self
+            .record(format!("run_job_sync {endpoint_id}"))?
+            .delays
+            .get(endpoint_id)
+            .copied();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        let mut output = input.input;
+        output["v"] = json!(endpoint_id);
+        Ok(job(endpoint_id, JobState::Completed, Some(output)))
+    }
+
+    async fn get_job_status(&self, endpoint_id: &str, job_id: &str) -> Result<JobStatus> {
+        drop(self.read(format!("get_job_status {job_id}"))?);
+        Ok(job(endpoint_id, JobState::Completed, None))
+    }
+
+    async fn stream_job(&self, _: &str, job_id: &str) -> Result<JobStream> {
+        drop(self.read(format!("stream_job {job_id}"))?);
+        Ok(serde_json::from_value(json!({
+            "status": JobState::Completed,
+            "stream": [],
+        }))?)
+    }
+
+    async fn cancel_job(&self, endpoint_id: &str, job_id: &str) -> Result<JobStatus> {
+        drop(self.record(format!("cancel_job {job_id}"))?);
+        Ok(job(endpoint_id, JobState::Cancelled, None))
+    }
+
+    async fn get_endpoint_health(&self, endpoint_id: &str) -> Result<EndpointHealth> {
+        let mut state = self.read(format!("get_endpoint_health {endpoint_id}"))?;
+        let health = state
+            .health
+            .get_mut(endpoint_id)
+            .ok_or_else(|| not_found("endpoint health", endpoint_id))?;
+        let next = match health.len() {
+            0 | 1 => health.front().cloned(),
+            _ => health.pop_front(),
+        };
+        next.ok_or_else(|| not_found("endpoint health", endpoint_id))
+    }
+}
+
+fn job(endpoint_id: &str, status: JobState, output: Option<Value>) -> JobStatus {
+    JobStatus {
+        id: format!("{endpoint_id}-job"),
+        status,
+        output,
+        error: None,
+        delay_time: None,
+        execution_time: None,
+        worker_id: None,
+    }
+}
@@ -0,0 +1,394 @@
+//! Template version history.
+//!
+//! [`TemplatesService::update_template`] overwrites a template in place. A
+//! [`TemplateHistory`] snapshots the template before every update it makes
+//! and keeps the snapshots as numbered [`TemplateVersion`]s in a
+//! [`VersionStore`], in memory with [`MemoryStore`] or on disk with
+//! [`JsonFileStore`]. Versions can be compared field by field with
+//! [`TemplateVersion::diff`] and restored with [`TemplateHistory::restore`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::model::v1::TemplateUpdateInput;
+//! use runpod_sdk::versioning::{JsonFileStore, TemplateHistory};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let store = JsonFileStore::new(".runpod/templates");
+//! let history = TemplateHistory::new(client).with_store(store);
+//!
+//! let update = TemplateUpdateInput {
+//!     image_name: Some("org/app:v2".to_string()),
+//!     ..Default::default()
+//! };
+//! history.update("template_id", update).await?;
+//!
+//! let diff = history.diff("template_id", 1, 2)?;
+//! for change in &diff.changes {
+//!     println!("{}: {} -> {}", change.path, change.before, change.after);
+//! }
+//!
+//! history.restore("template_id", 1).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod store;
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+pub use store::{JsonFileStore, MemoryStore, VersionStore};
+
+use crate::inventory::{FieldChange, diff_json};
+use crate::model::v1::{GetTemplateQuery, Template, TemplateUpdateInput};
+use crate::service::v1::TemplatesService;
+use crate::{Error, Result, RunpodClient};
+
+/// Template fields that change without an update and are not versioned.
+const UNVERSIONED_FIELDS: [&str; 2] = ["earned", "runtimeInMin"];
+
+/// A template as it was at a point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVersion {
+    /// Template ID.
+    pub template_id: String,
+
+    /// Version number, starting at 1.
+    pub version: u32,
+
+    /// When the version was captured.
+    pub captured_at: DateTime<Utc>,
+
+    /// The template.
+    pub template: Template,
+}
+
+impl TemplateVersion {
+    /// Compares this version with a later one, field by field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either template cannot be serialized.
+    pub fn diff(&self, later: &TemplateVersion) -> Result<TemplateDiff> {
+        Ok(TemplateDiff {
+            template_id: self.template_id.clone(),
+            from: self.version,
+            to: later.version,
+            changes: diff_json(&versioned(&self.template)?, &versioned(&later.template)?),
+        })
+    }
+
+    /// Returns an update setting a template back to this version.
+    ///
+    /// Every field is set, with empty values for the fields this version does
+    /// not have, as unset fields would keep their current values.
+    pub fn update_input(&self) -> TemplateUpdateInput {
+        let template = &self.template;
+        TemplateUpdateInput {
+            container_disk_in_gb: Some(template.container_disk_in_gb),
+            container_registry_auth_id: Some(
+                template
+                    .container_registry_auth_id
+                    .clone()
+                    .unwrap_or_default(),
+            ),
+            docker_entrypoint: Some(template.docker_entrypoint.clone().unwrap_or_default()),
+            docker_start_cmd: Some(template.docker_start_cmd.clone().unwrap_or_default()),
+            env: Some(template.env.clone().unwrap_or_default()),
+            image_name: Some(template.image_name.clone()),
+            is_public: Some(template.is_public),
+            name: Some(template.name.clone()),
+            ports: Some(template.ports.clone().unwrap_or_default()),
+            readme: Some(template.readme.clone().unwrap_or_default()),
+            volume_in_gb: Some(template.volume_in_gb),
+            volume_mount_path: Some(template.volume_mount_path.clone()),
+        }
+    }
+}
+
+/// Field-level differences between two versions of a template.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateDiff {
+    /// Template ID.
+    pub template_id: String,
+
+    /// Older version number.
+    pub from: u32,
+
+    /// Newer version number.
+    pub to: u32,
+
+    /// Changed fields using API field names, e.g. `imageName` or `env.MODEL`,
+    /// ordered by path.
+    pub changes: Vec<FieldChange>,
+}
+
+impl TemplateDiff {
+    /// Returns `true` if both versions are identical.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the change of the field at `path`, if it changed.
+    pub fn field(&self, path: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.path == path)
+    }
+}
+
+/// Updates templates while recording their previous versions.
+pub struct TemplateHistory<C = RunpodClient, S = MemoryStore> {
+    client: C,
+    store: S,
+}
+
+impl<C> TemplateHistory<C> {
+    /// Creates a history kept in memory.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            store: MemoryStore::new(),
+        }
+    }
+}
+
+impl<C, S> TemplateHistory<C, S> {
+    /// Replaces the version store.
+    pub fn with_store<T: VersionStore>(self, store: T) -> TemplateHistory<C, T> {
+        TemplateHistory {
+            client: self.client,
+            store,
+        }
+    }
+
+    /// Returns the version store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+}
+
+impl<C, S: VersionStore> TemplateHistory<C, S> {
+    /// Returns the recorded versions of a template, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub fn versions(&self, template_id: &str) -> Result<Vec<TemplateVersion>> {
+        self.store.list(template_id)
+    }
+
+    /// Returns a recorded version of a template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or the version does not exist.
+    pub fn version(&self, template_id: &str, version: u32) -> Result<TemplateVersion> {
+        self.versions(template_id)?
+            .into_iter()
+            .find(|v| v.version == version)
+            .ok_or_else(|| {
+                Error::Validation(format!("template {template_id} has no version {version}"))
+            })
+    }
+
+    /// Compares two recorded versions of a template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or a version does not exist.
+    pub fn diff(&self, template_id: &str, from: u32, to: u32) -> Result<TemplateDiff> {
+        let from = self.version(template_id, from)?;
+        let to = self.version(template_id, to)?;
+        from.diff(&to)
+    }
+
+    /// Records `template` as a new version unless it equals the latest one.
+    ///
+    /// Returns the recorded or latest version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or written.
+    pub fn record(&self, template: Template) -> Result<TemplateVersion> {
+        let versions = self.store.list(&template.id)?;
+        if let Some(latest) = versions.last()
+            && versioned(&latest.template)? == versioned(&template)?
+        {
+            return Ok(latest.clone());
+        }
+
+        let version = TemplateVersion {
+            template_id: template.id.clone(),
+            version: versions.last().map_or(1, |latest| latest.version + 1),
+            captured_at: Utc::now(),
+            template,
+        };
+        self.store.save(&version)?;
+        Ok(version)
+    }
+}
+
+impl<C: TemplatesService, S: VersionStore> TemplateHistory<C, S> {
+    /// Fetches a template and records it as a new version if it changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template cannot be fetched or the store fails.
+    pub async fn snapshot(&self, template_id: &str) -> Result<TemplateVersion> {
+        let template = self
+            .client
+            .get_template(template_id, GetTemplateQuery::default())
+            .await?;
+        self.record(template)
+    }
+
+    /// Records the current template, updates it, and records the result.
+    ///
+    /// Returns the version of the updated template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or the store fails. The template is
+    /// not updated if its current version cannot be recorded.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, input)))]
+    pub async fn update(
+        &self,
+        template_id: &str,
+        input: TemplateUpdateInput,
+    ) -> Result<TemplateVersion> {
+        self.snapshot(template_id).await?;
+        let template = self.client.update_template(template_id, input).await?;
+        self.record(template)
+    }
+
+    /// Sets a template back to a recorded version.
+    ///
+    /// The restored template is recorded as a new version, so restoring can be
+    /// undone as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version does not exist, a request fails or the
+    /// store fails.
+    pub async fn restore(&self, template_id: &str, version: u32) -> Result<TemplateVersion> {
+        let input = self.version(template_id, version)?.update_input();
+        self.update(template_id, input).await
+    }
+}
+
+impl<C: fmt::Debug, S: fmt::Debug> fmt::Debug for TemplateHistory<C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateHistory")
+            .field("client", &self.client)
+            .field("store", &self.store)
+            .finish()
+    }
+}
+
+/// Serializes the versioned fields of a template.
+///
+/// Empty fields are left out, so that a restored template with an empty
+/// value equals the version it was restored from without the field.
+fn versioned(template: &Template) -> Result<Value> {
+    let mut value = serde_json::to_value(template)?;
+    if let Value::Object(fields) = &mut value {
+        for field in UNVERSIONED_FIELDS {
+            fields.remove(field);
+        }
+        fields.retain(|_, value| match value {
+            Value::Null => false,
+            Value::String(value) => !value.is_empty(),
+            Value::Array(values) => !values.is_empty(),
+            Value::Object(values) => !values.is_empty(),
+            _ => true,
+        });
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::model::v1::EnvVars;
+    use crate::testing::{self, Cloud};
+
+    fn template(image: &str, env: Value) -> Template {
+        testing::template(json!({"imageName": image, "env": env, "earned": 0.0}))
+    }
+
+    #[test]
+    fn test_records_and_diffs_versions() {
+        let history = TemplateHistory::new(());
+        let v1 = history
+            .record(template("org/app:v1", json!({"MODEL": "small"})))
+            .unwrap();
+        let mut unchanged = template("org/app:v1", json!({"MODEL": "small"}));
+        unchanged.earned = Some(1.5);
+        assert_eq!(history.record(unchanged).unwrap().version, 1);
+
+        let v2 = history
+            .record(template("org/app:v2", json!({"MODEL": "large"})))
+            .unwrap();
+        assert_eq!(v2.version, 2);
+
+        let diff = history.diff("template", 1, 2).unwrap();
+        let paths: Vec<&str> = diff.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["env.MODEL", "imageName"]);
+        assert_eq!(diff.field("imageName").unwrap().after, json!("org/app:v2"));
+        assert!(v1.diff(&v1).unwrap().is_empty());
+
+        let restore = v1.update_input();
+        assert_eq!(restore.image_name.as_deref(), Some("org/app:v1"));
+        assert!(history.version("template", 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_clears_later_fields() {
+        let cloud = Cloud::new().with_template(testing::template(json!({})));
+        let history = TemplateHistory::new(cloud);
+
+        let update = TemplateUpdateInput {
+            image_name: Some("org/app:v2".to_string()),
+            env: Some(EnvVars::from([("MODEL".to_string(), "large".to_string())])),
+            ports: Some(vec!["8888/http".to_string()]),
+            ..Default::default()
+        };
+        let v2 = history.update("template", update).await.unwrap();
+        assert_eq!(v2.version, 2);
+
+        let v3 = history.restore("template", 1).await.unwrap();
+        assert_eq!(v3.version, 3);
+        assert!(history.diff("template", 1, 3).unwrap().is_empty());
+
+        let template = history.client.template("template").unwrap();
+        assert_eq!(template.image_name, "org/app:v1");
+        assert!(template.env.unwrap_or_default().is_empty());
+        assert!(template.ports.unwrap_or_default().is_empty());
+    }
+
+    #[test]
+    fn test_json_file_store() {
+        let directory =
+            std::env::temp_dir().join(format!("runpod-versions-{}", std::process::id()));
+        let store = JsonFileStore::new(&directory);
+        assert!(store.list("template").unwrap().is_empty());
+
+        let history = TemplateHistory::new(()).with_store(store.clone());
+        history.record(template("org/app:v1", json!({}))).unwrap();
+        history.record(template("org/app:v2", json!({}))).unwrap();
+
+        let versions = store.list("template").unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[1].template.image_name, "org/app:v2");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::TemplateVersion;
+use crate::Result;
+
+/// Storage for [`TemplateVersion`]s.
+///
+/// Implemented by [`MemoryStore`] and [`JsonFileStore`]; implement it to keep
+/// versions in a database or object storage.
+pub trait VersionStore {
+    /// Appends `version` to the history of its template.
+    fn save(&self, version: &TemplateVersion) -> Result<()>;
+
+    /// Returns the versions of `template_id`, oldest first.
+    fn list(&self, template_id: &str) -> Result<Vec<TemplateVersion>>;
+}
+
+/// [`VersionStore`] keeping versions in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    versions: Mutex<HashMap<String, Vec<TemplateVersion>>>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl VersionStore for MemoryStore {
+    fn save(&self, version: &TemplateVersion) -> Result<()> {
+        let mut versions = self.versions.lock().unwrap_or_else(|e| e.into_inner());
+        versions
+            .entry(version.template_id.clone())
+            .or_default()
+            .push(version.clone());
+        Ok(())
+    }
+
+    fn list(&self, template_id: &str) -> Result<Vec<TemplateVersion>> {
+        let versions = self.versions.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(versions.get(template_id).cloned().unwrap_or_default())
+    }
+}
+
+/// [`VersionStore`] keeping the versions of each template in a JSON file.
+///
+/// The history of a template is stored in `<directory>/<template_id>.json`,
+/// which is created on the first save.
+#[derive(Debug, Clone)]
+pub struct JsonFileStore {
+    directory: PathBuf,
+}
+
+impl JsonFileStore {
+    /// Creates a store writing to `directory`.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns the directory versions are stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn path(&self, template_id: &str) -> PathBuf {
+        let name: String = template_id
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        self.directory.join(format!("{name}.json"))
+    }
+}
+
+impl VersionStore for JsonFileStore {
+    fn save(&self, version: &TemplateVersion) -> Result<()> {
+        let mut versions = self.list(&version.template_id)?;
+        versions.push(version.clone());
+
+        std::fs::create_dir_all(&self.directory)?;
+        let path = self.path(&version.template_id);
+        let temporary = path.with_extension("json.tmp");
+        std::fs::write(&temporary, serde_json::to_vec_pretty(&versions)?)?;
+        std::fs::rename(temporary, path)?;
+        Ok(())
+    }
+
+    fn list(&self, template_id: &str) -> Result<Vec<TemplateVersion>> {
+        match std::fs::read(self.path(template_id)) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,830 @@
+//! File access to network volumes through their S3-compatible API.
+//!
+//! [`VolumesService`](crate::service::v1::VolumesService) manages network
+//! volume metadata only. Every data center also exposes the files of its
+//! network volumes through an S3-compatible API, in which the volume ID is the
+//! bucket name. [`VolumeData`] lists, uploads, downloads and deletes those
+//! files using [`S3Credentials`].
+//!
+//! Files larger than the part size are uploaded in parts, so large model
+//! weights never have to fit in memory, and an interrupted
+//! [`download_file`](VolumeData::download_file) resumes where the local file
+//! ends, as long as the remote file has not changed.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::service::v1::VolumesService;
+//! use runpod_sdk::volume_data::{S3Credentials, VolumeData};
+//! use runpod_sdk::{Result, RunpodClient};
+//!
+//! # async fn example() -> Result<()> {
+//! let client = RunpodClient::from_env()?;
+//! let volume = client.get_volume("volume_id").await?;
+//!
+//! let credentials = S3Credentials::new("user_id", "s3_api_key");
+//! let data = VolumeData::new(&volume, credentials);
+//!
+//! data.upload_file("models/weights.safetensors", "weights.safetensors")
+//!     .await?;
+//! for object in data.list("models/").await? {
+//!     println!("{} ({} bytes)", object.key, object.size);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod signer;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use reqwest::{Method, Response, StatusCode, header};
+use serde::{Deserialize, Serialize};
+pub use signer::S3Credentials;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use self::signer::{SignedRequest, canonical_query, sha256_hex, uri_encode};
+use crate::model::v1::NetworkVolume;
+use crate::{Error, Result};
+
+/// Default size of the parts of multipart uploads.
+pub const DEFAULT_PART_SIZE: usize = 64 * 1024 * 1024;
+
+/// A file stored on a network volume.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeObject {
+    /// Path of the file relative to the volume root.
+    pub key: String,
+
+    /// Size in bytes.
+    pub size: u64,
+
+    /// When the file was last modified.
+    pub last_modified: Option<DateTime<Utc>>,
+
+    /// Entity tag of the file content.
+    pub etag: Option<String>,
+}
+
+/// Client for the files of a network volume.
+#[derive(Debug, Clone)]
+pub struct VolumeData {
+    http: reqwest::Client,
+    endpoint: String,
+    region: String,
+    bucket: String,
+    credentials: S3Credentials,
+    part_size: usize,
+}
+
+impl VolumeData {
+    /// Creates a client for the files of `volume`.
+    ///
+    /// Requests are sent to the S3-compatible API of the volume's data center,
+    /// e.g. `https://s3api-eu-ro-1.runpod.io` for `EU-RO-1`.
+    pub fn new(volume: &NetworkVolume, credentials: S3Credentials) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            endpoint: format!(
+                "https://s3api-{}.runpod.io",
+                volume.data_center_id.to_lowercase()
+            ),
+            region: volume.data_center_id.clone(),
+            bucket: volume.id.clone(),
+            credentials,
+            part_size: DEFAULT_PART_SIZE,
+        }
+    }
+
+    /// Replaces the S3 API endpoint, e.g. with a local S3-compatible server.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Replaces the HTTP client.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sets the size of the parts of multipart uploads.
+    ///
+    /// Files up to this size are uploaded in a single request. S3 requires
+    /// parts other than the last to be at least 5 MiB.
+    pub fn with_part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size.max(1);
+        self
+    }
+
+    /// Returns the S3 API endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the bucket name, i.e. the volume ID.
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// Lists the files whose key starts with `prefix`, ordered by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or a response cannot be parsed.
+    pub async fn list(&self, prefix: &str) -> Result<Vec<VolumeObject>> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut query = vec![("list-type", "2".to_string())];
+            if !prefix.is_empty() {
+                query.push(("prefix", prefix.to_string()));
+            }
+            if let Some(token) = continuation_token.take() {
+                query.push(("continuation-token", token));
+            }
+
+            let response = self
+                .send(Method::GET, None, &query, &[], Vec::new())
+                .await?;
+            let page: ListBucketResult = parse_xml(&response.text().await?)?;
+            objects.extend(page.contents.into_iter().map(|object| {
+                VolumeObject {
+                    key: object.key,
+                    size: object.size,
+                    last_modified: object
+                        .last_modified
+                        .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+                        .map(|time| time.with_timezone(&Utc)),
+                    etag: object.e_tag,
+                }
+            }));
+
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => return Ok(objects),
+            }
+        }
+    }
+
+    /// Uploads `body` to `key` in a single request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn upload(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.send(Method::PUT, Some(key), &[], &[], body).await?;
+        Ok(())
+    }
+
+    /// Uploads the local file at `path` to `key`.
+    ///
+    /// Files larger than the part size are uploaded in parts read one at a
+    /// time. A failed multipart upload is aborted so that no parts are left
+    /// behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a request fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, path)))]
+    pub async fn upload_file(&self, key: &str, path: impl AsRef<Path>) -> Result<()> {
+        let mut file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+        if size <= self.part_size as u64 {
+            let mut body = Vec::with_capacity(size as usize);
+            file.read_to_end(&mut body).await?;
+            return self.upload(key, body).await;
+        }
+
+        let query = [("uploads", String::new())];
+        let response = self
+            .send(Method::POST, Some(key), &query, &[], Vec::new())
+            .await?;
+        let upload: InitiateMultipartUploadResult = parse_xml(&response.text().await?)?;
+
+        match self.upload_parts(key, &upload.upload_id, &mut file).await {
+            Ok(parts) => {
+                let body = quick_xml::se::to_string(&CompleteMultipartUpload { parts })
+                    .map_err(|e| Error::S3(format!("invalid request: {e}")))?;
+                let query = [("uploadId", upload.upload_id)];
+                let response = self
+                    .send(Method::POST, Some(key), &query, &[], body.into_bytes())
+                    .await?;
+                // Completion errors may be reported with a success status.
+                let body = response.text().await?;
+                match body.contains("<Error>") {
+                    true => Err(parse_xml::<S3Error>(&body)?.into()),
+                    false => Ok(()),
+                }
+            }
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %e, "Aborting multipart upload");
+
+                let query = [("uploadId", upload.upload_id)];
+                // The upload error is more useful than a failure to abort.
+                let _ = self
+                    .send(Method::DELETE, Some(key), &query, &[], Vec::new())
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Downloads the file at `key` into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn download(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self
+            .send(Method::GET, Some(key), &[], &[], Vec::new())
+            .await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Downloads the file at `key` to the local file at `path`.
+    ///
+    /// While downloading, the ETag of the remote file is kept next to the
+    /// local file, in `path` with an `.etag` suffix. If the local file exists
+    /// and that ETag still matches the remote file, only the remaining bytes
+    /// are requested, with an `If-Range` condition, and appended to it, so an
+    /// interrupted download can be resumed by calling this method again.
+    /// Otherwise the file is downloaded from the start. Returns the size of
+    /// the local file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or the file cannot be written.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, path)))]
+    pub async fn download_file(&self, key: &str, path: impl AsRef<Path>) -> Result<u64> {
+        let path = path.as_ref();
+        let etag_path = etag_path(path);
+        let offset = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+
+        let head = self
+            .send(Method::HEAD, Some(key), &[], &[], Vec::new())
+            .await?;
+        let etag = header_value(&head, header::ETAG);
+        let size = header_value(&head, header::CONTENT_LENGTH).and_then(|size| size.parse().ok());
+        let stored = match offset {
+            0 => None,
+            _ => tokio::fs::read_to_string(&etag_path).await.ok(),
+        };
+        let resume = etag.is_some() && stored == etag;
+        if resume && size == Some(offset) {
+            // The local file already holds every byte.
+            tokio::fs::remove_file(&etag_path).await?;
+            return Ok(offset);
+        }
+
+        let headers = match (&etag, resume) {
+            (Some(etag), true) => vec![
+                ("range", format!("bytes={offset}-")),
+                ("if-range", etag.clone()),
+            ],
+            _ => Vec::new(),
+        };
+        let mut response = self
+            .send(Method::GET, Some(key), &[], &headers, Vec::new())
+            .await?;
+        match header_value(&response, header::ETAG).or(etag) {
+            Some(etag) => tokio::fs::write(&etag_path, etag).await?,
+            None => remove_if_exists(&etag_path).await?,
+        }
+
+        // The server sends the whole file if the ETag no longer matches.
+        let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(path)
+            .await?;
+        let mut size = if resumed { offset } else { 0 };
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+        file.flush().await?;
+        remove_if_exists(&etag_path).await?;
+        Ok(size)
+    }
+
+    /// Deletes the file at `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn delete(&self, key: &str) -> Result<()> {
+        self.send(Method::DELETE, Some(key), &[], &[], Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        file: &mut tokio::fs::File,
+    ) -> Result<Vec<CompletedPart>> {
+        let mut parts = Vec::new();
+        loop {
+            let mut body = Vec::with_capacity(self.part_size);
+            (&mut *file)
+                .take(self.part_size as u64)
+                .read_to_end(&mut body)
+                .await?;
+            if body.is_empty() {
+                return Ok(parts);
+            }
+
+            let part_number = parts.len() as u32 + 1;
+            let query = [
+                ("partNumber", part_number.to_string()),
+                ("uploadId", upload_id.to_string()),
+            ];
+            let response = self.send(Method::PUT, Some(key), &query, &[], body).await?;
+            let e_tag = response
+                .headers()
+                .get(header::ETAG)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| Error::S3(format!("part {part_number} has no ETag")))?;
+            parts.push(CompletedPart {
+                part_number,
+                e_tag: e_tag.to_string(),
+            });
+        }
+    }
+
+    /// Sends a signed request and fails on error statuses.
+    async fn send(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        query: &[(&str, String)],
+        headers: &[(&str, String)],
+        body: Vec<u8>,
+    ) -> Result<Response> {
+        check(self.request(method, key, query, headers, body).await?).await
+    }
+
+    /// Sends a signed request.
+    async fn request(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        query: &[(&str, String)],
+        headers: &[(&str, String)],
+        body: Vec<u8>,
+    ) -> Result<Response> {
+        let mut path = format!("/{}", uri_encode(&self.bucket, true));
+        if let Some(key) = key {
+            path.push('/');
+            path.push_str(&uri_encode(key, false));
+        }
+        let query_string = canonical_query(query);
+        let url = match query_string.as_str() {
+            "" => format!("{}{path}", self.endpoint),
+            _ => format!("{}{path}?{query_string}", self.endpoint),
+        };
+        let host = reqwest::Url::parse(&url)
+            .ok()
+            .and_then(|url| {
+                let host = url.host_str()?.to_string();
+                Some(match url.port() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host,
+                })
+            })
+            .ok_or_else(|| Error::S3(format!("invalid endpoint {}", self.endpoint)))?;
+
+        let now = Utc::now();
+        let payload_hash = sha256_hex(&body);
+        let mut signed: BTreeMap<&str, String> = headers.iter().cloned().collect();
+        signed.insert("x-amz-content-sha256", payload_hash.clone());
+        signed.insert("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string());
+        let authorization = SignedRequest {
+            method: method.as_str(),
+            host: &host,
+            path: &path,
+            query,
+            headers: &signed,
+            payload_hash: &payload_hash,
+        }
+        .authorization(&self.credentials, &self.region, now);
+
+        let mut request = self
+            .http
+            .request(method, url)
+            .header(header::AUTHORIZATION, authorization)
+            .body(body);
+        for (name, value) in &signed {
+            request = request.header(*name, value);
+        }
+
+        Ok(request.send().await?)
+    }
+}
+
+/// Returns the path keeping the ETag of a download to `path`.
+fn etag_path(path: &Path) -> PathBuf {
+    let mut etag_path = path.as_os_str().to_owned();
+    etag_path.push(".etag");
+    PathBuf::from(etag_path)
+}
+
+async fn remove_if_exists(path: &Path) -> Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn header_value(response: &Response, name: header::HeaderName) -> Option<String> {
+    let value = response.headers().get(name)?;
+    value.to_str().ok().map(str::to_string)
+}
+
+/// Turns error statuses into [`Error::S3`].
+async fn check(response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    Err(match parse_xml::<S3Error>(&body) {
+        Ok(error) => Error::S3(format!("{status}: {error}")),
+        Err(_) => Error::S3(format!("{status}: {}", body.trim())),
+    })
+}
+
+/// Body of an S3 error response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct S3Error {
+    code: String,
+    message: Option<String>,
+}
+
+impl std::fmt::Display for S3Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {message}", self.code),
+            None => f.write_str(&self.code),
+        }
+    }
+}
+
+impl From<S3Error> for Error {
+    fn from(error: S3Error) -> Self {
+        Error::S3(error.to_string())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListBucketResult {
+    #[serde(default)]
+    contents: Vec<ListedObject>,
+    #[serde(default)]
+    is_truncated: bool,
+    next_continuation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListedObject {
+    key: String,
+    size: u64,
+    last_modified: Option<String>,
+    e_tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CompleteMultipartUpload {
+    #[serde(rename = "Part")]
+    parts: Vec<CompletedPart>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct CompletedPart {
+    part_number: u32,
+    e_tag: String,
+}
+
+fn parse_xml<T: serde::de::DeserializeOwned>(body: &str) -> Result<T> {
+    quick_xml::de::from_str(body).map_err(|e| Error::S3(format!("invalid response: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, Uri};
+    use axum::response::IntoResponse;
+
+    use super::*;
+
+    /// In-memory S3-compatible stand-in for a single bucket.
+    #[derive(Default)]
+    struct Bucket {
+        objects: BTreeMap<String, Vec<u8>>,
+        parts: BTreeMap<u32, Vec<u8>>,
+        requests: Vec<String>,
+    }
+
+    type Shared = Arc<Mutex<Bucket>>;
+
+    /// Decodes a URI-encoded query value.
+    fn decode(value: &str) -> String {
+        let mut bytes = Vec::new();
+        let mut rest = value.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            match (byte, tail.get(..2)) {
+                (b'%', Some(hex)) => {
+                    let hex = std::str::from_utf8(hex).unwrap();
+                    bytes.push(u8::from_str_radix(hex, 16).unwrap());
+                    rest = &tail[2..];
+                }
+                _ => {
+                    bytes.push(byte);
+                    rest = tail;
+                }
+            }
+        }
+        String::from_utf8(bytes).unwrap()
+    }
+
+    fn error(status: StatusCode, code: &str) -> axum::response::Response {
+        let body = format!("<Error><Code>{code}</Code><Message>stand-in</Message></Error>");
+        (status, body).into_response()
+    }
+
+    async fn handle(
+        State(bucket): State<Shared>,
+        method: Method,
+        uri: Uri,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> axum::response::Response {
+        let authorized = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("AWS4-HMAC-SHA256 Credential=user/"));
+        if !authorized {
+            return error(StatusCode::FORBIDDEN, "AccessDenied");
+        }
+
+        let query: HashMap<&str, String> = uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (name, decode(value))
+            })
+            .collect();
+        let key = uri.path().strip_prefix("/volume").unwrap_or_default();
+        let key = key.trim_start_matches('/').to_string();
+        let range = headers
+            .get(header::RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let mut bucket = bucket.lock().unwrap();
+        let path = uri.path_and_query().unwrap();
+        bucket.requests.push(match &range {
+            Some(range) => format!("{method} {path} {range}"),
+            None => format!("{method} {path}"),
+        });
+
+        match method {
+            Method::GET if key.is_empty() => {
+                let prefix = query.get("prefix").map_or("", String::as_str);
+                let after = query.get("continuation-token").map_or("", String::as_str);
+                let keys: Vec<&String> = bucket
+                    .objects
+                    .keys()
+                    .filter(|key| key.starts_with(prefix) && key.as_str() > after)
+                    .collect();
+                let mut body = format!(
+                    "<ListBucketResult><IsTruncated>{}</IsTruncated>",
+                    keys.len() > 2
+                );
+                if keys.len() > 2 {
+                    body.push_str(&format!(
+                        "<NextContinuationToken>{}</NextContinuationToken>",
+                        keys[1]
+                    ));
+                }
+                for key in keys.into_iter().take(2) {
+                    body.push_str(&format!(
+                        "<Contents><Key>{key}</Key><Size>{}</Size>\
+                         <LastModified>2025-01-06T09:00:00.000Z</LastModified>\
+                         <ETag>&quot;etag&quot;</ETag></Contents>",
+                        bucket.objects[key].len()
+                    ));
+                }
+                body.push_str("</ListBucketResult>");
+                body.into_response()
+            }
+            Method::HEAD | Method::GET => {
+                let Some(object) = bucket.objects.get(&key) else {
+                    return error(StatusCode::NOT_FOUND, "NoSuchKey");
+                };
+                let etag = format!("\"{}\"", sha256_hex(object));
+                let etag_header = [(header::ETAG, etag.clone())];
+                if method == Method::HEAD {
+                    let length = object.len().to_string();
+                    return ([(header::ETAG, etag), (header::CONTENT_LENGTH, length)], "")
+                        .into_response();
+                }
+
+                let if_range = headers.get(header::IF_RANGE);
+                let range = range.filter(|_| if_range.is_none_or(|value| *value == etag));
+                let start = range.and_then(|range| {
+                    range
+                        .strip_prefix("bytes=")?
+                        .strip_suffix('-')?
+                        .parse()
+                        .ok()
+                });
+                match start {
+                    Some(start) if start >= object.len() => {
+                        error(StatusCode::RANGE_NOT_SATISFIABLE, "InvalidRange")
+                    }
+                    Some(start) => (
+                        StatusCode::PARTIAL_CONTENT,
+                        etag_header,
+                        object[start..].to_vec(),
+                    )
+                        .into_response(),
+                    None => (etag_header, object.clone()).into_response(),
+                }
+            }
+            Method::PUT if query.contains_key("partNumber") => {
+                let part: u32 = query["partNumber"].parse().unwrap();
+                bucket.parts.insert(part, body.to_vec());
+                let etag = format!("\"part-{part}\"");
+                ([(header::ETAG, etag)], "").into_response()
+            }
+            Method::PUT => {
+                bucket.objects.insert(key, body.to_vec());
+                StatusCode::OK.into_response()
+            }
+            Method::POST if query.contains_key("uploads") => {
+                let body = "<InitiateMultipartUploadResult><UploadId>upload</UploadId>\
+                            </InitiateMultipartUploadResult>";
+                body.into_response()
+            }
+            Method::POST => {
+                let listed = String::from_utf8_lossy(&body)
+                    .matches("<PartNumber>")
+                    .count();
+                if listed != bucket.parts.len() {
+                    return error(StatusCode::BAD_REQUEST, "InvalidPart");
+                }
+                let object = std::mem::take(&mut bucket.parts).into_values().flatten();
+                let object = object.collect();
+                bucket.objects.insert(key, object);
+                "<CompleteMultipartUploadResult/>".into_response()
+            }
+            Method::DELETE if query.contains_key("uploadId") => {
+                bucket.parts.clear();
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Method::DELETE => {
+                bucket.objects.remove(&key);
+                StatusCode::NO_CONTENT.into_response()
+            }
+            _ => error(StatusCode::METHOD_NOT_ALLOWED, "MethodNotAllowed"),
+        }
+    }
+
+    async fn serve() -> (VolumeData, Shared) {
+        let bucket = Shared::default();
+        let app = axum::Router::new()
+            .fallback(handle)
+            .with_state(bucket.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let volume = NetworkVolume {
+            id: "volume".to_string(),
+            name: "models".to_string(),
+            size: 10,
+            data_center_id: "EU-RO-1".to_string(),
+        };
+        let data = VolumeData::new(&volume, S3Credentials::new("user", "secret"))
+            .with_endpoint(format!("http://{address}"));
+        (data, bucket)
+    }
+
+    #[tokio::test]
+    async fn test_lists_uploads_downloads_and_deletes() {
+        let (data, _) = serve().await;
+        assert_eq!(data.bucket(), "volume");
+
+        data.upload("readme.md", b"hello".to_vec()).await.unwrap();
+        data.upload("models/a.bin", vec![1; 3]).await.unwrap();
+        data.upload("models/b.bin", vec![2; 4]).await.unwrap();
+
+        let objects = data.list("").await.unwrap();
+        let keys: Vec<&str> = objects.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, vec!["models/a.bin", "models/b.bin", "readme.md"]);
+        assert_eq!(objects[1].size, 4);
+        assert_eq!(objects[1].etag.as_deref(), Some("\"etag\""));
+        assert!(objects[1].last_modified.is_some());
+        assert_eq!(data.list("models/").await.unwrap().len(), 2);
+
+        assert_eq!(data.download("readme.md").await.unwrap(), b"hello");
+        data.delete("readme.md").await.unwrap();
+        let error = data.download("readme.md").await.unwrap_err();
+        assert!(matches!(error, Error::S3(message) if message.contains("NoSuchKey")));
+
+        let unreachable = data.with_endpoint("http://127.0.0.1:1");
+        assert!(unreachable.list("").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_and_resumed_download() {
+        let (data, bucket) = serve().await;
+        let data = data.with_part_size(4);
+        let directory =
+            std::env::temp_dir().join(format!("runpod-volume-data-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let source = directory.join("weights.bin");
+        let content: Vec<u8> = (0..10).collect();
+        std::fs::write(&source, &content).unwrap();
+
+        data.upload_file("weights.bin", &source).await.unwrap();
+        let requests = std::mem::take(&mut bucket.lock().unwrap().requests);
+        assert_eq!(
+            requests,
+            vec![
+                "POST /volume/weights.bin?uploads=",
+                "PUT /volume/weights.bin?partNumber=1&uploadId=upload",
+                "PUT /volume/weights.bin?partNumber=2&uploadId=upload",
+                "PUT /volume/weights.bin?partNumber=3&uploadId=upload",
+                "POST /volume/weights.bin?uploadId=upload",
+            ]
+        );
+        assert_eq!(bucket.lock().unwrap().objects["weights.bin"], content);
+
+        let target = directory.join("downloaded.bin");
+        let etag_path = directory.join("downloaded.bin.etag");
+        std::fs::write(&target, &content[..6]).unwrap();
+        std::fs::write(&etag_path, format!("\"{}\"", sha256_hex(&content))).unwrap();
+        assert_eq!(
+            data.download_file("weights.bin", &target).await.unwrap(),
+            10
+        );
+        assert_eq!(std::fs::read(&target).unwrap(), content);
+        assert!(!etag_path.exists());
+        let requests = std::mem::take(&mut bucket.lock().unwrap().requests);
+        assert_eq!(
+            requests,
+            vec![
+                "HEAD /volume/weights.bin",
+                "GET /volume/weights.bin bytes=6-"
+            ]
+        );
+        assert_eq!(
+            data.download_file("weights.bin", &target).await.unwrap(),
+            10
+        );
+
+        // A partial download of a previous version is not resumed.
+        std::fs::write(&target, [9; 6]).unwrap();
+        std::fs::write(&etag_path, "\"previous\"").unwrap();
+        assert_eq!(
+            data.download_file("weights.bin", &target).await.unwrap(),
+            10
+        );
+        assert_eq!(std::fs::read(&target).unwrap(), content);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
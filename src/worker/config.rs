@@ -0,0 +1,305 @@
+//! Serverless worker configuration and builder.
+
+use std::fmt;
+use std::time::Duration;
+
+use derive_builder::Builder;
+
+use crate::Result;
+use crate::client::RunpodBuilderError;
+
+/// Configuration for a serverless [`Worker`](super::Worker).
+///
+/// Inside a RunPod serverless container the configuration is read from the
+/// environment with [`from_env`](Self::from_env). The builder is useful to
+/// point a worker at a local stand-in job server.
+///
+/// URL templates follow the RunPod worker protocol: `$ID` in the job-take URL
+/// and `$RUNPOD_POD_ID` in the result and heartbeat URLs are replaced by the
+/// worker ID, and `$ID` in the result URL by the job ID.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use runpod_sdk::worker::WorkerConfig;
+/// let config = WorkerConfig::builder()
+///     .with_api_key("local")
+///     .with_worker_id("local-worker")
+///     .with_job_take_url("http://localhost:8000/job-take/$ID")
+///     .with_job_done_url("http://localhost:8000/job-done/$RUNPOD_POD_ID/$ID")
+///     .with_concurrency(4usize)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Builder)]
+#[builder(
+    name = "WorkerBuilder",
+    pattern = "owned",
+    setter(into, strip_option, prefix = "with"),
+    build_fn(validate = "Self::validate_config", error = "RunpodBuilderError")
+)]
+pub struct WorkerConfig {
+    /// API key sent with every job server request.
+    api_key: String,
+
+    /// Identifier of this worker, usually the Pod ID.
+    worker_id: String,
+
+    /// URL template jobs are taken from.
+    job_take_url: String,
+
+    /// URL template job results are posted to.
+    job_done_url: String,
+
+    /// URL template partial outputs of streaming jobs are posted to.
+    ///
+    /// Partial outputs are not posted if unset.
+    #[builder(default)]
+    stream_url: Option<String>,
+
+    /// Whether streaming jobs return the list of their partial outputs.
+    ///
+    /// Equivalent to `return_aggregate_stream` of the Python SDK.
+    #[builder(default)]
+    aggregate_stream: bool,
+
+    /// URL template heartbeats are sent to.
+    ///
+    /// No heartbeats are sent if unset.
+    #[builder(default)]
+    ping_url: Option<String>,
+
+    /// Interval between heartbeats.
+    #[builder(default = "Self::default_ping_interval()")]
+    ping_interval: Duration,
+
+    /// Maximum number of jobs processed at once.
+    ///
+    /// Defaults to 1. A concurrency modifier can adjust it at runtime.
+    #[builder(default = "1")]
+    concurrency: usize,
+
+    /// Delay before polling again after the job server had no job.
+    #[builder(default = "Self::default_poll_interval()")]
+    poll_interval: Duration,
+
+    /// Timeout for job server requests.
+    #[builder(default = "Self::default_timeout()")]
+    timeout: Duration,
+}
+
+impl WorkerBuilder {
+    /// Returns the default heartbeat interval.
+    fn default_ping_interval() -> Duration {
+        Duration::from_millis(10_000)
+    }
+
+    /// Returns the default poll interval.
+    fn default_poll_interval() -> Duration {
+        Duration::from_millis(100)
+    }
+
+    /// Returns the default timeout.
+    fn default_timeout() -> Duration {
+        Duration::from_secs(90)
+    }
+
+    /// Validates the configuration before building.
+    fn validate_config(&self) -> Result<(), String> {
+        if let Some(ref worker_id) = self.worker_id
+            && worker_id.trim().is_empty()
+        {
+            return Err("Worker ID cannot be empty".to_string());
+        }
+
+        if self.concurrency == Some(0) {
+            return Err("Concurrency must be greater than 0".to_string());
+        }
+
+        if self
+            .ping_interval
+            .is_some_and(|interval| interval.is_zero())
+        {
+            return Err("Ping interval must be greater than 0".to_string());
+        }
+
+        if self.timeout.is_some_and(|timeout| timeout.is_zero()) {
+            return Err("Timeout must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl WorkerConfig {
+    /// Creates a new configuration builder.
+    pub fn builder() -> WorkerBuilder {
+        WorkerBuilder::default()
+    }
+
+    /// Creates a configuration from the environment of a serverless worker.
+    ///
+    /// Reads `RUNPOD_AI_API_KEY`, `RUNPOD_POD_ID`, `RUNPOD_WEBHOOK_GET_JOB`
+    /// and `RUNPOD_WEBHOOK_POST_OUTPUT`. Optionally reads `RUNPOD_WEBHOOK_POST_STREAM`,
+    /// `RUNPOD_WEBHOOK_PING` and `RUNPOD_PING_INTERVAL` (in milliseconds) if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required environment variable is not set
+    /// or any environment variable contains an invalid value.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| {
+                RunpodBuilderError::ValidationError(format!("{name} environment variable not set"))
+            })
+        };
+
+        let mut builder = Self::builder()
+            .with_api_key(var("RUNPOD_AI_API_KEY")?)
+            .with_worker_id(var("RUNPOD_POD_ID")?)
+            .with_job_take_url(var("RUNPOD_WEBHOOK_GET_JOB")?)
+            .with_job_done_url(var("RUNPOD_WEBHOOK_POST_OUTPUT")?);
+
+        // Optional: streamed outputs
+        if let Ok(stream_url) = std::env::var("RUNPOD_WEBHOOK_POST_STREAM") {
+            builder = builder.with_stream_url(stream_url);
+        }
+
+        // Optional: heartbeats
+        if let Ok(ping_url) = std::env::var("RUNPOD_WEBHOOK_PING") {
+            builder = builder.with_ping_url(ping_url);
+        }
+
+        // Optional: heartbeat interval
+        if let Ok(interval_str) = std::env::var("RUNPOD_PING_INTERVAL") {
+            let interval_ms = interval_str.parse::<u64>().map_err(|_| {
+                RunpodBuilderError::ValidationError(format!(
+                    "Invalid RUNPOD_PING_INTERVAL value: {}",
+                    interval_str
+                ))
+            })?;
+            builder = builder.with_ping_interval(Duration::from_millis(interval_ms));
+        }
+
+        builder.build().map_err(Into::into)
+    }
+
+    /// Returns the API key.
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Returns the worker ID.
+    pub fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    /// Returns the URL jobs are taken from.
+    pub fn job_take_url(&self) -> String {
+        self.job_take_url.replace("$ID", &self.worker_id)
+    }
+
+    /// Returns the URL the result of job `job_id` is posted to.
+    pub fn job_done_url(&self, job_id: &str) -> String {
+        self.job_done_url
+            .replace("$RUNPOD_POD_ID", &self.worker_id)
+            .replace("$ID", job_id)
+    }
+
+    /// Returns the URL partial outputs of job `job_id` are posted to, if any.
+    pub fn stream_url(&self, job_id: &str) -> Option<String> {
+        self.stream_url.as_ref().map(|url| {
+            url.replace("$RUNPOD_POD_ID", &self.worker_id)
+                .replace("$ID", job_id)
+        })
+    }
+
+    /// Returns whether streaming jobs return the list of their partial outputs.
+    pub fn aggregate_stream(&self) -> bool {
+        self.aggregate_stream
+    }
+
+    /// Returns the URL heartbeats are sent to, if any.
+    pub fn ping_url(&self) -> Option<String> {
+        self.ping_url
+            .as_ref()
+            .map(|url| url.replace("$RUNPOD_POD_ID", &self.worker_id))
+    }
+
+    /// Returns the heartbeat interval.
+    pub fn ping_interval(&self) -> Duration {
+        self.ping_interval
+    }
+
+    /// Returns the maximum number of concurrent jobs.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Returns the delay between polls while no job is available.
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Returns the timeout duration.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl fmt::Debug for WorkerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerConfig")
+            .field("api_key", &"****")
+            .field("worker_id", &self.worker_id)
+            .field("job_take_url", &self.job_take_url)
+            .field("job_done_url", &self.job_done_url)
+            .field("stream_url", &self.stream_url)
+            .field("aggregate_stream", &self.aggregate_stream)
+            .field("ping_url", &self.ping_url)
+            .field("ping_interval", &self.ping_interval)
+            .field("concurrency", &self.concurrency)
+            .field("poll_interval", &self.poll_interval)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urls_are_templated() {
+        let config = WorkerConfig::builder()
+            .with_api_key("key")
+            .with_worker_id("worker")
+            .with_job_take_url("http://host/job-take/$ID?gpu=A100")
+            .with_job_done_url("http://host/job-done/$RUNPOD_POD_ID/$ID?gpu=A100")
+            .with_ping_url("http://host/ping/$RUNPOD_POD_ID")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            config.job_take_url(),
+            "http://host/job-take/worker?gpu=A100"
+        );
+        assert_eq!(
+            config.job_done_url("job"),
+            "http://host/job-done/worker/job?gpu=A100"
+        );
+        assert_eq!(config.ping_url().unwrap(), "http://host/ping/worker");
+    }
+
+    #[test]
+    fn test_zero_concurrency_is_rejected() {
+        let result = WorkerConfig::builder()
+            .with_api_key("key")
+            .with_worker_id("worker")
+            .with_job_take_url("http://host/job-take/$ID")
+            .with_job_done_url("http://host/job-done/$RUNPOD_POD_ID/$ID")
+            .with_concurrency(0usize)
+            .build();
+        assert!(result.is_err());
+    }
+}
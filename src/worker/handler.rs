@@ -0,0 +1,156 @@
+use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::pin;
+
+use futures::{FutureExt, Stream, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::Job;
+use super::runner::Transport;
+
+/// Async function processing a serverless [`Job`].
+///
+/// Implemented for every `Fn(Job<I>) -> impl Future<Output = Result<O, E>>`
+/// whose output serializes to JSON and whose error can be displayed.
+pub trait Handler<I>: Send + Sync + 'static {
+    /// The output of a successful job.
+    type Output: Serialize + Send;
+    /// The error of a failed job.
+    type Error: fmt::Display;
+
+    /// Processes `job`.
+    fn call(&self, job: Job<I>) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send;
+}
+
+impl<F, Fut, I, O, E> Handler<I> for F
+where
+    F: Fn(Job<I>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<O, E>> + Send,
+    O: Serialize + Send,
+    E: fmt::Display,
+{
+    type Error = E;
+    type Output = O;
+
+    fn call(&self, job: Job<I>) -> impl Future<Output = Result<O, E>> + Send {
+        self(job)
+    }
+}
+
+/// Function processing a serverless [`Job`] into a stream of partial outputs.
+///
+/// Implemented for every `Fn(Job<I>) -> impl Stream<Item = Result<O, E>>`
+/// whose items serialize to JSON and whose error can be displayed. An error
+/// item ends the job as failed; outputs streamed before it remain delivered.
+pub trait StreamHandler<I>: Send + Sync + 'static {
+    /// A partial output of the job.
+    type Output: Serialize + Send;
+    /// The error of a failed job.
+    type Error: fmt::Display;
+
+    /// Processes `job`, yielding partial outputs as they are produced.
+    fn call(&self, job: Job<I>) -> impl Stream<Item = Result<Self::Output, Self::Error>> + Send;
+}
+
+impl<F, S, I, O, E> StreamHandler<I> for F
+where
+    F: Fn(Job<I>) -> S + Send + Sync + 'static,
+    S: Stream<Item = Result<O, E>> + Send,
+    O: Serialize + Send,
+    E: fmt::Display,
+{
+    type Error = E;
+    type Output = O;
+
+    fn call(&self, job: Job<I>) -> impl Stream<Item = Result<O, E>> + Send {
+        self(job)
+    }
+}
+
+/// Strategy turning a [`Job`] into its final output or error message.
+///
+/// Sealed: implemented only by [`Unary`] and [`Streaming`].
+pub trait Process<I>: Send + Sync + 'static {
+    /// Whether partial outputs are streamed while the job runs.
+    fn is_streaming(&self) -> bool;
+
+    /// Processes `job`, returning the final output or an error message.
+    fn process(
+        &self,
+        job: Job<I>,
+        transport: &Transport,
+    ) -> impl Future<Output = Result<Value, String>> + Send;
+}
+
+/// Adapter processing jobs with a [`Handler`].
+#[derive(Debug, Clone)]
+pub struct Unary<H>(pub(crate) H);
+
+impl<I, H> Process<I> for Unary<H>
+where
+    I: Send + 'static,
+    H: Handler<I>,
+{
+    fn is_streaming(&self) -> bool {
+        false
+    }
+
+    async fn process(&self, job: Job<I>, _transport: &Transport) -> Result<Value, String> {
+        match AssertUnwindSafe(self.0.call(job)).catch_unwind().await {
+            Ok(Ok(output)) => {
+                serde_json::to_value(output).map_err(|e| format!("Invalid job output: {e}"))
+            }
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err("Handler panicked".to_string()),
+        }
+    }
+}
+
+/// Adapter processing jobs with a [`StreamHandler`].
+///
+/// Every partial output is posted to the stream URL before the next one is
+/// pulled from the handler, so a slow job server applies backpressure to the
+/// handler. The final output is the list of partial outputs if
+/// [`aggregate_stream`](super::WorkerConfig::aggregate_stream) is set, and an
+/// empty list otherwise.
+#[derive(Debug, Clone)]
+pub struct Streaming<H>(pub(crate) H);
+
+impl<I, H> Process<I> for Streaming<H>
+where
+    I: Send + 'static,
+    H: StreamHandler<I>,
+{
+    fn is_streaming(&self) -> bool {
+        true
+    }
+
+    async fn process(&self, job: Job<I>, transport: &Transport) -> Result<Value, String> {
+        let job_id = job.id.clone();
+        let mut stream = pin!(self.0.call(job));
+        let mut aggregate = Vec::new();
+
+        loop {
+            let item = match AssertUnwindSafe(stream.next()).catch_unwind().await {
+                Ok(Some(item)) => item.map_err(|e| e.to_string())?,
+                Ok(None) => break,
+                Err(_) => return Err("Handler panicked".to_string()),
+            };
+
+            let output =
+                serde_json::to_value(item).map_err(|e| format!("Invalid job output: {e}"))?;
+            transport
+                .post_stream(&job_id, &output)
+                .await
+                .map_err(|e| format!("Failed to stream job output: {e}"))?;
+
+            if transport.config.aggregate_stream() {
+                aggregate.push(output);
+            }
+        }
+
+        Ok(Value::Array(aggregate))
+    }
+}
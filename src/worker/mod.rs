@@ -0,0 +1,117 @@
+//! Serverless worker runtime.
+//!
+//! This module is the Rust counterpart of `runpod.serverless.start`: a
+//! [`Worker`] implements the RunPod worker protocol inside a serverless
+//! container. It takes jobs from `RUNPOD_WEBHOOK_GET_JOB`, runs them through
+//! an async [`Handler`], posts outputs and errors to
+//! `RUNPOD_WEBHOOK_POST_OUTPUT`, and sends heartbeats to
+//! `RUNPOD_WEBHOOK_PING`. A [`StreamHandler`] instead yields partial outputs,
+//! which are posted to `RUNPOD_WEBHOOK_POST_STREAM` as they are produced so
+//! clients can read them from `/stream`. A [`ConcurrencyModifier`] can adjust
+//! how many jobs run at once, and on `SIGINT` or `SIGTERM` the worker stops
+//! taking jobs and finishes the ones in progress. An [`InputSchema`]
+//! validates job input before it reaches the handler, like `rp_validator` of
+//! the Python SDK.
+//!
+//! Every URL is configurable through [`WorkerConfig`], so a worker can be
+//! exercised against a local stand-in job server.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use runpod_sdk::Result;
+//! use runpod_sdk::worker::{self, Job};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize)]
+//! struct Input {
+//!     prompt: String,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Output {
+//!     text: String,
+//! }
+//!
+//! async fn handler(job: Job<Input>) -> Result<Output> {
+//!     Ok(Output {
+//!         text: job.input.prompt.to_uppercase(),
+//!     })
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     worker::start(handler).await
+//! }
+//! ```
+
+mod config;
+mod handler;
+mod runner;
+mod schema;
+
+pub use config::{WorkerBuilder, WorkerConfig};
+#[cfg(feature = "emulator")]
+pub(crate) use handler::Process;
+pub use handler::{Handler, StreamHandler, Streaming, Unary};
+#[cfg(feature = "emulator")]
+pub(crate) use runner::shutdown_signal;
+pub use runner::{ConcurrencyModifier, Worker};
+pub use schema::{Field, FieldType, InputSchema, ValidationError, ValidationErrors};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// A serverless job taken from the job server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job<I = serde_json::Value> {
+    /// The unique identifier of the job.
+    pub id: String,
+    /// The job input.
+    pub input: I,
+}
+
+/// Runs a serverless worker configured from the environment.
+///
+/// Processes jobs with `handler` until `SIGINT` or `SIGTERM` is received.
+/// See [`WorkerConfig::from_env`] for the environment variables read.
+///
+/// With the `emulator` feature and the `--rp_serve_api` argument, jobs are
+/// served by a local [`Emulator`](crate::emulator::Emulator) instead.
+pub async fn start<I, H>(handler: H) -> Result<()>
+where
+    I: DeserializeOwned + Send + 'static,
+    H: Handler<I>,
+{
+    #[cfg(feature = "emulator")]
+    if let Some(address) = crate::emulator::serve_api_address() {
+        let emulator = crate::emulator::Emulator::bind(address).await?;
+        return emulator.with_handler(handler).run().await;
+    }
+
+    Worker::from_env(handler)?.run().await
+}
+
+/// Runs a serverless worker streaming partial outputs, configured from the environment.
+///
+/// Processes jobs with `handler` until `SIGINT` or `SIGTERM` is received,
+/// posting every partial output to `RUNPOD_WEBHOOK_POST_STREAM`.
+///
+/// With the `emulator` feature and the `--rp_serve_api` argument, jobs are
+/// served by a local [`Emulator`](crate::emulator::Emulator) instead.
+pub async fn start_streaming<I, H>(handler: H) -> Result<()>
+where
+    I: DeserializeOwned + Send + 'static,
+    H: StreamHandler<I>,
+{
+    #[cfg(feature = "emulator")]
+    if let Some(address) = crate::emulator::serve_api_address() {
+        let emulator = crate::emulator::Emulator::bind(address).await?;
+        return emulator.with_stream_handler(handler).run().await;
+    }
+
+    Worker::streaming(WorkerConfig::from_env()?, handler)
+        .run()
+        .await
+}
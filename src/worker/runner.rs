@@ -0,0 +1,645 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::task::JoinSet;
+
+use super::handler::{Handler, Process, StreamHandler, Streaming, Unary};
+use super::{InputSchema, Job, WorkerConfig};
+use crate::Result;
+
+/// Delay before taking jobs again after the job server failed.
+const ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Number of attempts to deliver a job result.
+const POST_ATTEMPTS: u32 = 3;
+
+/// Adjusts the number of concurrently processed jobs at runtime.
+///
+/// Called before every job take with the current concurrency; the returned
+/// value (at least 1) becomes the new concurrency. The unit type keeps the
+/// configured concurrency, and any `Fn(usize) -> usize` can be used directly.
+pub trait ConcurrencyModifier {
+    /// Returns the desired concurrency given the `current` one.
+    fn modify(&self, current: usize) -> usize;
+}
+
+impl ConcurrencyModifier for () {
+    fn modify(&self, current: usize) -> usize {
+        current
+    }
+}
+
+impl<F> ConcurrencyModifier for F
+where
+    F: Fn(usize) -> usize,
+{
+    fn modify(&self, current: usize) -> usize {
+        self(current)
+    }
+}
+
+/// Serverless worker runtime implementing the RunPod worker protocol.
+///
+/// The worker takes jobs from the job server, runs them through a
+/// [`Handler`] or [`StreamHandler`] with bounded concurrency, posts every
+/// output or error back, and sends heartbeats listing the jobs in progress.
+/// On shutdown it stops taking jobs and waits for the jobs in progress to
+/// finish.
+pub struct Worker<I, P, M = ()> {
+    config: WorkerConfig,
+    handler: P,
+    modifier: M,
+    schema: Option<InputSchema>,
+    _input: PhantomData<fn(I)>,
+}
+
+impl<I, H> Worker<I, Unary<H>>
+where
+    I: DeserializeOwned + Send + 'static,
+    H: Handler<I>,
+{
+    /// Creates a worker processing jobs with `handler`.
+    pub fn new(config: WorkerConfig, handler: H) -> Self {
+        Self {
+            config,
+            handler: Unary(handler),
+            modifier: (),
+            schema: None,
+            _input: PhantomData,
+        }
+    }
+
+    /// Creates a worker configured from the serverless environment.
+    ///
+    /// See [`WorkerConfig::from_env`].
+    pub fn from_env(handler: H) -> Result<Self> {
+        Ok(Self::new(WorkerConfig::from_env()?, handler))
+    }
+}
+
+impl<I, H> Worker<I, Streaming<H>>
+where
+    I: DeserializeOwned + Send + 'static,
+    H: StreamHandler<I>,
+{
+    /// Creates a worker streaming the partial outputs of `handler`.
+    ///
+    /// Partial outputs are posted to the
+    /// [`stream_url`](WorkerConfig::stream_url) as they are produced.
+    pub fn streaming(config: WorkerConfig, handler: H) -> Self {
+        Self {
+            config,
+            handler: Streaming(handler),
+            modifier: (),
+            schema: None,
+            _input: PhantomData,
+        }
+    }
+}
+
+impl<I, P, M> Worker<I, P, M>
+where
+    I: DeserializeOwned + Send + 'static,
+    P: Process<I>,
+    M: ConcurrencyModifier,
+{
+    /// Sets the modifier adjusting concurrency at runtime.
+    pub fn with_concurrency_modifier<M2>(self, modifier: M2) -> Worker<I, P, M2>
+    where
+        M2: ConcurrencyModifier,
+    {
+        Worker {
+            config: self.config,
+            handler: self.handler,
+            modifier,
+            schema: self.schema,
+            _input: PhantomData,
+        }
+    }
+
+    /// Sets the schema job inputs are validated against.
+    ///
+    /// Inputs failing validation are not passed to the handler; the job fails
+    /// with the serialized [`ValidationErrors`](super::ValidationErrors) as
+    /// its error. Valid inputs reach the handler with defaults applied and
+    /// values converted.
+    pub fn with_schema(mut self, schema: InputSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Returns the worker configuration.
+    pub fn config(&self) -> &WorkerConfig {
+        &self.config
+    }
+
+    /// Runs the worker until `SIGINT` or `SIGTERM` is received.
+    pub async fn run(self) -> Result<()> {
+        self.run_until(shutdown_signal()).await
+    }
+
+    /// Runs the worker until `shutdown` completes.
+    ///
+    /// Jobs in progress when `shutdown` completes are finished and their
+    /// results posted before this returns.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(worker_id = %self.config.worker_id())))]
+    pub async fn run_until(self, shutdown: impl Future<Output = ()>) -> Result<()> {
+        let client = Client::builder().timeout(self.config.timeout()).build()?;
+        let transport = Arc::new(Transport {
+            client,
+            in_progress: Mutex::new(BTreeSet::new()),
+            schema: self.schema,
+            config: self.config,
+        });
+        let handler = Arc::new(self.handler);
+
+        let heartbeat = transport
+            .config
+            .ping_url()
+            .map(|url| tokio::spawn(Arc::clone(&transport).heartbeat(url)));
+
+        let mut shutdown = pin!(shutdown);
+        let mut jobs = JoinSet::new();
+        let mut concurrency = transport.config.concurrency();
+
+        loop {
+            while jobs.try_join_next().is_some() {}
+            concurrency = self.modifier.modify(concurrency).max(1);
+
+            if jobs.len() >= concurrency {
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = jobs.join_next() => continue,
+                }
+            }
+
+            let taken = tokio::select! {
+                _ = &mut shutdown => break,
+                taken = transport.take_job(!jobs.is_empty()) => taken,
+            };
+
+            let delay = match taken {
+                Ok(Some(job)) => {
+                    jobs.spawn(Arc::clone(&transport).process(Arc::clone(&handler), job));
+                    continue;
+                }
+                Ok(None) => transport.config.poll_interval(),
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "Failed to take job");
+                    ERROR_BACKOFF
+                }
+            };
+
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(in_progress = jobs.len(), "Shutting down worker");
+
+        while jobs.join_next().await.is_some() {}
+        if let Some(heartbeat) = heartbeat {
+            heartbeat.abort();
+        }
+
+        Ok(())
+    }
+}
+
+impl<I, P, M> fmt::Debug for Worker<I, P, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Worker")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Job server connection shared between the worker loop, jobs and heartbeats.
+pub struct Transport {
+    pub(crate) config: WorkerConfig,
+    client: Client,
+    schema: Option<InputSchema>,
+    in_progress: Mutex<BTreeSet<String>>,
+}
+
+impl Transport {
+    /// Takes the next job, or `None` if the job server has none.
+    async fn take_job(&self, job_in_progress: bool) -> Result<Option<Job<Value>>> {
+        let response = self
+            .client
+            .get(self.config.job_take_url())
+            .header("Authorization", self.config.api_key())
+            .query(&[("job_in_progress", if job_in_progress { "1" } else { "0" })])
+            .send()
+            .await?;
+
+        match response.status() {
+            // No job available, or the job server asks to back off.
+            StatusCode::NO_CONTENT | StatusCode::BAD_REQUEST | StatusCode::TOO_MANY_REQUESTS => {
+                Ok(None)
+            }
+            _ => {
+                let body = response.error_for_status()?.bytes().await?;
+                if body.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(serde_json::from_slice(&body)?))
+            }
+        }
+    }
+
+    /// Runs `job` through the handler and posts its result.
+    async fn process<I, P>(self: Arc<Self>, handler: Arc<P>, job: Job<Value>)
+    where
+        I: DeserializeOwned,
+        P: Process<I>,
+    {
+        let job_id = job.id.clone();
+        self.track(&job_id, true);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(job_id = %job_id, "Processing job");
+
+        let result = match self.validate(job.input) {
+            Ok(input) => match serde_json::from_value(input) {
+                Ok(input) => handler.process(Job { id: job.id, input }, &self).await,
+                Err(e) => Err(format!("Invalid job input: {e}")),
+            },
+            Err(errors) => Err(errors),
+        };
+
+        let body = match result {
+            Ok(output) => serde_json::json!({ "output": output }),
+            Err(error) => serde_json::json!({ "error": error }),
+        };
+
+        let url = self.config.job_done_url(&job_id);
+        if let Err(_e) = self.post(&url, handler.is_streaming(), &body).await {
+            #[cfg(feature = "tracing")]
+            tracing::error!(job_id = %job_id, error = %_e, "Failed to post job result");
+        }
+
+        self.track(&job_id, false);
+    }
+
+    /// Validates job input against the schema, if any.
+    fn validate(&self, input: Value) -> Result<Value, String> {
+        match &self.schema {
+            Some(schema) => schema.validate(input).map_err(|errors| {
+                serde_json::to_string(&errors).unwrap_or_else(|_| errors.to_string())
+            }),
+            None => Ok(input),
+        }
+    }
+
+    /// Posts a partial output of a streaming job.
+    ///
+    /// Does nothing if no stream URL is configured.
+    pub(crate) async fn post_stream(&self, job_id: &str, output: &Value) -> Result<()> {
+        match self.config.stream_url(job_id) {
+            Some(url) => {
+                let body = serde_json::json!({ "output": output });
+                self.post(&url, true, &body).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Posts a job output to `url`, retrying transient failures.
+    async fn post(&self, url: &str, is_stream: bool, body: &Value) -> Result<()> {
+        let is_stream = if is_stream { "true" } else { "false" };
+        let mut attempt = 1;
+        loop {
+            let result = self
+                .client
+                .post(url)
+                .header("Authorization", self.config.api_key())
+                .query(&[("isStream", is_stream)])
+                .json(body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            match result {
+                Ok(_) => return Ok(()),
+                Err(e) if attempt >= POST_ATTEMPTS => return Err(e.into()),
+                Err(_) => {
+                    tokio::time::sleep(ERROR_BACKOFF * attempt).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Sends heartbeats listing the jobs in progress until aborted.
+    async fn heartbeat(self: Arc<Self>, url: String) {
+        let mut interval = tokio::time::interval(self.config.ping_interval());
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            let job_ids = self.job_ids().join(",");
+            let result = self
+                .client
+                .get(&url)
+                .header("Authorization", self.config.api_key())
+                .query(&[("job_id", job_ids.as_str()), ("retry_ping", "0")])
+                .timeout(self.config.ping_interval())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            if let Err(_e) = result {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_e, "Failed to send heartbeat");
+            }
+        }
+    }
+
+    /// Marks a job as in progress or done.
+    fn track(&self, job_id: &str, in_progress: bool) {
+        let mut jobs = self.in_progress.lock().unwrap_or_else(|e| e.into_inner());
+        if in_progress {
+            jobs.insert(job_id.to_string());
+        } else {
+            jobs.remove(job_id);
+        }
+    }
+
+    /// Returns the IDs of the jobs in progress.
+    fn job_ids(&self) -> Vec<String> {
+        let jobs = self.in_progress.lock().unwrap_or_else(|e| e.into_inner());
+        jobs.iter().cloned().collect()
+    }
+}
+
+/// Completes when `SIGINT` or, on Unix, `SIGTERM` is received.
+pub(crate) async fn shutdown_signal() {
+    #[cfg(unix)]
+    if let Ok(mut terminate) =
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+    {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+        return;
+    }
+
+    if tokio::signal::ctrl_c().await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use axum::Router;
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{StatusCode, Uri};
+    use axum::routing::{get, post};
+    use futures::StreamExt;
+    use serde::Deserialize;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+    use tokio::sync::Notify;
+
+    use super::*;
+    use crate::worker::WorkerBuilder;
+
+    /// Stand-in job server handing out queued jobs and recording results.
+    #[derive(Default)]
+    struct JobServer {
+        queue: Mutex<VecDeque<Value>>,
+        results: Mutex<Vec<(String, Value)>>,
+        posted: Notify,
+    }
+
+    impl JobServer {
+        async fn start(jobs: Vec<Value>) -> (Arc<Self>, WorkerBuilder) {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            let server = Arc::new(Self {
+                queue: Mutex::new(jobs.into()),
+                ..Default::default()
+            });
+
+            let app = Router::new()
+                .route("/job-take/{worker_id}", get(Self::take))
+                .route("/job-done/{worker_id}/{job_id}", post(Self::record))
+                .route("/job-stream/{worker_id}/{job_id}", post(Self::record))
+                .with_state(Arc::clone(&server));
+            tokio::spawn(async move { axum::serve(listener, app).await });
+
+            let config = WorkerConfig::builder()
+                .with_api_key("key")
+                .with_worker_id("worker")
+                .with_job_take_url(format!("http://{address}/job-take/$ID"))
+                .with_job_done_url(format!("http://{address}/job-done/$RUNPOD_POD_ID/$ID"))
+                .with_stream_url(format!("http://{address}/job-stream/$RUNPOD_POD_ID/$ID"))
+                .with_concurrency(2usize)
+                .with_poll_interval(Duration::from_millis(10));
+
+            (server, config)
+        }
+
+        async fn take(State(server): State<Arc<Self>>) -> (StatusCode, String) {
+            match server.queue.lock().unwrap().pop_front() {
+                Some(job) => (StatusCode::OK, job.to_string()),
+                None => (StatusCode::NO_CONTENT, String::new()),
+            }
+        }
+
+        async fn record(State(server): State<Arc<Self>>, uri: Uri, body: Bytes) {
+            let result = serde_json::from_slice(&body).unwrap();
+            let target = uri.path_and_query().unwrap().to_string();
+            server.results.lock().unwrap().push((target, result));
+            server.posted.notify_one();
+        }
+
+        async fn wait_for_results(&self, count: usize) -> Vec<(String, Value)> {
+            loop {
+                let results = self.results.lock().unwrap().clone();
+                if results.len() >= count {
+                    return results;
+                }
+                self.posted.notified().await;
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Input {
+        value: i64,
+    }
+
+    async fn double(job: Job<Input>) -> Result<i64, String> {
+        if job.input.value < 0 {
+            return Err("negative value".to_string());
+        }
+        Ok(job.input.value * 2)
+    }
+
+    #[tokio::test]
+    async fn test_processes_jobs_and_posts_results() {
+        let (server, config) = JobServer::start(vec![
+            json!({"id": "a", "input": {"value": 1}}),
+            json!({"id": "b", "input": {"value": -1}}),
+            json!({"id": "c", "input": "invalid"}),
+        ])
+        .await;
+
+        let worker = Worker::new(config.build().unwrap(), double);
+        let state = Arc::clone(&server);
+        worker
+            .run_until(async move {
+                state.wait_for_results(3).await;
+            })
+            .await
+            .unwrap();
+
+        let mut results = server.results.lock().unwrap().clone();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(results[0].0, "/job-done/worker/a?isStream=false");
+        assert_eq!(results[0].1, json!({"output": 2}));
+        assert_eq!(results[1].1, json!({"error": "negative value"}));
+        assert!(
+            results[2].1["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("Invalid job input")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finishes_jobs_in_progress_on_shutdown() {
+        let (server, config) =
+            JobServer::start(vec![json!({"id": "slow", "input": {"value": 2}})]).await;
+
+        let started = Arc::new(Notify::new());
+        let handler = {
+            let started = Arc::clone(&started);
+            move |job: Job<Input>| {
+                let started = Arc::clone(&started);
+                async move {
+                    started.notify_one();
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    double(job).await
+                }
+            }
+        };
+
+        Worker::new(config.build().unwrap(), handler)
+            .with_concurrency_modifier(|_| 1)
+            .run_until(async move { started.notified().await })
+            .await
+            .unwrap();
+
+        let results = server.results.lock().unwrap().clone();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, json!({"output": 4}));
+    }
+
+    #[tokio::test]
+    async fn test_streams_partial_outputs() {
+        let (server, config) = JobServer::start(vec![
+            json!({"id": "a", "input": {"value": 3}}),
+            json!({"id": "b", "input": {"value": -1}}),
+        ])
+        .await;
+
+        let handler = |job: Job<Input>| {
+            let value = job.input.value;
+            futures::stream::iter(0..value.abs()).map(move |i| {
+                if value < 0 {
+                    Err("negative value")
+                } else {
+                    Ok(i)
+                }
+            })
+        };
+
+        let config = config.with_aggregate_stream(true).build().unwrap();
+        let state = Arc::clone(&server);
+        Worker::streaming(config, handler)
+            .run_until(async move {
+                state.wait_for_results(5).await;
+            })
+            .await
+            .unwrap();
+
+        let results = server.results.lock().unwrap().clone();
+        let posted = |prefix: &str| -> Vec<Value> {
+            results
+                .iter()
+                .filter(|(target, _)| target.starts_with(prefix))
+                .map(|(_, body)| body.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            posted("/job-stream/worker/a?isStream=true"),
+            vec![
+                json!({"output": 0}),
+                json!({"output": 1}),
+                json!({"output": 2})
+            ]
+        );
+        assert_eq!(
+            posted("/job-done/worker/a?isStream=true"),
+            vec![json!({"output": [0, 1, 2]})]
+        );
+        assert!(posted("/job-stream/worker/b").is_empty());
+        assert_eq!(
+            posted("/job-done/worker/b"),
+            vec![json!({"error": "negative value"})]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_input_failing_schema() {
+        let (server, config) = JobServer::start(vec![
+            json!({"id": "a", "input": {"value": "4"}}),
+            json!({"id": "b", "input": {"other": 1}}),
+        ])
+        .await;
+
+        let schema = crate::worker::InputSchema::new()
+            .field("value", crate::worker::Field::integer().required());
+        let state = Arc::clone(&server);
+        Worker::new(config.build().unwrap(), double)
+            .with_schema(schema)
+            .run_until(async move {
+                state.wait_for_results(2).await;
+            })
+            .await
+            .unwrap();
+
+        let mut results = server.results.lock().unwrap().clone();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(results[0].1, json!({"output": 8}));
+        let error: Value = serde_json::from_str(results[1].1["error"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            error,
+            json!({"errors": [
+                {"field": "other", "message": "is not a valid input option"},
+                {"field": "value", "message": "is required"},
+            ]})
+        );
+    }
+}
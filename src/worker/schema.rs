@@ -0,0 +1,455 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Type of a job input field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    /// A string. Numbers and booleans are converted to strings.
+    String,
+    /// An integer. Whole floats and numeric strings are converted.
+    Integer,
+    /// A number. Numeric strings are converted.
+    Number,
+    /// A boolean. The strings `"true"` and `"false"` are converted.
+    Boolean,
+    /// An array.
+    Array,
+    /// An object.
+    Object,
+    /// Any value.
+    Any,
+}
+
+impl FieldType {
+    /// Converts `value` to this type, or returns `None` if it cannot be.
+    fn coerce(self, value: Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Any, value) => Some(value),
+            (Self::String, value @ Value::String(_)) => Some(value),
+            (Self::String, Value::Number(n)) => Some(Value::String(n.to_string())),
+            (Self::String, Value::Bool(b)) => Some(Value::String(b.to_string())),
+            (Self::Integer, Value::Number(n)) => {
+                if n.is_i64() || n.is_u64() {
+                    Some(Value::Number(n))
+                } else {
+                    n.as_f64()
+                        .filter(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+                        .map(|f| Value::from(f as i64))
+                }
+            }
+            (Self::Integer, Value::String(s)) => s.trim().parse::<i64>().ok().map(Value::from),
+            (Self::Number, value @ Value::Number(_)) => Some(value),
+            (Self::Number, Value::String(s)) => s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            (Self::Boolean, value @ Value::Bool(_)) => Some(value),
+            (Self::Boolean, Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            (Self::Array, value @ Value::Array(_)) => Some(value),
+            (Self::Object, value @ Value::Object(_)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::String => "a string",
+            Self::Integer => "an integer",
+            Self::Number => "a number",
+            Self::Boolean => "a boolean",
+            Self::Array => "an array",
+            Self::Object => "an object",
+            Self::Any => "any value",
+        };
+        f.write_str(name)
+    }
+}
+
+type Check = Arc<dyn Fn(&Value) -> bool + Send + Sync>;
+
+/// Schema of a single job input field.
+///
+/// Fields are optional unless marked [`required`](Self::required). Missing
+/// optional fields take their [`default`](Self::default) if one is set.
+#[derive(Clone)]
+pub struct Field {
+    field_type: FieldType,
+    required: bool,
+    default: Option<Value>,
+    constraints: Vec<(String, Check)>,
+}
+
+impl Field {
+    /// Creates a field of type `field_type`.
+    pub fn new(field_type: FieldType) -> Self {
+        Self {
+            field_type,
+            required: false,
+            default: None,
+            constraints: Vec::new(),
+        }
+    }
+
+    /// Creates a string field.
+    pub fn string() -> Self {
+        Self::new(FieldType::String)
+    }
+
+    /// Creates an integer field.
+    pub fn integer() -> Self {
+        Self::new(FieldType::Integer)
+    }
+
+    /// Creates a number field.
+    pub fn number() -> Self {
+        Self::new(FieldType::Number)
+    }
+
+    /// Creates a boolean field.
+    pub fn boolean() -> Self {
+        Self::new(FieldType::Boolean)
+    }
+
+    /// Creates an array field.
+    pub fn array() -> Self {
+        Self::new(FieldType::Array)
+    }
+
+    /// Creates an object field.
+    pub fn object() -> Self {
+        Self::new(FieldType::Object)
+    }
+
+    /// Creates a field accepting any value.
+    pub fn any() -> Self {
+        Self::new(FieldType::Any)
+    }
+
+    /// Marks the field as required.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Sets the value used when the field is missing.
+    pub fn default(mut self, value: impl Into<Value>) -> Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Requires a number field to be at least `min`.
+    pub fn min(self, min: f64) -> Self {
+        self.constraint(format!("must be at least {min}"), move |value| {
+            value.as_f64().is_some_and(|value| value >= min)
+        })
+    }
+
+    /// Requires a number field to be at most `max`.
+    pub fn max(self, max: f64) -> Self {
+        self.constraint(format!("must be at most {max}"), move |value| {
+            value.as_f64().is_some_and(|value| value <= max)
+        })
+    }
+
+    /// Requires the field to be one of `values`.
+    pub fn one_of<V: Into<Value>>(self, values: impl IntoIterator<Item = V>) -> Self {
+        let values: Vec<Value> = values.into_iter().map(Into::into).collect();
+        let message = format!("must be one of {}", Value::Array(values.clone()));
+        self.constraint(message, move |value| values.contains(value))
+    }
+
+    /// Requires the field to satisfy `check`, failing with `message` otherwise.
+    ///
+    /// Constraints run on the converted value.
+    pub fn constraint<F>(mut self, message: impl Into<String>, check: F) -> Self
+    where
+        F: Fn(&Value) -> bool + Send + Sync + 'static,
+    {
+        self.constraints.push((message.into(), Arc::new(check)));
+        self
+    }
+
+    /// Returns the type of the field.
+    pub fn field_type(&self) -> FieldType {
+        self.field_type
+    }
+
+    /// Returns whether the field is required.
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+impl fmt::Debug for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let constraints: Vec<&str> = self.constraints.iter().map(|(m, _)| m.as_str()).collect();
+        f.debug_struct("Field")
+            .field("field_type", &self.field_type)
+            .field("required", &self.required)
+            .field("default", &self.default)
+            .field("constraints", &constraints)
+            .finish()
+    }
+}
+
+/// Declarative schema validating job input.
+///
+/// Validation applies defaults, converts values to the declared types,
+/// rejects fields not in the schema, and collects every error instead of
+/// stopping at the first one. Attach a schema to a worker with
+/// [`Worker::with_schema`](super::Worker::with_schema) to reject invalid jobs
+/// before they reach the handler.
+///
+/// # Examples
+///
+/// ```rust
+/// use runpod_sdk::worker::{Field, InputSchema};
+/// use serde_json::json;
+///
+/// let schema = InputSchema::new()
+///     .field("prompt", Field::string().required())
+///     .field("steps", Field::integer().default(20).min(1.0).max(150.0))
+///     .field("scheduler", Field::string().default("ddim").one_of(["ddim", "euler"]));
+///
+/// let input = schema.validate(json!({"prompt": "a cat", "steps": "30"})).unwrap();
+/// assert_eq!(input, json!({"prompt": "a cat", "steps": 30, "scheduler": "ddim"}));
+///
+/// let errors = schema.validate(json!({"steps": 0})).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InputSchema {
+    fields: BTreeMap<String, Field>,
+}
+
+impl InputSchema {
+    /// Creates an empty schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a field to the schema.
+    pub fn field(mut self, name: impl Into<String>, field: Field) -> Self {
+        self.fields.insert(name.into(), field);
+        self
+    }
+
+    /// Returns the fields of the schema by name.
+    pub fn fields(&self) -> &BTreeMap<String, Field> {
+        &self.fields
+    }
+
+    /// Validates `input`, returning it with defaults applied and values converted.
+    pub fn validate(&self, input: Value) -> Result<Value, ValidationErrors> {
+        let mut input = match input {
+            Value::Object(input) => input,
+            Value::Null => Map::new(),
+            _ => {
+                return Err(ValidationErrors::from(vec![ValidationError::new(
+                    "input",
+                    "must be an object",
+                )]));
+            }
+        };
+
+        let mut errors = Vec::new();
+        for name in input.keys() {
+            if !self.fields.contains_key(name) {
+                errors.push(ValidationError::new(name, "is not a valid input option"));
+            }
+        }
+
+        let mut validated = Map::new();
+        for (name, field) in &self.fields {
+            let value = match input.remove(name) {
+                Some(Value::Null) | None => match (&field.default, field.required) {
+                    (_, true) => {
+                        errors.push(ValidationError::new(name, "is required"));
+                        continue;
+                    }
+                    (Some(default), false) => default.clone(),
+                    (None, false) => continue,
+                },
+                Some(value) => match field.field_type.coerce(value) {
+                    Some(value) => value,
+                    None => {
+                        let message = format!("must be {}", field.field_type);
+                        errors.push(ValidationError::new(name, message));
+                        continue;
+                    }
+                },
+            };
+
+            for (message, check) in &field.constraints {
+                if !check(&value) {
+                    errors.push(ValidationError::new(name, message.clone()));
+                }
+            }
+            validated.insert(name.clone(), value);
+        }
+
+        match errors.is_empty() {
+            true => Ok(Value::Object(validated)),
+            false => Err(ValidationErrors::from(errors)),
+        }
+    }
+}
+
+/// A job input field that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationError {
+    /// The name of the field.
+    pub field: String,
+    /// Why the field is invalid.
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Creates a validation error for `field`.
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.message)
+    }
+}
+
+/// All validation errors of a job input.
+///
+/// Serializes as `{"errors": [{"field": .., "message": ..}]}`, which is how a
+/// [`Worker`](super::Worker) reports invalid input as the job error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("invalid job input: {}", display_errors(.errors))]
+pub struct ValidationErrors {
+    /// The individual field errors.
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    /// Returns the number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl From<Vec<ValidationError>> for ValidationErrors {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        Self { errors }
+    }
+}
+
+fn display_errors(errors: &[ValidationError]) -> String {
+    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    errors.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> InputSchema {
+        InputSchema::new()
+            .field("prompt", Field::string().required())
+            .field("steps", Field::integer().default(20).min(1.0).max(150.0))
+            .field("guidance", Field::number().default(7.5))
+            .field("upscale", Field::boolean().default(false))
+            .field("sampler", Field::string().one_of(["ddim", "euler"]))
+            .field("tags", Field::array())
+    }
+
+    #[test]
+    fn test_applies_defaults_and_coerces_types() {
+        let input = json!({
+            "prompt": 42,
+            "steps": "30",
+            "guidance": "3",
+            "upscale": "TRUE",
+            "tags": ["a"],
+        });
+
+        assert_eq!(
+            schema().validate(input).unwrap(),
+            json!({
+                "prompt": "42",
+                "steps": 30,
+                "guidance": 3.0,
+                "upscale": true,
+                "tags": ["a"],
+            })
+        );
+        assert_eq!(
+            schema()
+                .validate(json!({"prompt": "cat", "steps": 12.0}))
+                .unwrap()["steps"],
+            json!(12)
+        );
+    }
+
+    #[test]
+    fn test_collects_all_errors() {
+        let input = json!({
+            "steps": 500,
+            "guidance": "high",
+            "sampler": "plms",
+            "tags": "a",
+            "seed": 1,
+        });
+
+        let errors = schema().validate(input).unwrap_err();
+        let errors: Vec<(&str, &str)> = errors
+            .errors
+            .iter()
+            .map(|e| (e.field.as_str(), e.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("seed", "is not a valid input option"),
+                ("guidance", "must be a number"),
+                ("prompt", "is required"),
+                ("sampler", "must be one of [\"ddim\",\"euler\"]"),
+                ("steps", "must be at most 150"),
+                ("tags", "must be an array"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_object_input() {
+        let errors = schema().validate(json!("cat")).unwrap_err();
+        assert_eq!(
+            errors.errors,
+            vec![ValidationError::new("input", "must be an object")]
+        );
+        assert_eq!(
+            serde_json::to_value(&errors).unwrap(),
+            json!({"errors": [{"field": "input", "message": "must be an object"}]})
+        );
+    }
+}